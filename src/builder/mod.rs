@@ -0,0 +1,178 @@
+use std::env;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use super::{Graphic, GraphicError, Result};
+use super::{SPEC_ROOT, SPEC_ROOT_DEFAULT, SPEC_SUBD_NCT, SPEC_SUBD_NCS};
+use super::{SPEC_EXT_NCT, SPEC_EXT_NCS};
+use super::sheet::Sheet;
+
+/// The builder `GraphicBuilder` prepares a Graphic from an explicit
+/// root directory and/or from readable sources of texel and sprite.
+pub struct GraphicBuilder {
+    /// The root with the texel and sprite sub-directories.
+    root: Option<PathBuf>,
+    /// The readable sources of texel.
+    texel: Vec<Box<Read>>,
+    /// The readable sources of sprite by Sheet.
+    sprite: Vec<(Sheet, Box<Read>)>,
+    /// Creates the missing sub-directories of the root.
+    mkdir: bool,
+}
+
+impl GraphicBuilder {
+    /// The constructor `new` returns a builder without source.
+    pub fn new() -> Self {
+        GraphicBuilder::default()
+    }
+
+    /// The accessor method `get_env_root` returns the root given by
+    /// the `NEKO_PATH` environment variable or the default assets.
+    pub fn get_env_root() -> PathBuf {
+        env::var(SPEC_ROOT).ok()
+            .and_then(|repertory: String|
+                      Some(PathBuf::from(repertory)))
+            .unwrap_or_else(||
+                      PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                                        .join(SPEC_ROOT_DEFAULT))
+    }
+
+    /// The method `root` sets the directory with the texel and
+    /// sprite sub-directories.
+    pub fn root<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.root = Some(root.as_ref().to_path_buf());
+        self
+    }
+
+    /// The method `texel` adds a readable source of texel.
+    pub fn texel<R: Read + 'static>(mut self, source: R) -> Self {
+        self.texel.push(Box::new(source));
+        self
+    }
+
+    /// The method `sprite` adds a readable source of sprite
+    /// for the Sheet key.
+    pub fn sprite<R: Read + 'static>(mut self, sheet: Sheet, source: R) -> Self {
+        self.sprite.push((sheet, Box::new(source)));
+        self
+    }
+
+    /// The method `create_dir` sets if the missing sub-directories
+    /// of the root are created.
+    pub fn create_dir(mut self, mkdir: bool) -> Self {
+        self.mkdir = mkdir;
+        self
+    }
+
+    /// The function `mkdir` creates the sub-directory if it's
+    /// allowed and missing.
+    fn mkdir(&self, path: &Path, error: fn(io::Error) -> GraphicError
+    ) -> Result<()> {
+        if self.mkdir {
+            match fs::create_dir_all(path) {
+                Err(ref why) if why.kind().eq(&io::ErrorKind::AlreadyExists) => Ok(()),
+                Err(why) => Err(error(why)),
+                Ok(()) => Ok(()),
+            }
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The method `build` returns the Graphic loaded with the texels
+    /// before the sprites, from the root and then from the sources,
+    /// and with the expressions and transitions of the root.
+    pub fn build(self) -> Result<Graphic> {
+        let mut manager: Graphic = Graphic::default();
+        let entries: Result<(Vec<PathBuf>, Vec<PathBuf>)> = match self.root {
+            None => Ok((Vec::new(), Vec::new())),
+            Some(ref root) => {
+                let (nct, ncs) = (root.join(SPEC_SUBD_NCT), root.join(SPEC_SUBD_NCS));
+
+                self.mkdir(&nct, GraphicError::MkDirTexel)
+                    .and(self.mkdir(&ncs, GraphicError::MkDirSprite))
+                    .and_then(|()|
+                        match (fs::read_dir(&nct), fs::read_dir(&ncs)) {
+                            (Err(why), _) => Err(GraphicError::ReadDir(nct.clone(), why)),
+                            (_, Err(why)) => Err(GraphicError::ReadDir(ncs.clone(), why)),
+                            (Ok(entry_nct), Ok(entry_ncs)) => Ok((
+                                entry_nct.filter_map(|texel| texel.ok())
+                                         .map(|entry| entry.path())
+                                         .filter(|path|
+                                             path.extension().eq(&Some(OsStr::new(SPEC_EXT_NCT))))
+                                         .collect::<Vec<PathBuf>>(),
+                                entry_ncs.filter_map(|sprite| sprite.ok())
+                                         .map(|entry| entry.path())
+                                         .filter(|path|
+                                             path.extension().eq(&Some(OsStr::new(SPEC_EXT_NCS))))
+                                         .collect::<Vec<PathBuf>>(),
+                            )),
+                        })
+            },
+        };
+        let GraphicBuilder { root, texel, sprite, mkdir } = self;
+
+        manager.root = root;
+        manager.mkdir = mkdir;
+        entries.and_then(|(entry_nct, entry_ncs)|
+            if let Some(why) = entry_nct.iter()
+                                        .filter_map(|path|
+                                             manager.insert_from_texelfile(path).err())
+                                        .next() {
+                Err(why)
+            } else if let Some(why) = texel.into_iter()
+                                           .filter_map(|source|
+                                                manager.insert_from_texel(source).err())
+                                           .next() {
+                Err(why)
+            } else if let Some(why) = manager.root.clone()
+                                             .and_then(|_| manager.read_templatefile().err()) {
+                Err(why)
+            } else if let Some(why) = entry_ncs.iter()
+                                               .filter_map(|path|
+                                                    manager.insert_from_spritefile(path).err())
+                                               .next() {
+                Err(why)
+            } else if let Some(why) = sprite.into_iter()
+                                            .filter_map(|(sheet, source)|
+                                                 manager.insert_from_sprite(sheet, source).err())
+                                            .next() {
+                Err(why)
+            } else if manager.root.is_some() {
+                manager.read_expressionfile()
+                       .and(manager.read_transitionfile())
+                       .and(manager.read_effectfile())
+                       .and(Ok(manager))
+            } else {
+                Ok(manager)
+            })
+    }
+}
+
+impl fmt::Debug for GraphicBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GraphicBuilder {{ root: {:?}, texel: {}, sprite: {:?}, mkdir: {} }}",
+               self.root,
+               self.texel.len(),
+               self.sprite.iter()
+                          .map(|&(sheet, _)| sheet)
+                          .collect::<Vec<Sheet>>(),
+               self.mkdir)
+    }
+}
+
+impl Default for GraphicBuilder {
+    /// The constructor `default` returns a builder without root,
+    /// source nor directory creation.
+    fn default() -> GraphicBuilder {
+        GraphicBuilder {
+            root: None,
+            texel: Vec::new(),
+            sprite: Vec::new(),
+            mkdir: false,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+
+use ::editeur::GraphicError;
+use ::editeur::sheet::SheetError;
+use ::editeur::sprite::texel::fallback::FallbackError;
+
+pub type Result<T> = ::std::result::Result<T, CommandError>;
+
+/// The enum `CommandError` defines the possible errors from constructor Command.
+#[derive(Debug)]
+pub enum CommandError {
+    /// Can't write on the output.
+    Write(io::Error),
+    /// The Graphic interface has meet an error.
+    Graphic(GraphicError),
+    /// The Sheet interface has meet an error.
+    Sheet(SheetError),
+    /// The Fallback interface has meet an error.
+    Fallback(FallbackError),
+    /// Can't parse the number argument.
+    Number(ParseIntError),
+    /// Unknown sub-command.
+    UnknownCommand(String),
+    /// Unknown list.
+    UnknownList(String),
+    /// Missing argument.
+    MissingArgument(&'static str),
+    /// Can't found the sprite.
+    FoundSprite(String),
+    /// Can't found the draw.
+    FoundDraw(usize),
+    /// The validation has found invalid files.
+    Invalid(usize),
+    /// The sprites are different.
+    Differ(usize),
+    /// The merge has left conflicts.
+    Conflict(usize),
+    /// Can't listen on the socket.
+    Socket(io::Error),
+}
+
+impl fmt::Display for CommandError {
+    /// The function `fmt` formats the value using the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CommandError::Write(_) => write!(f, "can't write on the output"),
+            CommandError::Graphic(_) => write!(f, "can't load the root"),
+            CommandError::Sheet(_) => write!(f, "the sheet is invalid"),
+            CommandError::Fallback(_) => write!(f, "the fallback is invalid"),
+            CommandError::Number(_) => write!(f, "can't parse the number argument"),
+            CommandError::UnknownCommand(ref name) => {
+                write!(f, "the command `{}` is unknown", name)
+            },
+            CommandError::UnknownList(ref name) => write!(f, "the list `{}` is unknown", name),
+            CommandError::MissingArgument(name) => write!(f, "the argument `{}` is missing", name),
+            CommandError::FoundSprite(ref name) => write!(f, "can't find the sprite `{}`", name),
+            CommandError::FoundDraw(index) => write!(f, "can't find the draw {}", index),
+            CommandError::Invalid(count) => write!(f, "{} invalid entries were found", count),
+            CommandError::Differ(count) => write!(f, "the sprites differ by {} changes", count),
+            CommandError::Conflict(count) => write!(f, "the merge has left {} conflicts", count),
+            CommandError::Socket(_) => write!(f, "can't listen on the socket"),
+        }
+    }
+}
+
+impl Error for CommandError {
+    /// The function `description` returns a short description of the error.
+    fn description(&self) -> &str {
+        match *self {
+            CommandError::Write(_) => "Can't write on the output.",
+            CommandError::Graphic(_) => "The Graphic interface has meet an error.",
+            CommandError::Sheet(_) => "The Sheet interface has meet an error.",
+            CommandError::Fallback(_) => "The Fallback interface has meet an error.",
+            CommandError::Number(_) => "Can't parse the number argument.",
+            CommandError::UnknownCommand(ref name) => name,
+            CommandError::UnknownList(ref name) => name,
+            CommandError::MissingArgument(name) => name,
+            CommandError::FoundSprite(ref name) => name,
+            CommandError::FoundDraw(_) => "Can't found the draw.",
+            CommandError::Invalid(_) => "The validation has found invalid files.",
+            CommandError::Differ(_) => "The sprites are different.",
+            CommandError::Conflict(_) => "The merge has left conflicts.",
+            CommandError::Socket(_) => "Can't listen on the socket.",
+        }
+    }
+
+    /// The function `source` returns the lower-level source of this error, if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            CommandError::Write(ref err) => Some(err),
+            CommandError::Graphic(ref err) => Some(err),
+            CommandError::Sheet(ref err) => Some(err),
+            CommandError::Fallback(ref err) => Some(err),
+            CommandError::Number(ref err) => Some(err),
+            CommandError::Socket(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CommandError {
+    fn from(why: io::Error) -> CommandError {
+        CommandError::Write(why)
+    }
+}
+
+impl From<GraphicError> for CommandError {
+    fn from(why: GraphicError) -> CommandError {
+        CommandError::Graphic(why)
+    }
+}
@@ -0,0 +1,925 @@
+mod err;
+
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, Write};
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+
+pub use self::err::{CommandError, Result};
+
+use super::termion;
+use super::server;
+use super::lsp;
+
+use editeur::{Graphic, GraphicBuilder, GraphicError};
+use editeur::lint;
+use editeur::coverage::Coverage;
+use editeur::glyph::GlyphMap;
+use editeur::tuple::Tuple;
+use editeur::{SPEC_SUBD_NCT, SPEC_SUBD_NCS, SPEC_EXT_NCT, SPEC_EXT_NCS};
+use editeur::{SPEC_FILE_NCP, SPEC_EXT_NCP};
+use editeur::emotion::Emotion;
+use editeur::expression;
+use editeur::transition;
+use editeur::template;
+use editeur::effect;
+use editeur::sheet::Sheet;
+use editeur::sprite::Sprite;
+use editeur::sprite::diff::Diff;
+use editeur::sprite::merge::{self, Merge};
+use editeur::sprite::draw::{Color, Draw, Fallback, Layer, SPEC_MAX_X};
+use editeur::sprite::texel::Texel;
+use editeur::sprite::texel::part::Part;
+
+/// The usage of the binary.
+pub const SPEC_USAGE: &'static str = "\
+Usage: editeur [--root <directory>] [--fallback <Glyph|Code|Ascii|Block>] [<command>]
+
+Without command, the interactive editor is started. Without fallback, the
+rendering of the glyphs is the variable NEKO_FALLBACK or else is probed from
+the locale and the terminal, and the editor measures the width of a private
+glyph; the font can't be asked, so a glyph missing from the font is only seen
+by the user who chooses another rendering.
+
+Commands:
+    validate                        Checks every texel and sprite file.
+    lint [<file>...]                Reports the pairs, glyphs and draws dropped
+                                    by the loader in the sprite files.
+    list [sheets|parts|emotions|expressions|transitions|templates|effects|colors]
+                                    Lists the names of the dictionaries or the
+                                    expressions, transitions, templates and
+                                    effects of the root.
+    show <Sheet>                    Prints the sprite like the editor.
+    texels <Sheet>                  Prints the texels of the posture with the
+                                    parent which declares the inherited ones.
+    coverage [--csv|--html] [--all] Prints the matrix of Part:Emotion texels
+                                    by posture.
+    glyphs [--collisions] [--free <n>]
+                                    Prints the owners of each glyph, only the
+                                    collisions or n suggested free glyphs.
+    render <Sheet> [<draw>] [--effect <Effect>]...
+                                    Prints the glyphs of the sprite or of a draw,
+                                    with the effects played since the first draw.
+    convert <file> [<output>]       Rewrites a sprite file in the canonical format.
+    expand <Template> <Sheet> [<output>]
+                                    Writes the sprite with the frames of the
+                                    template expanded from each draw.
+    diff <file> <file>              Prints the changes by draw and by cell with
+                                    the changed draws side by side.
+    merge <base> <ours> <theirs> [<path>]
+                                    Merges three sprite files in ours, the
+                                    conflicts keep ours and are written in
+                                    <path>.conflict for the editor.
+    lsp                             Starts the language server of the texel
+                                    and sprite files on the standard input.
+    serve <socket>                  Answers the line-delimited JSON requests
+                                    on the Unix socket.
+    help                            Prints this message.
+";
+
+/// The enum `List` defines the dictionaries of names.
+#[derive(Clone, Copy, Debug)]
+pub enum List {
+    Sheet,
+    Part,
+    Emotion,
+    Expression,
+    Transition,
+    Template,
+    Effect,
+    Color,
+}
+
+/// The enum `Format` defines the outputs of a report.
+#[derive(Clone, Copy, Debug)]
+pub enum Format {
+    Text,
+    Csv,
+    Html,
+}
+
+/// The enum `Order` defines the sub-commands.
+#[derive(Clone, Debug)]
+pub enum Order {
+    /// Checks every texel and sprite file.
+    Validate,
+    /// Reports the problems of the sprite files or of every sprite file.
+    Lint(Vec<PathBuf>),
+    /// Lists the names of one or all dictionaries.
+    List(Option<List>),
+    /// Prints the sprite like the editor.
+    Show(Sheet),
+    /// Prints the texels of the posture with their origin.
+    Texel(Sheet),
+    /// Prints the matrix of texels, with or without the unused columns.
+    Coverage(Format, bool),
+    /// Prints the glyph map, only the collisions or free glyphs.
+    Glyph(bool, Option<usize>),
+    /// Prints the glyphs of the sprite or of a draw with the effects.
+    Render(Sheet, Option<usize>, Vec<String>),
+    /// Rewrites a sprite file in the canonical format.
+    Convert(PathBuf, Option<PathBuf>),
+    /// Writes the sprite with the template expanded.
+    Expand(String, Sheet, Option<PathBuf>),
+    /// Prints the changes between two sprite files.
+    Diff(PathBuf, PathBuf),
+    /// Merges the base, our and their sprite files in ours
+    /// with the path of the conflict file.
+    Merge(PathBuf, PathBuf, PathBuf, Option<PathBuf>),
+    /// Answers the requests on the Unix socket.
+    Serve(PathBuf),
+    /// Starts the language server on the standard input.
+    Lsp,
+    /// Prints the usage.
+    Help,
+}
+
+/// The struct `Command` is a headless sub-command with its root
+/// and the rendering of its glyphs.
+#[derive(Clone, Debug)]
+pub struct Command {
+    root: PathBuf,
+    fallback: Fallback,
+    order: Order,
+}
+
+impl Command {
+    /// The constructor `new` returns the root, the rendering of the glyphs
+    /// given by `--fallback` and the sub-command parsed from the arguments,
+    /// without sub-command for the editor.
+    pub fn new<I: Iterator<Item = String>>(
+        arguments: I
+    ) -> Result<(PathBuf, Option<Fallback>, Option<Command>)> {
+        let mut arguments = arguments.collect::<Vec<String>>().into_iter();
+        let mut root: PathBuf = GraphicBuilder::get_env_root();
+        let mut fallback: Option<Fallback> = None;
+        let mut order: Option<String> = arguments.next();
+
+        loop {
+            match order.as_ref().map(String::as_str) {
+                Some("--root") => match arguments.next() {
+                    None => return Err(CommandError::MissingArgument("--root <directory>")),
+                    Some(directory) => root = PathBuf::from(directory),
+                },
+                Some("--fallback") => match arguments.next() {
+                    None => return Err(CommandError::MissingArgument("--fallback <Fallback>")),
+                    Some(name) => {
+                        fallback = Some(try!(Fallback::new(&name).map_err(CommandError::Fallback)))
+                    },
+                },
+                _ => break ,
+            }
+            order = arguments.next();
+        }
+
+        order.map_or(Ok(None), |order|
+            Command::order(&order, &arguments.collect::<Vec<String>>())
+                .and_then(|order| Ok(Some(order))))
+            .and_then(|order|
+                Ok((root.clone(), fallback, order.map(|order|
+                    Command {
+                        root: root,
+                        fallback: fallback.unwrap_or_else(Fallback::probe),
+                        order: order,
+                    }))))
+    }
+
+    /// The function `order` returns the sub-command with its arguments.
+    fn order(order: &str, arguments: &[String]) -> Result<Order> {
+        match (order, arguments.first(), arguments.get(1)) {
+            ("validate", _, _) => Ok(Order::Validate),
+            ("lint", _, _) => {
+                Ok(Order::Lint(arguments.iter().map(PathBuf::from).collect::<Vec<PathBuf>>()))
+            },
+            ("list", None, _) => Ok(Order::List(None)),
+            ("list", Some(list), _) => match list.as_str() {
+                "sheets" => Ok(Order::List(Some(List::Sheet))),
+                "parts" => Ok(Order::List(Some(List::Part))),
+                "emotions" => Ok(Order::List(Some(List::Emotion))),
+                "expressions" => Ok(Order::List(Some(List::Expression))),
+                "transitions" => Ok(Order::List(Some(List::Transition))),
+                "templates" => Ok(Order::List(Some(List::Template))),
+                "effects" => Ok(Order::List(Some(List::Effect))),
+                "colors" => Ok(Order::List(Some(List::Color))),
+                list => Err(CommandError::UnknownList(list.to_string())),
+            },
+            ("show", Some(sheet), _) => {
+                Sheet::new(sheet).map_err(CommandError::Sheet)
+                                 .and_then(|sheet| Ok(Order::Show(sheet)))
+            },
+            ("coverage", _, _) => {
+                arguments.iter()
+                         .map(|argument| match argument.as_str() {
+                             "--csv" => Ok((Some(Format::Csv), false)),
+                             "--html" => Ok((Some(Format::Html), false)),
+                             "--all" => Ok((None, true)),
+                             argument => Err(CommandError::UnknownCommand(argument.to_string())),
+                         })
+                         .collect::<Result<Vec<(Option<Format>, bool)>>>()
+                         .and_then(|options|
+                              Ok(Order::Coverage(
+                                  options.iter()
+                                         .filter_map(|&(format, _)| format)
+                                         .last()
+                                         .unwrap_or(Format::Text),
+                                  options.iter().any(|&(_, all)| all))))
+            },
+            ("glyphs", None, _) => Ok(Order::Glyph(false, None)),
+            ("glyphs", Some(option), count) => match (option.as_str(), count) {
+                ("--collisions", _) => Ok(Order::Glyph(true, None)),
+                ("--free", Some(count)) => {
+                    count.parse::<usize>()
+                         .map_err(CommandError::Number)
+                         .and_then(|count| Ok(Order::Glyph(false, Some(count))))
+                },
+                ("--free", None) => Err(CommandError::MissingArgument("glyphs --free <n>")),
+                (option, _) => Err(CommandError::UnknownCommand(option.to_string())),
+            },
+            ("show", None, _) => Err(CommandError::MissingArgument("show <Sheet>")),
+            ("texels", Some(sheet), _) => {
+                Sheet::new(sheet).map_err(CommandError::Sheet)
+                                 .and_then(|sheet| Ok(Order::Texel(sheet)))
+            },
+            ("texels", None, _) => Err(CommandError::MissingArgument("texels <Sheet>")),
+            ("render", Some(sheet), _) => {
+                let mut options = arguments.iter().skip(1);
+                let mut effects: Vec<String> = Vec::new();
+                let mut draw: Option<&String> = None;
+
+                while let Some(option) = options.next() {
+                    if option.eq("--effect") {
+                        match options.next() {
+                            Some(name) => effects.push(name.to_string()),
+                            None => {
+                                return Err(CommandError::MissingArgument("render --effect <Effect>"))
+                            },
+                        }
+                    } else {
+                        draw = Some(option);
+                    }
+                }
+                match (Sheet::new(sheet), draw.map(|draw| draw.parse::<usize>())) {
+                    (Err(why), _) => Err(CommandError::Sheet(why)),
+                    (_, Some(Err(why))) => Err(CommandError::Number(why)),
+                    (Ok(sheet), draw) => Ok(Order::Render(sheet, draw.and_then(|draw|
+                                                                               draw.ok()),
+                                                          effects)),
+                }
+            },
+            ("render", None, _) => {
+                Err(CommandError::MissingArgument("render <Sheet> [<draw>] [--effect <Effect>]..."))
+            },
+            ("convert", Some(source), output) => {
+                Ok(Order::Convert(PathBuf::from(source), output.map(PathBuf::from)))
+            },
+            ("convert", None, _) => Err(CommandError::MissingArgument("convert <file> [<output>]")),
+            ("expand", Some(name), Some(sheet)) => {
+                Sheet::new(sheet).map_err(CommandError::Sheet)
+                                 .and_then(|sheet|
+                                      Ok(Order::Expand(name.to_string(), sheet,
+                                                       arguments.get(2).map(PathBuf::from))))
+            },
+            ("expand", _, _) => {
+                Err(CommandError::MissingArgument("expand <Template> <Sheet> [<output>]"))
+            },
+            ("diff", Some(before), Some(after)) => {
+                Ok(Order::Diff(PathBuf::from(before), PathBuf::from(after)))
+            },
+            ("diff", _, _) => Err(CommandError::MissingArgument("diff <file> <file>")),
+            ("merge", Some(base), Some(ours)) if arguments.len() > 2 => {
+                Ok(Order::Merge(PathBuf::from(base), PathBuf::from(ours),
+                                PathBuf::from(&arguments[2]),
+                                arguments.get(3).map(PathBuf::from)))
+            },
+            ("merge", _, _) => {
+                Err(CommandError::MissingArgument("merge <base> <ours> <theirs> [<path>]"))
+            },
+            ("lsp", _, _) => Ok(Order::Lsp),
+            ("serve", Some(socket), _) => Ok(Order::Serve(PathBuf::from(socket))),
+            ("serve", None, _) => Err(CommandError::MissingArgument("serve <socket>")),
+            ("help", _, _) | ("--help", _, _) | ("-h", _, _) => Ok(Order::Help),
+            (order, _, _) => Err(CommandError::UnknownCommand(order.to_string())),
+        }
+    }
+
+    /// The method `run` executes the sub-command on the output, colored
+    /// when the caller knows that the output is a terminal.
+    pub fn run<W: Write>(&self, output: &mut W, color: bool) -> Result<()> {
+        match self.order {
+            Order::Validate => self.validate(output),
+            Order::Lint(ref sources) => self.lint(output, sources),
+            Order::List(list) => self.list(output, list),
+            Order::Show(ref sheet) => {
+                self.graphic()
+                    .and_then(|graphic|
+                        Command::sprite(&graphic, sheet)
+                            .and_then(|sprite| self.show(output, sheet, sprite)))
+            },
+            Order::Texel(ref sheet) => {
+                self.graphic()
+                    .and_then(|graphic| self.texel(output, &graphic, sheet))
+            },
+            Order::Coverage(format, all) => {
+                self.graphic()
+                    .and_then(|graphic| {
+                        let coverage: Coverage = if all {
+                            Coverage::new(&graphic)
+                        } else {
+                            Coverage::new(&graphic).used()
+                        };
+
+                        match format {
+                            Format::Text => coverage.write_text(output),
+                            Format::Csv => coverage.write_csv(output),
+                            Format::Html => coverage.write_html(output),
+                        }.map_err(CommandError::Write)
+                    })
+            },
+            Order::Glyph(collision, free) => {
+                self.graphic()
+                    .and_then(|graphic| self.glyph(output, &GlyphMap::new(&graphic),
+                                                   collision, free))
+            },
+            Order::Render(ref sheet, draw, ref effects) => {
+                self.graphic()
+                    .and_then(|graphic|
+                        Command::sprite(&graphic, sheet)
+                            .and_then(|sprite| self.render(output, &graphic, sprite, draw, effects,
+                                                          color)))
+            },
+            Order::Convert(ref source, ref destination) => {
+                self.convert(output, source, destination.as_ref())
+            },
+            Order::Expand(ref name, ref sheet, ref destination) => {
+                self.graphic()
+                    .and_then(|graphic|
+                         graphic.expand_template(sheet, name).map_err(CommandError::Graphic))
+                    .and_then(|sprite: Sprite|
+                        match *destination {
+                            None => write!(output, "{}", sprite),
+                            Some(ref destination) => {
+                                fs::File::create(destination)
+                                    .and_then(|mut file| write!(file, "{}", sprite))
+                            },
+                        }.map_err(CommandError::Write))
+            },
+            Order::Diff(ref before, ref after) => self.diff(output, before, after, color),
+            Order::Merge(ref base, ref ours, ref theirs, ref path) => {
+                self.merge(output, base, ours, theirs, path.as_ref().unwrap_or(ours))
+            },
+            Order::Serve(ref socket) => {
+                self.graphic()
+                    .and_then(|graphic|
+                        server::serve(graphic, socket).map_err(CommandError::Socket))
+            },
+            Order::Lsp => {
+                let stdin: io::Stdin = io::stdin();
+                let mut server: lsp::Server = lsp::Server::new(&self.root);
+
+                server.run(stdin.lock(), output).map_err(CommandError::Write)
+            },
+            Order::Help => output.write_all(SPEC_USAGE.as_bytes())
+                                 .map_err(CommandError::Write),
+        }
+    }
+
+    /// The function `graphic` returns the Graphic of the root.
+    fn graphic(&self) -> Result<Graphic> {
+        GraphicBuilder::new().root(&self.root)
+                             .build()
+                             .map_err(CommandError::Graphic)
+    }
+
+    /// The method `texels` returns a Graphic with only the texel files
+    /// of the root, the sprite files which the loader refuses are
+    /// left to the lints.
+    fn texels(&self) -> Result<Graphic> {
+        let mut graphic: Graphic = Graphic::default();
+
+        try!(try!(self.entries(SPEC_SUBD_NCT, SPEC_EXT_NCT))
+                 .iter()
+                 .map(|path| graphic.insert_from_texelfile(path))
+                 .find(|result| result.is_err())
+                 .unwrap_or_else(|| Ok(()))
+                 .map_err(CommandError::Graphic));
+        Ok(graphic)
+    }
+
+    /// The function `sprite` returns the sprite of the Sheet key.
+    fn sprite<'a>(graphic: &'a Graphic, sheet: &Sheet) -> Result<&'a Sprite> {
+        graphic.get_sprite(sheet)
+               .ok_or_else(|| CommandError::FoundSprite(sheet.get_name().to_string()))
+    }
+
+    /// The function `entries` returns the files of the sub-directory
+    /// with the extension.
+    fn entries(&self, sub: &str, extension: &str) -> Result<Vec<PathBuf>> {
+        fs::read_dir(self.root.join(sub))
+            .map_err(|why|
+                CommandError::Graphic(GraphicError::ReadDir(self.root.join(sub), why)))
+            .and_then(|entry| {
+                let mut entries: Vec<PathBuf> = entry.filter_map(|entry| entry.ok())
+                                                     .map(|entry| entry.path())
+                                                     .filter(|path|
+                                                         path.extension()
+                                                             .eq(&Some(OsStr::new(extension))))
+                                                     .collect::<Vec<PathBuf>>();
+                entries.sort();
+                Ok(entries)
+            })
+    }
+
+    /// The method `validate` loads the template file, every texel file
+    /// and then every sprite file and writes the result by file.
+    fn validate<W: Write>(&self, output: &mut W) -> Result<()> {
+        let mut graphic: Graphic = Graphic::default();
+        let ncp: Vec<PathBuf> = Some(self.root.join(SPEC_FILE_NCP).with_extension(SPEC_EXT_NCP))
+                                    .into_iter()
+                                    .filter(|path| path.exists())
+                                    .collect::<Vec<PathBuf>>();
+        let (nct, ncs) = (try!(self.entries(SPEC_SUBD_NCT, SPEC_EXT_NCT)),
+                          try!(self.entries(SPEC_SUBD_NCS, SPEC_EXT_NCS)));
+        let invalid: usize =
+            try!(ncp.iter()
+                    .map(|path| (path, fs::File::open(path).map_err(|why|
+                                           GraphicError::OpenFile(path.clone(), why))
+                                       .and_then(|file| graphic.insert_from_template(file)
+                                                                .map_err(|why| why.with_path(path)))))
+                    .collect::<Vec<(&PathBuf, ::editeur::Result<()>)>>()
+                    .into_iter()
+                    .chain(nct.iter()
+                              .map(|path| (path, graphic.insert_from_texelfile(path)))
+                              .collect::<Vec<(&PathBuf, ::editeur::Result<()>)>>())
+                    .chain(ncs.iter()
+                              .map(|path| (path, graphic.insert_from_spritefile(path)))
+                              .collect::<Vec<(&PathBuf, ::editeur::Result<()>)>>())
+                    .map(|(path, result)| match result {
+                        Ok(()) => writeln!(output, "ok {}", path.display())
+                                      .and(Ok(0)),
+                        Err(GraphicError::File(_, why)) => writeln!(output, "error {}: {}",
+                                                                   path.display(),
+                                                                   editeur::report(&*why))
+                                                              .and(Ok(1)),
+                        Err(why) => writeln!(output, "error {}: {}",
+                                             path.display(),
+                                             editeur::report(&why))
+                                        .and(Ok(1)),
+                    })
+                    .collect::<io::Result<Vec<usize>>>())
+                .iter()
+                .sum();
+
+        if invalid.eq(&0) {
+            Ok(())
+        } else {
+            Err(CommandError::Invalid(invalid))
+        }
+    }
+
+    /// The method `lint` writes the lints of the sprite files
+    /// or of every sprite file.
+    fn lint<W: Write>(&self, output: &mut W, sources: &[PathBuf]) -> Result<()> {
+        let graphic: Graphic = try!(self.texels());
+        let sources: Vec<PathBuf> = if sources.is_empty() {
+            try!(self.entries(SPEC_SUBD_NCS, SPEC_EXT_NCS))
+        } else {
+            sources.to_vec()
+        };
+        let invalid: usize =
+            try!(sources.iter()
+                        .map(|path|
+                             lint::lint_spritefile(&graphic, path)
+                                 .map_err(CommandError::Graphic)
+                                 .and_then(|reports|
+                                     reports.iter()
+                                            .map(|report|
+                                                 writeln!(output, "{}:{}",
+                                                          path.display(), report))
+                                            .find(|report| report.is_err())
+                                            .unwrap_or_else(|| Ok(()))
+                                            .map_err(CommandError::Write)
+                                            .and(Ok(reports.len()))))
+                        .collect::<Result<Vec<usize>>>())
+                .iter()
+                .sum();
+
+        if invalid.eq(&0) {
+            Ok(())
+        } else {
+            Err(CommandError::Invalid(invalid))
+        }
+    }
+
+    /// The method `list` writes the names of one or all dictionaries.
+    fn list<W: Write>(&self, output: &mut W, list: Option<List>) -> Result<()> {
+        fn names<W: Write, T: ::std::fmt::Debug>(
+            output: &mut W, title: Option<&str>, names: &[T]
+        ) -> io::Result<()> {
+            title.map_or(Ok(()), |title| writeln!(output, "{}:", title))
+                 .and(names.iter()
+                           .map(|name| writeln!(output, "{:?}", name))
+                           .find(|name| name.is_err())
+                           .unwrap_or_else(|| Ok(())))
+        }
+        match list {
+            Some(List::Sheet) => names(output, None, Sheet::list()).map_err(CommandError::Write),
+            Some(List::Part) => names(output, None, Part::list()).map_err(CommandError::Write),
+            Some(List::Emotion) => {
+                names(output, None, Emotion::list()).map_err(CommandError::Write)
+            },
+            Some(List::Expression) => {
+                self.graphic().and_then(|graphic|
+                    expression::write_expressions(output, graphic.get_expression_list())
+                               .map_err(CommandError::Write))
+            },
+            Some(List::Transition) => {
+                self.graphic().and_then(|graphic|
+                    transition::write_transitions(output, graphic.get_transition_list())
+                               .map_err(CommandError::Write))
+            },
+            Some(List::Template) => {
+                self.graphic().and_then(|graphic|
+                    template::write_templates(output, graphic.get_template_list())
+                             .map_err(CommandError::Write))
+            },
+            Some(List::Effect) => {
+                self.graphic().and_then(|graphic|
+                    effect::write_effects(output, graphic.get_effect_list())
+                           .map_err(CommandError::Write))
+            },
+            Some(List::Color) => {
+                Color::list().iter()
+                             .map(|name| writeln!(output, "{}", name))
+                             .find(|name| name.is_err())
+                             .unwrap_or_else(|| Ok(()))
+                             .map_err(CommandError::Write)
+            },
+            None => {
+                names(output, Some("sheets"), Sheet::list())
+                    .and(names(output, Some("parts"), Part::list()))
+                    .and(names(output, Some("emotions"), Emotion::list()))
+                    .map_err(CommandError::Write)
+            },
+        }
+    }
+
+    /// The method `draw_line` returns the line by glyph or fallback, part
+    /// and emotion, the cells of the changed indexes are highlighted.
+    fn draw_line(&self, line: &[(Emotion, Texel)], start: usize,
+                 changed: &[usize], color: bool) -> String {
+        let cell = |index: usize, cell: String|
+            if changed.contains(&(start + index)).not() {
+                cell
+            } else if color {
+                format!("{}{}{}", termion::style::Invert, cell, termion::style::Reset)
+            } else {
+                format!("{}\u{0332}", cell)
+            };
+
+        format!("{} {} {}",
+                line.iter()
+                    .enumerate()
+                    .map(|(index, &(_, ref texel))|
+                         cell(index, if color {
+                             texel.get_painted(&self.fallback)
+                         } else {
+                             texel.get_rendered(&self.fallback)
+                         }))
+                    .collect::<String>(),
+                line.iter()
+                    .enumerate()
+                    .map(|(index, &(_, ref texel))|
+                         cell(index, texel.get_part().to_string()))
+                    .collect::<String>(),
+                line.iter()
+                    .enumerate()
+                    .map(|(index, &(ref emotion, _))|
+                         cell(index, emotion.to_string()))
+                    .collect::<String>())
+    }
+
+    /// The function `write_draw_command` writes the all
+    /// the non-none (part, emotions) command of this draw.
+    fn write_draw_command(draw: &Draw) -> String {
+        let mut sheet =
+            draw.into_iter()
+                .filter_map(|&(ref emotion, ref texel): &(Emotion, Texel)|
+                            emotion.not_empty()
+                                   .and_then(|emotion: &Emotion|
+                                             Some((*texel.get_part(), *emotion))))
+                .collect::<Vec<(Part, Emotion)>>();
+
+        sheet.dedup();
+        sheet.iter()
+             .map(|&(part, emotion)| format!(" {:?}:{:?}", part, emotion))
+             .collect::<String>()
+    }
+
+    /// The method `show` writes the sprite like the editor: the draws
+    /// with their duration and the command of the sprite.
+    fn show<W: Write>(&self, output: &mut W, sheet: &Sheet,
+                      sprite: &Sprite) -> Result<()> {
+        writeln!(output, "{}", sheet.get_name())
+            .and(sprite.into_iter()
+                       .enumerate()
+                       .map(|(index, draw)|
+                            writeln!(output, "{} - {}: {}",
+                                     index, draw.get_duration(), draw.get_posture())
+                                .and(draw.into_iter()
+                                         .as_slice()
+                                         .chunks(SPEC_MAX_X)
+                                         .map(|line|
+                                              writeln!(output, "{}",
+                                                       self.draw_line(line, 0, &[], false)))
+                                         .find(|line| line.is_err())
+                                         .unwrap_or_else(|| Ok(())))
+                                .and(writeln!(output)))
+                       .find(|draw| draw.is_err())
+                       .unwrap_or_else(|| Ok(())))
+            .and(writeln!(output, "{}",
+                          sprite.into_iter()
+                                .map(|draw|
+                                     format!("--{}{}", draw.get_posture(),
+                                             Command::write_draw_command(draw)))
+                                .collect::<Vec<String>>()
+                                .join(" ")))
+            .map_err(CommandError::Write)
+    }
+
+    /// The method `texel` writes the chain of parents of the posture
+    /// and its texels, with the parent which declares the inherited ones.
+    fn texel<W: Write>(&self, output: &mut W, graphic: &Graphic,
+                       sheet: &Sheet) -> Result<()> {
+        let mut texels: Vec<(Tuple, Vec<Texel>)> =
+            graphic.get_texel_resolved(sheet)
+                   .unwrap_or_default()
+                   .into_iter()
+                   .collect::<Vec<(Tuple, Vec<Texel>)>>();
+
+        texels.sort_by_key(|&(Tuple { part, emotion }, _)| format!("{:?}:{:?}", part, emotion));
+        writeln!(output, "{}",
+                 graphic.get_chain(sheet)
+                        .iter()
+                        .map(|posture| posture.get_name())
+                        .collect::<Vec<&str>>()
+                        .join(" < "))
+            .and(texels.iter()
+                       .map(|&(tuple, ref glyphs)|
+                            writeln!(output, "{:?}:{:?} {}{}",
+                                     tuple.part, tuple.emotion,
+                                     glyphs.iter()
+                                           .map(|texel| texel.get_glyph())
+                                           .collect::<String>(),
+                                     graphic.get_texel_origin(sheet, &tuple)
+                                            .and_then(|origin|
+                                                 if origin.eq(sheet) {
+                                                     None
+                                                 } else {
+                                                     Some(format!(" from {}", origin))
+                                                 })
+                                            .unwrap_or_default()))
+                       .find(|texel| texel.is_err())
+                       .unwrap_or_else(|| Ok(())))
+            .map_err(CommandError::Write)
+    }
+
+    /// The method `glyph` writes the glyph map, the collisions
+    /// or the suggested free glyphs.
+    fn glyph<W: Write>(&self, output: &mut W, map: &GlyphMap,
+                       collision: bool, free: Option<usize>) -> Result<()> {
+        match (collision, free) {
+            (true, _) => {
+                let collisions: Vec<(char, Vec<Tuple>)> = map.collisions();
+
+                try!(collisions.iter()
+                               .map(|&(glyph, ref pairs)|
+                                    writeln!(output, "U+{:04X} {}",
+                                             glyph as u32,
+                                             pairs.iter()
+                                                  .map(|&Tuple { part, emotion }|
+                                                       format!("{:?}:{:?}", part, emotion))
+                                                  .collect::<Vec<String>>()
+                                                  .join(" ")))
+                               .find(|line| line.is_err())
+                               .unwrap_or_else(|| Ok(())));
+                if collisions.is_empty() {
+                    Ok(())
+                } else {
+                    Err(CommandError::Invalid(collisions.len()))
+                }
+            },
+            (false, Some(count)) => {
+                writeln!(output, "{}",
+                         map.suggest(count)
+                            .iter()
+                            .map(|&glyph| format!("U+{:04X}", glyph as u32))
+                            .collect::<Vec<String>>()
+                            .join(" ")).map_err(CommandError::Write)
+            },
+            (false, None) => map.write_text(output).map_err(CommandError::Write),
+        }
+    }
+
+    /// The method `render` writes the glyphs or fallbacks of all the draws
+    /// or of only one draw, colored or not, with the frame of each
+    /// effect played since the first draw at the start of the draw.
+    fn render<W: Write>(&self, output: &mut W, graphic: &Graphic, sprite: &Sprite,
+                        index: Option<usize>, effects: &[String], color: bool) -> Result<()> {
+        let mut start: i64 = 0;
+        let draws: Vec<Draw> =
+            try!(sprite.into_iter()
+                       .map(|draw: &Draw| {
+                           let elapsed: i64 = start;
+
+                           start += draw.get_duration().num_milliseconds();
+                           graphic.compose_effects(draw,
+                                                   &effects.iter()
+                                                           .map(|name| (name.as_str(), elapsed))
+                                                           .collect::<Vec<(&str, i64)>>())
+                       })
+                       .collect::<::editeur::Result<Vec<Draw>>>()
+                       .map_err(CommandError::Graphic));
+        let draws: Vec<&Draw> = match index {
+            None => draws.iter().collect::<Vec<&Draw>>(),
+            Some(index) => {
+                try!(draws.get(index)
+                          .ok_or(CommandError::FoundDraw(index))
+                          .and_then(|draw| Ok(vec![draw])))
+            },
+        };
+
+        draws.iter()
+             .enumerate()
+             .map(|(index, draw)|
+                  if index.eq(&0) { Ok(()) } else { writeln!(output) }
+                      .and(if color {
+                               draw.get_painted_lines(&self.fallback)
+                           } else {
+                               draw.get_rendered_lines(&self.fallback)
+                           }.iter()
+                               .map(|line| writeln!(output, "{}", line))
+                               .find(|line| line.is_err())
+                               .unwrap_or_else(|| Ok(()))))
+             .find(|draw| draw.is_err())
+             .unwrap_or_else(|| Ok(()))
+             .map_err(CommandError::Write)
+    }
+
+    /// The function `sprite_from_file` returns the sprite of a file
+    /// with the texels of the Graphic.
+    fn sprite_from_file(graphic: &Graphic, source: &Path) -> Result<Sprite> {
+        fs::File::open(source)
+            .map_err(|why|
+                CommandError::Graphic(GraphicError::OpenFile(source.to_path_buf(), why)))
+            .and_then(|file|
+                graphic.sprite_from_reader(file)
+                       .map_err(|why| CommandError::Graphic(why.with_path(source))))
+    }
+
+    /// The function `base_from_file` returns the sprite of a sprite file
+    /// with its base draws instead of its expanded templates, and the
+    /// names of its templates.
+    fn base_from_file(graphic: &Graphic, source: &Path) -> Result<(Sprite, Vec<String>)> {
+        Command::sprite_from_file(graphic, source)
+            .map(|sprite: Sprite| {
+                let template: Vec<String> = sprite.get_template().clone();
+
+                (sprite.get_base().unwrap_or(sprite), template)
+            })
+    }
+
+    /// The function `merge_template` returns the template names kept by
+    /// both sides from the base followed by the names which a side added.
+    fn merge_template(base: &[String], ours: &[String], theirs: &[String]) -> Vec<String> {
+        ours.iter()
+            .filter(|name| theirs.contains(name) || base.contains(name).not())
+            .chain(theirs.iter()
+                         .filter(|name| ours.contains(name).not() &&
+                                        base.contains(name).not()))
+            .cloned()
+            .collect::<Vec<String>>()
+    }
+
+    /// The method `convert` rewrites a sprite file in the canonical
+    /// format on the destination or else on the output.
+    fn convert<W: Write>(&self, output: &mut W, source: &Path,
+                         destination: Option<&PathBuf>) -> Result<()> {
+        self.graphic()
+            .and_then(|graphic| Command::sprite_from_file(&graphic, source))
+            .and_then(|sprite: Sprite|
+                match destination {
+                    None => write!(output, "{}", sprite),
+                    Some(destination) => {
+                        fs::File::create(destination)
+                            .and_then(|mut file| write!(file, "{}", sprite))
+                    },
+                }.map_err(CommandError::Write))
+    }
+
+    /// The method `diff` writes the changes between two sprite files
+    /// and the changed draws side by side, colored or not.
+    fn diff<W: Write>(&self, output: &mut W, before: &Path, after: &Path,
+                      color: bool) -> Result<()> {
+        let graphic: Graphic = try!(self.graphic());
+        let ((before, before_template), (after, after_template)):
+            ((Sprite, Vec<String>), (Sprite, Vec<String>)) = (
+            try!(Command::base_from_file(&graphic, before)),
+            try!(Command::base_from_file(&graphic, after)),
+        );
+        let diff: Diff = before.diff(&after);
+        let template: Vec<String> =
+            before_template.iter()
+                           .filter(|name| after_template.contains(name).not())
+                           .map(|name| format!("template {}: removed", name))
+                           .chain(after_template.iter()
+                                                .filter(|name| before_template.contains(name).not())
+                                                .map(|name| format!("template {}: added", name)))
+                           .collect::<Vec<String>>();
+        try!(template.iter()
+                     .map(|line| writeln!(output, "{}", line))
+                     .find(|line| line.is_err())
+                     .unwrap_or_else(|| Ok(())));
+        try!(write!(output, "{}", diff));
+        try!(before.into_iter()
+                   .zip(after.into_iter())
+                   .enumerate()
+                   .filter_map(|(index, draws)|
+                               diff.get_cells(index)
+                                   .and_then(|cells| Some((index, draws, cells))))
+                   .filter(|&(_, _, cells)| cells.is_empty().not())
+                   .map(|(index, (before, after), cells)| {
+                       let changed: Vec<usize> = cells.iter()
+                                                      .map(|cell| cell.index)
+                                                      .collect::<Vec<usize>>();
+
+                       writeln!(output, "\ndraw {}:", index)
+                           .and(before.composite(Layer::list())
+                                      .chunks(SPEC_MAX_X)
+                                      .zip(after.composite(Layer::list())
+                                                .chunks(SPEC_MAX_X))
+                                      .enumerate()
+                                      .map(|(y, (before, after))|
+                                           writeln!(output, "{} | {}",
+                                                    self.draw_line(before, y * SPEC_MAX_X,
+                                                                   &changed, color),
+                                                    self.draw_line(after, y * SPEC_MAX_X,
+                                                                   &changed, color)))
+                                      .find(|line| line.is_err())
+                                      .unwrap_or_else(|| Ok(())))
+                   })
+                   .find(|draw| draw.is_err())
+                   .unwrap_or_else(|| Ok(())));
+        if diff.is_empty() && template.is_empty() {
+            Ok(())
+        } else {
+            Err(CommandError::Differ(diff.get_changes().len() + template.len()))
+        }
+    }
+
+    /// The method `merge` writes the three-way merge of the sprite files
+    /// in our file, the conflicts are written in the conflict file of
+    /// the path and on the output.
+    fn merge<W: Write>(&self, output: &mut W, base: &Path, ours: &Path,
+                       theirs: &Path, path: &Path) -> Result<()> {
+        let graphic: Graphic = try!(self.graphic());
+        let ((base, base_template), (theirs, theirs_template)):
+            ((Sprite, Vec<String>), (Sprite, Vec<String>)) = (
+            try!(Command::base_from_file(&graphic, base)),
+            try!(Command::base_from_file(&graphic, theirs)),
+        );
+        let (sprite, ours_template): (Sprite, Vec<String>) =
+            try!(Command::base_from_file(&graphic, ours));
+        let merge: Merge = try!(sprite.merge(&base, &theirs)
+                                      .map_err(|why| GraphicError::Sprite(why).with_path(ours)));
+        let template: Vec<String> =
+            Command::merge_template(&base_template, &ours_template, &theirs_template);
+        let mut sprite: Sprite = merge.get_sprite().clone();
+        let conflict: PathBuf = merge::conflict_path(path);
+
+        if template.is_empty().not() {
+            let draws: Vec<Draw> = sprite.into_iter().cloned().collect::<Vec<Draw>>();
+
+            sprite.set_template(&template.iter()
+                                         .map(|name| name.as_str())
+                                         .collect::<Vec<&str>>(),
+                                &draws);
+        }
+        try!(fs::File::create(ours).and_then(|mut file| write!(file, "{}", sprite)));
+        if merge.is_clean() {
+            match fs::remove_file(conflict) {
+                Err(ref why) if why.kind() == io::ErrorKind::NotFound => Ok(()),
+                result => result.map_err(CommandError::Write),
+            }
+        } else {
+            try!(fs::File::create(&conflict).and_then(|file|
+                     merge::write_conflicts(file, merge.get_conflicts())));
+            try!(merge.get_conflicts()
+                      .iter()
+                      .map(|conflict| writeln!(output, "{}: {}", path.display(), conflict))
+                      .find(|line| line.is_err())
+                      .unwrap_or_else(|| Ok(())));
+            Err(CommandError::Conflict(merge.get_conflicts().len()))
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use super::Graphic;
+use super::emotion::Emotion;
+use super::sheet::Sheet;
+use super::sprite::texel::part::Part;
+use super::tuple::Tuple;
+
+/// The enum `Status` defines the coverage of a pair for a posture.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// The pair hasn't texel.
+    Missing,
+    /// The pair has fewer glyphs than the cells of its part
+    /// in a draw of the posture.
+    Partial(usize, usize),
+    /// The pair has a glyph for every cell of its part.
+    Present,
+}
+
+impl Status {
+    /// The accessor method `get_name` returns the name used by
+    /// the CSV and HTML outputs.
+    pub fn get_name(&self) -> &'static str {
+        match *self {
+            Status::Missing => "missing",
+            Status::Partial(..) => "partial",
+            Status::Present => "present",
+        }
+    }
+
+    /// The accessor method `get_mark` returns the symbol used by
+    /// the text output.
+    pub fn get_mark(&self) -> char {
+        match *self {
+            Status::Missing => '.',
+            Status::Partial(..) => '+',
+            Status::Present => '#',
+        }
+    }
+}
+
+/// The struct `Coverage` is a matrix of the pairs of Part and Emotion
+/// by posture.
+#[derive(Clone, Debug)]
+pub struct Coverage {
+    /// The pairs of the columns.
+    columns: Vec<Tuple>,
+    /// The postures of the rows with a status by column.
+    rows: Vec<(Sheet, Vec<Status>)>,
+}
+
+impl Coverage {
+    /// The constructor `new` returns the matrix of every posture
+    /// and every pair from the texels and the sprites of the Graphic.
+    pub fn new(graphic: &Graphic) -> Self {
+        let mut cells: HashMap<(Sheet, Part), usize> = HashMap::new();
+        let columns: Vec<Tuple> =
+            Part::list().iter()
+                        .flat_map(|&part|
+                                  Emotion::list().iter()
+                                                 .map(move |&emotion|
+                                                      Tuple::from((part, emotion))))
+                        .collect::<Vec<Tuple>>();
+
+        graphic.get_sprite_list()
+               .iter()
+               .flat_map(|&(_, ref sprite)| sprite.into_iter())
+               .all(|draw| {
+                   let mut count: HashMap<Part, usize> = HashMap::new();
+
+                   draw.into_iter()
+                       .all(|&(_, ref texel)| {
+                           *count.entry(*texel.get_part()).or_insert(0) += 1;
+                           true
+                       });
+                   count.into_iter()
+                        .all(|(part, count)| {
+                            let cell = cells.entry((*draw.get_posture(), part))
+                                            .or_insert(0);
+                            *cell = count.max(*cell);
+                            true
+                        })
+               });
+        Coverage {
+            rows: Sheet::list().iter()
+                               .filter(|sheet| sheet.ne(&&Sheet::None))
+                               .map(|&sheet|
+                                    (sheet, columns.iter()
+                                                   .map(|tuple|
+                                        match (graphic.get_texel(&sheet, tuple),
+                                               cells.get(&(sheet, tuple.part))) {
+                                            (None, _) => Status::Missing,
+                                            (Some(texels), Some(&cell)) if texels.len() < cell => {
+                                                Status::Partial(texels.len(), cell)
+                                            },
+                                            (Some(_), _) => Status::Present,
+                                        })
+                                                   .collect::<Vec<Status>>()))
+                               .collect::<Vec<(Sheet, Vec<Status>)>>(),
+            columns: columns,
+        }
+    }
+
+    /// The method `used` returns the matrix without the columns
+    /// which are missing for every posture.
+    pub fn used(self) -> Self {
+        let keep: Vec<bool> = (0..self.columns.len())
+            .map(|index|
+                 self.rows.iter()
+                          .any(|&(_, ref status)|
+                               status[index].ne(&Status::Missing)))
+            .collect::<Vec<bool>>();
+        let filter = |list: Vec<Status>| list.into_iter()
+                                             .zip(keep.iter())
+                                             .filter(|&(_, keep)| *keep)
+                                             .map(|(status, _)| status)
+                                             .collect::<Vec<Status>>();
+
+        Coverage {
+            columns: self.columns.into_iter()
+                                 .zip(keep.iter())
+                                 .filter(|&(_, keep)| *keep)
+                                 .map(|(tuple, _)| tuple)
+                                 .collect::<Vec<Tuple>>(),
+            rows: self.rows.into_iter()
+                           .map(|(sheet, status)| (sheet, filter(status)))
+                           .collect::<Vec<(Sheet, Vec<Status>)>>(),
+        }
+    }
+
+    /// The accessor method `get_columns` returns the pairs of the columns.
+    pub fn get_columns(&self) -> &Vec<Tuple> {
+        &self.columns
+    }
+
+    /// The accessor method `get_rows` returns the postures with
+    /// their status by column.
+    pub fn get_rows(&self) -> &Vec<(Sheet, Vec<Status>)> {
+        &self.rows
+    }
+
+    /// The function `groups` returns the columns grouped by part
+    /// with the width of the group.
+    fn groups(&self) -> Vec<(Part, usize, usize)> {
+        let mut groups: Vec<(Part, usize, usize)> = Vec::new();
+
+        self.columns.iter()
+                    .enumerate()
+                    .all(|(index, &Tuple { part, .. })| {
+                        match groups.last_mut() {
+                            Some(&mut (ref last, _, ref mut len)) if last.eq(&part) => {
+                                *len += 1;
+                            },
+                            _ => groups.push((part, index, 1)),
+                        }
+                        true
+                    });
+        groups
+    }
+
+    /// The function `write_text_line` writes a line of the text output
+    /// with the head and a string by group.
+    fn write_text_line<W: Write, F: Fn(usize, usize) -> String>(
+        &self, output: &mut W, head: &str, width: usize, cell: F
+    ) -> io::Result<()> {
+        writeln!(output, "{:width$}{}", head,
+                 self.groups()
+                     .iter()
+                     .map(|&(_, start, len)|
+                          format!(" {:len$}", cell(start, len), len = len.max(2)))
+                     .collect::<String>(),
+                 width = width)
+    }
+
+    /// The method `write_text` writes the matrix with a mark by pair,
+    /// the part code and the emotion symbol as headers.
+    pub fn write_text<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let width: usize = Sheet::list().iter()
+                                        .map(|sheet| sheet.get_name().len())
+                                        .max()
+                                        .unwrap_or_default();
+
+        self.write_text_line(output, "", width, |start, _|
+                             self.columns[start].part.to_string())
+            .and(self.write_text_line(output, "", width, |start, len|
+                     self.columns[start..start + len].iter()
+                                                     .map(|tuple| tuple.emotion.to_string())
+                                                     .collect::<String>()))
+            .and(self.rows.iter()
+                          .map(|&(ref sheet, ref status)|
+                               self.write_text_line(output, sheet.get_name(), width,
+                                                    |start, len|
+                                   status[start..start + len].iter()
+                                                             .map(|status| status.get_mark())
+                                                             .collect::<String>()))
+                          .find(|line| line.is_err())
+                          .unwrap_or_else(|| Ok(())))
+            .and(writeln!(output, "\n{} present {} partial {} missing",
+                          Status::Present.get_mark(),
+                          Status::Partial(0, 0).get_mark(),
+                          Status::Missing.get_mark()))
+    }
+
+    /// The method `write_csv` writes the matrix as comma-separated values
+    /// with a `Part:Emotion` header by column.
+    pub fn write_csv<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "Sheet,{}",
+                 self.columns.iter()
+                             .map(|&Tuple { part, emotion }|
+                                  format!("{:?}:{:?}", part, emotion))
+                             .collect::<Vec<String>>()
+                             .join(","))
+            .and(self.rows.iter()
+                          .map(|&(ref sheet, ref status)|
+                               writeln!(output, "{},{}",
+                                        sheet.get_name(),
+                                        status.iter()
+                                              .map(|status| status.get_name())
+                                              .collect::<Vec<&str>>()
+                                              .join(",")))
+                          .find(|line| line.is_err())
+                          .unwrap_or_else(|| Ok(())))
+    }
+
+    /// The method `write_html` writes the matrix as a HTML page
+    /// with a class by status.
+    pub fn write_html<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+                          <title>Coverage</title>\n<style>\n\
+                          table {{ border-collapse: collapse; font-family: monospace; }}\n\
+                          th, td {{ border: 1px solid #ccc; padding: 2px 4px; }}\n\
+                          td.present {{ background: #8c8; }}\n\
+                          td.partial {{ background: #ec6; }}\n\
+                          td.missing {{ background: #eee; }}\n\
+                          </style>\n</head>\n<body>\n<table>")
+            .and(writeln!(output, "<tr><th></th>{}</tr>",
+                          self.groups()
+                              .iter()
+                              .map(|&(part, _, len)|
+                                   format!("<th colspan=\"{}\">{:?}</th>", len, part))
+                              .collect::<String>()))
+            .and(writeln!(output, "<tr><th></th>{}</tr>",
+                          self.columns.iter()
+                                      .map(|tuple| format!("<th>{:?}</th>", tuple.emotion))
+                                      .collect::<String>()))
+            .and(self.rows.iter()
+                          .map(|&(ref sheet, ref status)|
+                               writeln!(output, "<tr><th>{}</th>{}</tr>",
+                                        sheet.get_name(),
+                                        status.iter()
+                                              .map(|status| match *status {
+                                                  Status::Partial(glyph, cell) => {
+                                                      format!("<td class=\"{}\" title=\"{}/{}\">{}</td>",
+                                                              status.get_name(), glyph, cell,
+                                                              status.get_mark())
+                                                  },
+                                                  _ => format!("<td class=\"{}\">{}</td>",
+                                                               status.get_name(),
+                                                               status.get_mark()),
+                                              })
+                                              .collect::<String>()))
+                          .find(|line| line.is_err())
+                          .unwrap_or_else(|| Ok(())))
+            .and(writeln!(output, "</table>\n</body>\n</html>"))
+    }
+}
@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::fmt;
+
+use super::super::emotion::EmotionError;
+use super::super::sprite::texel::part::PartError;
+
+pub type Result<T> = ::std::result::Result<T, EffectError>;
+
+
+/// The enum `EffectError` defines the possible errors
+/// from constructor Effect.
+#[derive(Clone, Debug)]
+pub enum EffectError {
+    /// The name isn't a word of letters and digits.
+    UnvalidName(String),
+    /// Unvalid effect syntax.
+    SyntaxEffect(String),
+    /// The anchor is out of the draw.
+    OutOfBoard(String),
+    /// The effect isn't defined.
+    UnknownEffect(String),
+    /// The Part interface has meet an error.
+    Part(PartError),
+    /// The Emotion interface has meet an error.
+    Emotion(EmotionError),
+}
+
+impl fmt::Display for EffectError {
+    /// The function `fmt` formats the value using
+    /// the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EffectError::UnvalidName(ref name) => {
+                write!(f, "the name `{}` isn't a word of letters and digits", name)
+            },
+            EffectError::SyntaxEffect(ref line) => {
+                write!(f, "the effect line `{}` is malformed", line)
+            },
+            EffectError::OutOfBoard(ref name) => {
+                write!(f, "the anchor of the effect `{}` is out of the draw", name)
+            },
+            EffectError::UnknownEffect(ref name) => {
+                write!(f, "the effect `{}` isn't defined", name)
+            },
+            EffectError::Part(_) => write!(f, "the part of the effect is invalid"),
+            EffectError::Emotion(_) => write!(f, "the emotion of the effect is invalid"),
+        }
+    }
+}
+
+impl Error for EffectError {
+    /// The function `description` returns a short description of
+    /// the error.
+    fn description(&self) -> &str {
+        match *self {
+            EffectError::UnvalidName(ref name) => name,
+            EffectError::SyntaxEffect(ref line) => line,
+            EffectError::OutOfBoard(ref name) => name,
+            EffectError::UnknownEffect(ref name) => name,
+            EffectError::Part(_) => "The Part interface has meet an error.",
+            EffectError::Emotion(_) => "The Emotion interface has meet an error.",
+        }
+    }
+
+    /// The function `source` returns the lower-level source of
+    /// this error if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            EffectError::Part(ref why) => Some(why),
+            EffectError::Emotion(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
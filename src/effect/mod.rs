@@ -0,0 +1,286 @@
+mod err;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Read, Write};
+use std::io;
+use std::ops::Not;
+use std::str::FromStr;
+
+pub use self::err::{EffectError, Result};
+
+use super::emotion::Emotion;
+use super::grammar;
+use super::sprite::draw::{Draw, Layer, SPEC_MAX_X, SPEC_MAX_Y, SPEC_MAX_XY};
+use super::sprite::texel::Texel;
+use super::sprite::texel::part::Part;
+use super::template::SPEC_KEYWORD_FOR;
+use super::tuple::Tuple;
+use super::GraphicError;
+
+/// The keyword before the anchor of an effect.
+pub const SPEC_KEYWORD_AT: &'static str = " at ";
+
+/// The struct `Frame` is a draw of effect, the rows of emotion
+/// by part held for a duration in milliseconds.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    rows: Vec<Vec<Tuple>>,
+    duration: i64,
+}
+
+impl Frame {
+    /// The constructor `new` returns a frame from its rows of emotion
+    /// by part and its duration.
+    pub fn new(rows: Vec<Vec<Tuple>>, duration: i64) -> Self {
+        Frame {
+            rows: rows,
+            duration: duration,
+        }
+    }
+
+    /// The accessor method `get_rows` returns the rows of emotion by part.
+    pub fn get_rows(&self) -> &Vec<Vec<Tuple>> {
+        &self.rows
+    }
+
+    /// The accessor method `get_duration` returns the duration
+    /// in milliseconds.
+    pub fn get_duration(&self) -> i64 {
+        self.duration
+    }
+}
+
+impl fmt::Display for Frame {
+    /// The function `fmt` formats the frame like in an effect file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}{}",
+               self.rows.iter()
+                        .map(|row|
+                             row.iter()
+                                .map(|&Tuple { part, emotion }| format!("{:?}:{:?}", part, emotion))
+                                .collect::<Vec<String>>()
+                                .join(" "))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+               SPEC_KEYWORD_FOR,
+               self.duration)
+    }
+}
+
+impl FromStr for Frame {
+    type Err = EffectError;
+
+    /// The function `from_str` parses a frame like
+    /// `Heart:None None:None, None:None Heart:None for 200`.
+    fn from_str(frame: &str) -> ::std::result::Result<Self, Self::Err> {
+        match frame.rfind(SPEC_KEYWORD_FOR).map(|position| frame.split_at(position)) {
+            None => Err(EffectError::SyntaxEffect(frame.to_string())),
+            Some((rows, duration)) => {
+                match duration[SPEC_KEYWORD_FOR.len()..].trim().parse::<i64>() {
+                    Ok(duration) if duration > 0 => {
+                        rows.split(',')
+                            .map(|row|
+                                 row.split_whitespace()
+                                    .map(|pair|
+                                         match pair.split(':').collect::<Vec<&str>>().as_slice() {
+                                             &[part, emotion] => {
+                                                 match (Part::new(part), Emotion::new(emotion)) {
+                                                     (Err(why), _) => Err(EffectError::Part(why)),
+                                                     (_, Err(why)) => Err(EffectError::Emotion(why)),
+                                                     (Ok(part), Ok(emotion)) => {
+                                                         Ok(Tuple::from((part, emotion)))
+                                                     },
+                                                 }
+                                             },
+                                             _ => Err(EffectError::SyntaxEffect(frame.to_string())),
+                                         })
+                                    .collect::<Result<Vec<Tuple>>>())
+                            .collect::<Result<Vec<Vec<Tuple>>>>()
+                            .and_then(|rows|
+                                 if rows.iter().all(|row| row.is_empty()) {
+                                     Err(EffectError::SyntaxEffect(frame.to_string()))
+                                 } else {
+                                     Ok(Frame::new(rows, duration))
+                                 })
+                    },
+                    _ => Err(EffectError::SyntaxEffect(frame.to_string())),
+                }
+            },
+        }
+    }
+}
+
+/// The struct `Effect` is a named track of frames anchored at a cell
+/// over the draws of a sprite and played in a loop with its own rhythm,
+/// like `Love at 6 0 = Heart:None for 200; Hearts:None for 200`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Effect {
+    name: String,
+    /// The column and the line of the first cell of the frames.
+    anchor: (usize, usize),
+    frames: Vec<Frame>,
+}
+
+impl Effect {
+    /// The constructor `new` returns an effect from a name of letters
+    /// and digits with its anchor and its frames.
+    pub fn new(name: &str, anchor: (usize, usize), frames: Vec<Frame>) -> Result<Self> {
+        if name.is_empty() || name.chars().all(char::is_alphanumeric).not() {
+            Err(EffectError::UnvalidName(name.to_string()))
+        } else if frames.is_empty() {
+            Err(EffectError::SyntaxEffect(name.to_string()))
+        } else if anchor.0 >= SPEC_MAX_X || anchor.1 >= SPEC_MAX_Y {
+            Err(EffectError::OutOfBoard(name.to_string()))
+        } else {
+            Ok(Effect {
+                name: name.to_string(),
+                anchor: anchor,
+                frames: frames,
+            })
+        }
+    }
+
+    /// The accessor method `get_name` returns the name.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The accessor method `get_anchor` returns the column and
+    /// the line of the first cell.
+    pub fn get_anchor(&self) -> (usize, usize) {
+        self.anchor
+    }
+
+    /// The accessor method `get_frames` returns the frames.
+    pub fn get_frames(&self) -> &Vec<Frame> {
+        &self.frames
+    }
+
+    /// The accessor method `get_duration` returns the duration
+    /// of a loop in milliseconds.
+    pub fn get_duration(&self) -> i64 {
+        self.frames.iter()
+                   .map(|frame| frame.duration)
+                   .sum()
+    }
+
+    /// The accessor method `get_index` returns the index of the frame
+    /// played after the elapsed milliseconds.
+    pub fn get_index(&self, elapsed: i64) -> usize {
+        let mut elapsed: i64 = elapsed.checked_rem(self.get_duration()).unwrap_or_default();
+
+        self.frames.iter()
+                   .position(|frame|
+                        if elapsed < frame.duration {
+                            true
+                        } else {
+                            elapsed -= frame.duration;
+                            false
+                        })
+                   .unwrap_or_default()
+    }
+
+    /// The method `compose` returns the draw with the frame played after
+    /// the elapsed milliseconds over its effect layer, with the texels of
+    /// its posture; the cells out of the draw are clipped and the cells
+    /// without part or without texel let see the layer.
+    pub fn compose(&self, base: &Draw, elapsed: i64, texels: &HashMap<Tuple, Vec<Texel>>) -> Draw {
+        let mut draw: Draw = *base;
+        let mut cells: [(Emotion, Texel); SPEC_MAX_XY] = *base.get_layer_cells(&Layer::Effect);
+        let mut count: HashMap<Part, usize> = HashMap::new();
+        let (anchor_x, anchor_y): (usize, usize) = self.anchor;
+
+        if let Some(frame) = self.frames.get(self.get_index(elapsed)) {
+            frame.rows.iter().enumerate().all(|(y, row)|
+                row.iter()
+                   .enumerate()
+                   .filter(|&(_, tuple)| tuple.part.not_empty().is_some())
+                   .all(|(x, tuple)| {
+                       let nth: &mut usize = count.entry(tuple.part).or_insert(0);
+                       let (x, y): (usize, usize) = (anchor_x + x, anchor_y + y);
+
+                       if let Some(texel) = texels.get(tuple).and_then(|texels| texels.get(*nth)) {
+                           if x < SPEC_MAX_X && y < SPEC_MAX_Y {
+                               cells[y * SPEC_MAX_X + x] = (tuple.emotion, *texel);
+                           }
+                       }
+                       *nth += 1;
+                       true
+                   }));
+        }
+        draw.set_layer_cells(&Layer::Effect, &cells).ok();
+        draw
+    }
+}
+
+impl fmt::Display for Effect {
+    /// The function `fmt` formats the effect as a line of effect file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}{} {} = {}",
+               self.name,
+               SPEC_KEYWORD_AT,
+               self.anchor.0,
+               self.anchor.1,
+               self.frames.iter()
+                          .map(|frame| frame.to_string())
+                          .collect::<Vec<String>>()
+                          .join("; "))
+    }
+}
+
+impl FromStr for Effect {
+    type Err = EffectError;
+
+    /// The function `from_str` parses a line of effect file,
+    /// the anchor is the first cell without `at`.
+    fn from_str(line: &str) -> ::std::result::Result<Self, Self::Err> {
+        match line.find('=').map(|position| line.split_at(position)) {
+            None => Err(EffectError::SyntaxEffect(line.to_string())),
+            Some((head, frames)) => {
+                let (name, anchor): (&str, Result<(usize, usize)>) =
+                    match head.find(SPEC_KEYWORD_AT) {
+                        None => (head, Ok((0, 0))),
+                        Some(position) => (
+                            &head[..position],
+                            match *head[position + SPEC_KEYWORD_AT.len()..].split_whitespace()
+                                                                           .map(|number| number.parse::<usize>())
+                                                                           .collect::<Vec<_>>()
+                                                                           .as_slice() {
+                                [Ok(x), Ok(y)] => Ok((x, y)),
+                                _ => Err(EffectError::SyntaxEffect(line.to_string())),
+                            },
+                        ),
+                    };
+
+                anchor.and_then(|anchor|
+                    frames[1..].split(';')
+                               .map(str::trim)
+                               .filter(|frame| frame.is_empty().not())
+                               .map(|frame| frame.parse::<Frame>())
+                               .collect::<Result<Vec<Frame>>>()
+                               .and_then(|frames| Effect::new(name.trim(), anchor, frames)))
+            },
+        }
+    }
+}
+
+/// The function `compose_all` returns the draw with the frames of the
+/// effects played after their elapsed milliseconds, the last effect
+/// over the others.
+pub fn compose_all(base: &Draw, effects: &[(&Effect, i64)],
+                   texels: &HashMap<Tuple, Vec<Texel>>) -> Draw {
+    effects.iter()
+           .fold(*base, |draw: Draw, &(effect, elapsed)| effect.compose(&draw, elapsed, texels))
+}
+
+/// The function `read_effects` returns the effects of an
+/// effect file from a readable source.
+pub fn read_effects<R: Read>(source: R) -> ::Result<Vec<Effect>> {
+    grammar::definitions(source, GraphicError::Effect)
+}
+
+/// The function `write_effects` writes a line by effect.
+pub fn write_effects<W: Write>(output: W, effects: &[Effect]) -> io::Result<()> {
+    grammar::write_definitions(output, effects)
+}
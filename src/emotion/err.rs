@@ -9,13 +9,22 @@ pub type Result<T> = ::std::result::Result<T, EmotionError>;
 #[derive(Clone, Debug)]
 pub enum EmotionError {
     UnknownEmotion(String),
+    /// The level of intensity isn't in the scale.
+    UnvalidLevel(String),
 }
 
 impl fmt::Display for EmotionError {
     /// The function `fmt` formats the value using
     /// the given formatter.
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EmotionError::UnknownEmotion(ref name) => {
+                write!(f, "the emotion `{}` is unknown", name)
+            },
+            EmotionError::UnvalidLevel(ref level) => {
+                write!(f, "the level `{}` is out of the scale", level)
+            },
+        }
     }
 }
 
@@ -25,12 +34,13 @@ impl Error for EmotionError {
   fn description(&self) -> &str {
       match *self {
          EmotionError::UnknownEmotion(ref name) => name,
+         EmotionError::UnvalidLevel(ref name) => name,
       }
   }
 
-    /// The function `cause` returns the lower-level cause of
+    /// The function `source` returns the lower-level source of
     /// this error if any.
-    fn cause(&self) -> Option<&Error> {
+    fn source(&self) -> Option<&(Error + 'static)> {
         None
     }
 }
@@ -0,0 +1,80 @@
+use std::fmt;
+
+use super::{Emotion, EmotionError, Result};
+
+/// The lowest level of intensity, slightly.
+pub const SPEC_LEVEL_MIN: u8 = 1;
+/// The highest level of intensity, very.
+pub const SPEC_LEVEL_MAX: u8 = 5;
+/// The level of the texels declared without level.
+pub const SPEC_LEVEL_DEFAULT: u8 = 3;
+
+/// The struct `Intensity` is an emotion with its level
+/// from `SPEC_LEVEL_MIN` to `SPEC_LEVEL_MAX`.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct Intensity {
+    emotion: Emotion,
+    level: u8,
+}
+
+impl Intensity {
+    /// The constructor `new` parses an emotion with an optional level
+    /// like `Happy` or `Happy@5`.
+    pub fn new(content: &str) -> Result<Self> {
+        let mut split = content.splitn(2, '@');
+
+        match (split.next().map(Emotion::new), split.next()) {
+            (Some(Err(why)), _) => Err(why),
+            (Some(Ok(emotion)), None) => Ok(Intensity::from((emotion, SPEC_LEVEL_DEFAULT))),
+            (Some(Ok(emotion)), Some(level)) => {
+                match level.parse::<u8>() {
+                    Ok(level @ SPEC_LEVEL_MIN...SPEC_LEVEL_MAX) => {
+                        Ok(Intensity::from((emotion, level)))
+                    },
+                    _ => Err(EmotionError::UnvalidLevel(content.to_string())),
+                }
+            },
+            (None, _) => Err(EmotionError::UnknownEmotion(content.to_string())),
+        }
+    }
+
+    /// The accessor method `get_emotion` returns the emotion.
+    pub fn get_emotion(&self) -> &Emotion {
+        &self.emotion
+    }
+
+    /// The accessor method `get_level` returns the level.
+    pub fn get_level(&self) -> u8 {
+        self.level
+    }
+
+    /// The accessor method `is_default` returns true if the level is
+    /// the level of the texels declared without level.
+    pub fn is_default(&self) -> bool {
+        self.level.eq(&SPEC_LEVEL_DEFAULT)
+    }
+}
+
+impl From<(Emotion, u8)> for Intensity {
+    /// The constructor `from` returns an intensity with the level
+    /// bounded to the scale.
+    fn from((emotion, level): (Emotion, u8)) -> Intensity {
+        Intensity {
+            emotion: emotion,
+            level: level.max(SPEC_LEVEL_MIN).min(SPEC_LEVEL_MAX),
+        }
+    }
+}
+
+impl From<Emotion> for Intensity {
+    fn from(emotion: Emotion) -> Intensity {
+        Intensity::from((emotion, SPEC_LEVEL_DEFAULT))
+    }
+}
+
+impl fmt::Display for Intensity {
+    /// The function `fmt` formats the intensity like a texel file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}@{}", self.emotion, self.level)
+    }
+}
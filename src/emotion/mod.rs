@@ -1,9 +1,11 @@
 mod err;
+pub mod intensity;
 
 use std::fmt;
 use std::char;
 
 pub use self::err::{EmotionError, Result};
+pub use self::intensity::Intensity;
 
 #[repr(u32)]
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
@@ -28,6 +30,19 @@ pub enum Emotion {
     Speechless = 0x65,
 }
 
+/// The list of all Emotions.
+static SPEC_EMOTION_LIST: [Emotion; 9] = [
+    Emotion::None,
+    Emotion::Angry,
+    Emotion::Happy,
+    Emotion::Love,
+    Emotion::Malicious,
+    Emotion::Misunderstanding,
+    Emotion::Shocked,
+    Emotion::Sleepy,
+    Emotion::Speechless,
+];
+
 impl Emotion {
     pub fn new(content: &str) -> Result<Self> {
         match content {
@@ -44,6 +59,11 @@ impl Emotion {
         }
     }
 
+    /// The function `list` returns all the Emotions.
+    pub fn list() -> &'static [Emotion] {
+        &SPEC_EMOTION_LIST
+    }
+
     /// The accessor method `is_none` returns a boolean
     /// for None, axiom of emotion.
     pub fn is_none(&self) -> bool {
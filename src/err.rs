@@ -2,10 +2,19 @@ use std::error::Error;
 use std::fmt;
 use std::io;
 use std::env;
+use std::path::{Path, PathBuf};
 
 use super::sheet::SheetError;
 use super::emotion::EmotionError;
+use super::expression::ExpressionError;
+use super::grammar::GrammarError;
+use super::machine::MachineError;
+use super::sprite::SpriteError;
+use super::template::TemplateError;
+use super::effect::EffectError;
+use super::transition::TransitionError;
 use super::sprite::draw::DrawError;
+use super::sprite::draw::layer::LayerError;
 use super::sprite::texel::TexelError;
 use super::sprite::texel::part::PartError;
 
@@ -16,27 +25,45 @@ pub type Result<T> = ::std::result::Result<T, GraphicError>;
 #[derive(Debug)]
 pub enum GraphicError {
     /// Can't read the sub-directory.
-    ReadDir(io::Error),
+    ReadDir(PathBuf, io::Error),
     /// Can't open the file.
-    OpenFile(io::Error),
-    /// Can't read the file.
-    ReadFile(io::Error),
+    OpenFile(PathBuf, io::Error),
+    /// Can't read the file, from a path or a readable source.
+    ReadFile(Option<PathBuf>, io::Error),
+    /// Can't write the file.
+    WriteFile(PathBuf, io::Error),
     /// Can't create the texel sub-directory.
     MkDirTexel(io::Error),
     /// Can't create the sprite sub-directory.
     MkDirSprite(io::Error),
+    /// The Sprite interface has meet an error.
+    Sprite(SpriteError),
     /// The Draw interface has meet an error.
     Draw(DrawError),
+    /// The Layer interface has meet an error.
+    Layer(LayerError),
     /// The Emotion interface has meet an error.
     Emotion(EmotionError),
+    /// The Expression interface has meet an error.
+    Expression(ExpressionError),
     /// The Texel interface has meet an error.
     Texel(TexelError),
     /// Can't split the chunk of sprite.
     SpriteSplitFirst(String),
     /// The Part interface has meet an error.
     Part(PartError),
+    /// The Machine interface has meet an error.
+    Machine(MachineError),
+    /// The Grammar interface has meet an error.
+    Grammar(GrammarError),
     /// The Sheet interface has meet an error.
     Sheet(SheetError),
+    /// The Transition interface has meet an error.
+    Transition(TransitionError),
+    /// The Template interface has meet an error.
+    Template(TemplateError),
+    /// The Effect interface has meet an error.
+    Effect(EffectError),
     /// Can't found the NEKO_PATH environement variable.
     NekoPath,
     /// Can't found the glyph of texel.
@@ -45,13 +72,86 @@ pub enum GraphicError {
     FoundTexel(String),
     /// Unvalid texel syntax.
     SyntaxTexel(String),
+    /// Can't found the sprite.
+    FoundSprite(String),
+    /// The posture inherits from itself.
+    CycleParent(String),
+    /// The line of a file has meet an error.
+    Line(usize, Box<GraphicError>),
+    /// The file has meet an error.
+    File(PathBuf, Box<GraphicError>),
+}
+
+impl GraphicError {
+    /// The method `with_path` returns the error with the path of the
+    /// file which it comes from, an error which already names a file
+    /// is kept as is.
+    pub fn with_path<P: AsRef<Path>>(self, path: P) -> Self {
+        match self {
+            GraphicError::ReadFile(None, why) => {
+                GraphicError::ReadFile(Some(path.as_ref().to_path_buf()), why)
+            },
+            why @ GraphicError::ReadDir(..) |
+            why @ GraphicError::OpenFile(..) |
+            why @ GraphicError::ReadFile(..) |
+            why @ GraphicError::WriteFile(..) |
+            why @ GraphicError::File(..) => why,
+            why => GraphicError::File(path.as_ref().to_path_buf(), Box::new(why)),
+        }
+    }
 }
 
 impl fmt::Display for GraphicError {
     /// The function `fmt` formats the value using
     /// the given formatter.
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
-       Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GraphicError::ReadDir(ref path, _) => {
+                write!(f, "can't read the sub-directory `{}`", path.display())
+            },
+            GraphicError::OpenFile(ref path, _) => {
+                write!(f, "can't open the file `{}`", path.display())
+            },
+            GraphicError::ReadFile(Some(ref path), _) => {
+                write!(f, "can't read the file `{}`", path.display())
+            },
+            GraphicError::ReadFile(None, _) => write!(f, "can't read the file"),
+            GraphicError::WriteFile(ref path, _) => {
+                write!(f, "can't write the file `{}`", path.display())
+            },
+            GraphicError::MkDirTexel(_) => write!(f, "can't create the texel sub-directory"),
+            GraphicError::MkDirSprite(_) => write!(f, "can't create the sprite sub-directory"),
+            GraphicError::Sheet(_) => write!(f, "the sheet is invalid"),
+            GraphicError::Sprite(_) => write!(f, "the sprite is invalid"),
+            GraphicError::Draw(_) => write!(f, "the draw is invalid"),
+            GraphicError::Layer(_) => write!(f, "the layer is invalid"),
+            GraphicError::Emotion(_) => write!(f, "the emotion is invalid"),
+            GraphicError::Expression(_) => write!(f, "the expression is invalid"),
+            GraphicError::Texel(_) => write!(f, "the texel is invalid"),
+            GraphicError::Part(_) => write!(f, "the part is invalid"),
+            GraphicError::Transition(_) => write!(f, "the transition is invalid"),
+            GraphicError::Template(_) => write!(f, "the template is invalid"),
+            GraphicError::Effect(_) => write!(f, "the effect is invalid"),
+            GraphicError::Machine(_) => write!(f, "the state machine is invalid"),
+            GraphicError::Grammar(_) => write!(f, "the grammar is invalid"),
+            GraphicError::NekoPath => write!(f, "can't find the $NEKO_PATH environment variable"),
+            GraphicError::Glyph => write!(f, "can't find the glyph of the texel"),
+            GraphicError::SpriteSplitFirst(ref chunk) => {
+                write!(f, "can't split the chunk of sprite `{}`", chunk)
+            },
+            GraphicError::FoundTexel(ref name) => write!(f, "can't find the texel `{}`", name),
+            GraphicError::SyntaxTexel(ref line) => {
+                write!(f, "the texel line `{}` is malformed", line)
+            },
+            GraphicError::FoundSprite(ref name) => write!(f, "can't find the sprite `{}`", name),
+            GraphicError::CycleParent(ref name) => {
+                write!(f, "the posture `{}` inherits from itself", name)
+            },
+            GraphicError::Line(line, _) => write!(f, "the line {} is invalid", line),
+            GraphicError::File(ref path, _) => {
+                write!(f, "the file `{}` is invalid", path.display())
+            },
+        }
     }
 }
 
@@ -60,43 +160,80 @@ impl Error for GraphicError {
     /// the error.
     fn description(&self) -> &str {
         match *self {
-            GraphicError::ReadDir(_) => "Can't read the sub-directory.",
-            GraphicError::OpenFile(_) => "Can't open the file.",
-            GraphicError::ReadFile(_) => "Can't read the file.",
+            GraphicError::ReadDir(..) => "Can't read the sub-directory.",
+            GraphicError::OpenFile(..) => "Can't open the file.",
+            GraphicError::ReadFile(..) => "Can't read the file.",
+            GraphicError::WriteFile(..) => "Can't write the file.",
             GraphicError::MkDirTexel(_) => "Can't create the texel sub-directory.",
             GraphicError::MkDirSprite(_) => "Can't create the sprite sub-directory.",
             GraphicError::Sheet(_) => "The Sheet interface has meet an error.",
+            GraphicError::Sprite(_) => "The Sprite interface has meet an error.",
             GraphicError::Draw(_) => "The Draw interface has meet an error.",
+            GraphicError::Layer(_) => "The Layer interface has meet an error.",
             GraphicError::Emotion(_) => "The Emotion interface has meet an error.",
+            GraphicError::Expression(_) => "The Expression interface has meet an error.",
             GraphicError::Texel(_) => "The Texel interface has meet an error.",
             GraphicError::Part(_) => "The Part interface has meet an error.",
+            GraphicError::Transition(_) => "The Transition interface has meet an error.",
+            GraphicError::Template(_) => "The Template interface has meet an error.",
+            GraphicError::Effect(_) => "The Effect interface has meet an error.",
+            GraphicError::Machine(_) => "The Machine interface has meet an error.",
+            GraphicError::Grammar(_) => "The Grammar interface has meet an error.",
             GraphicError::NekoPath => "Can't found the $NEKO_PATH environement variable.",
             GraphicError::Glyph => "Can't found the glyph of texel.",
             GraphicError::SpriteSplitFirst(ref name) => name,
             GraphicError::FoundTexel(ref name) => name,
             GraphicError::SyntaxTexel(ref name) => name,
+            GraphicError::FoundSprite(ref name) => name,
+            GraphicError::CycleParent(ref name) => name,
+            GraphicError::Line(_, _) => "The line of a file has meet an error.",
+            GraphicError::File(_, _) => "The file has meet an error.",
         }
     }
 
-  /// The function `cause` returns the lower-level cause of
+    /// The function `source` returns the lower-level source of
     /// this error if any.
-    fn cause(&self) -> Option<&Error> {
+    fn source(&self) -> Option<&(Error + 'static)> {
         match *self {
-            GraphicError::ReadDir(ref why) |
-            GraphicError::OpenFile(ref why) |
-            GraphicError::ReadFile(ref why) |
+            GraphicError::ReadDir(_, ref why) |
+            GraphicError::OpenFile(_, ref why) |
+            GraphicError::ReadFile(_, ref why) |
+            GraphicError::WriteFile(_, ref why) |
             GraphicError::MkDirTexel(ref why) |
             GraphicError::MkDirSprite(ref why) => Some(why),
             GraphicError::Sheet(ref why) => Some(why),
+            GraphicError::Sprite(ref why) => Some(why),
             GraphicError::Draw(ref why) => Some(why),
+            GraphicError::Layer(ref why) => Some(why),
             GraphicError::Emotion(ref why) => Some(why),
+            GraphicError::Expression(ref why) => Some(why),
             GraphicError::Texel(ref why) => Some(why),
             GraphicError::Part(ref why) => Some(why),
+            GraphicError::Transition(ref why) => Some(why),
+            GraphicError::Template(ref why) => Some(why),
+            GraphicError::Effect(ref why) => Some(why),
+            GraphicError::Machine(ref why) => Some(why),
+            GraphicError::Grammar(ref why) => Some(why),
+            GraphicError::Line(_, ref why) |
+            GraphicError::File(_, ref why) => Some(why.as_ref()),
             _ => None,
         }
     }
 }
 
+/// The function `report` returns the message of an error followed
+/// by the messages of its chain of sources.
+pub fn report(error: &Error) -> String {
+    let mut messages: Vec<String> = vec![error.to_string()];
+    let mut source: Option<&Error> = error.source();
+
+    while let Some(why) = source {
+        messages.push(why.to_string());
+        source = why.source();
+    }
+    messages.join(": ")
+}
+
 impl From<env::VarError> for GraphicError {
     fn from(_: env::VarError) -> GraphicError {
         GraphicError::NekoPath
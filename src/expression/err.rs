@@ -0,0 +1,62 @@
+use std::error::Error;
+use std::fmt;
+
+use super::super::emotion::EmotionError;
+use super::super::sprite::texel::part::PartError;
+
+pub type Result<T> = ::std::result::Result<T, ExpressionError>;
+
+
+/// The enum `ExpressionError` defines the possible errors
+/// from constructor Expression.
+#[derive(Clone, Debug)]
+pub enum ExpressionError {
+    /// The name isn't a word of letters and digits.
+    UnvalidName(String),
+    /// Unvalid expression syntax.
+    SyntaxExpression(String),
+    /// The Part interface has meet an error.
+    Part(PartError),
+    /// The Emotion interface has meet an error.
+    Emotion(EmotionError),
+}
+
+impl fmt::Display for ExpressionError {
+    /// The function `fmt` formats the value using
+    /// the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ExpressionError::UnvalidName(ref name) => {
+                write!(f, "the name `{}` isn't a word of letters and digits", name)
+            },
+            ExpressionError::SyntaxExpression(ref line) => {
+                write!(f, "the expression line `{}` is malformed", line)
+            },
+            ExpressionError::Part(_) => write!(f, "the part of the expression is invalid"),
+            ExpressionError::Emotion(_) => write!(f, "the emotion of the expression is invalid"),
+        }
+    }
+}
+
+impl Error for ExpressionError {
+    /// The function `description` returns a short description of
+    /// the error.
+    fn description(&self) -> &str {
+        match *self {
+            ExpressionError::UnvalidName(ref name) => name,
+            ExpressionError::SyntaxExpression(ref line) => line,
+            ExpressionError::Part(_) => "The Part interface has meet an error.",
+            ExpressionError::Emotion(_) => "The Emotion interface has meet an error.",
+        }
+    }
+
+    /// The function `source` returns the lower-level source of
+    /// this error if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            ExpressionError::Part(ref why) => Some(why),
+            ExpressionError::Emotion(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+mod err;
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::ops::{Not, Range};
+use std::str::FromStr;
+
+pub use self::err::{ExpressionError, Result};
+
+use super::emotion::Emotion;
+use super::sprite::SPEC_MAX_DRAW;
+use super::sprite::draw::{Draw, SPEC_MAX_XY};
+use super::sprite::texel::part::Part;
+use super::tuple::Tuple;
+use super::grammar;
+use super::GraphicError;
+
+/// The struct `Expression` is a named list of emotion by part,
+/// like `Smug = EyeLeft:Malicious, EyeRight:Happy, Mouth:Happy`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Expression {
+    name: String,
+    tuples: Vec<Tuple>,
+}
+
+impl Expression {
+    /// The constructor `new` returns an expression from a name of letters
+    /// and digits with its list of emotion by part, a part is kept once.
+    pub fn new(name: &str, tuples: &[Tuple]) -> Result<Self> {
+        if name.is_empty().not() && name.chars().all(char::is_alphanumeric) {
+            let mut expression: Expression = Expression {
+                name: name.to_string(),
+                tuples: Vec::with_capacity(tuples.len()),
+            };
+
+            tuples.iter().all(|tuple| {
+                if expression.tuples.iter().any(|other| other.part.eq(&tuple.part)).not() {
+                    expression.tuples.push(*tuple);
+                }
+                true
+            });
+            Ok(expression)
+        } else {
+            Err(ExpressionError::UnvalidName(name.to_string()))
+        }
+    }
+
+    /// The constructor `from_draw` returns an expression named from
+    /// the emotion of each part of the draw.
+    pub fn from_draw(name: &str, draw: &Draw) -> Result<Self> {
+        Expression::new(name,
+                        draw.into_iter()
+                            .filter(|&&(_, ref texel)| texel.get_part().ne(&Part::None))
+                            .map(|&(emotion, ref texel)| Tuple::from((*texel.get_part(), emotion)))
+                            .collect::<Vec<Tuple>>()
+                            .as_slice())
+    }
+
+    /// The accessor method `get_name` returns the name.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The accessor method `get_tuples` returns the emotion by part.
+    pub fn get_tuples(&self) -> &Vec<Tuple> {
+        &self.tuples
+    }
+
+    /// The method `get_change` returns the change of `explicite_emotion`
+    /// which applies the expression on the range of draws.
+    pub fn get_change(&self, range: Range<usize>) -> [[Tuple; SPEC_MAX_XY]; SPEC_MAX_DRAW] {
+        let mut change: [[Tuple; SPEC_MAX_XY]; SPEC_MAX_DRAW] =
+            [[Tuple::default(); SPEC_MAX_XY]; SPEC_MAX_DRAW];
+
+        change.iter_mut()
+              .enumerate()
+              .filter(|&(index, _)| range.start <= index && index < range.end)
+              .all(|(_, draw)|
+                   draw.iter_mut()
+                       .zip(self.tuples.iter())
+                       .all(|(cell, tuple)| {
+                           cell.clone_from(tuple);
+                           true
+                       }));
+        change
+    }
+}
+
+impl fmt::Display for Expression {
+    /// The function `fmt` formats the expression as a line of expression file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}",
+               self.name,
+               self.tuples.iter()
+                          .map(|&Tuple { part, emotion }| format!("{:?}:{:?}", part, emotion))
+                          .collect::<Vec<String>>()
+                          .join(", "))
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    /// The function `from_str` parses a line of expression file.
+    fn from_str(line: &str) -> ::std::result::Result<Self, Self::Err> {
+        match line.find('=').map(|position| line.split_at(position)) {
+            None => Err(ExpressionError::SyntaxExpression(line.to_string())),
+            Some((name, tuples)) => {
+                tuples[1..].split(',')
+                           .map(str::trim)
+                           .filter(|pair| pair.is_empty().not())
+                           .map(|pair|
+                                match pair.split(':').collect::<Vec<&str>>().as_slice() {
+                                    &[part, emotion] => {
+                                        match (Part::new(part), Emotion::new(emotion)) {
+                                            (Err(why), _) => Err(ExpressionError::Part(why)),
+                                            (_, Err(why)) => Err(ExpressionError::Emotion(why)),
+                                            (Ok(part), Ok(emotion)) => {
+                                                Ok(Tuple::from((part, emotion)))
+                                            },
+                                        }
+                                    },
+                                    _ => Err(ExpressionError::SyntaxExpression(line.to_string())),
+                                })
+                           .collect::<Result<Vec<Tuple>>>()
+                           .and_then(|tuples| Expression::new(name.trim(), &tuples))
+            },
+        }
+    }
+}
+
+/// The function `read_expressions` returns the expressions of an
+/// expression file from a readable source.
+pub fn read_expressions<R: Read>(source: R) -> ::Result<Vec<Expression>> {
+    grammar::definitions(source, GraphicError::Expression)
+}
+
+/// The function `write_expressions` writes a line by expression.
+pub fn write_expressions<W: Write>(output: W, expressions: &[Expression]) -> io::Result<()> {
+    grammar::write_definitions(output, expressions)
+}
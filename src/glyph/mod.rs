@@ -0,0 +1,143 @@
+use std::char;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::ops::Not;
+
+use super::Graphic;
+use super::sheet::Sheet;
+use super::sprite::texel::{SPEC_GLYPH_MIN, SPEC_GLYPH_MAX};
+use super::tuple::Tuple;
+
+/// The struct `GlyphMap` is the dictionary of the owners of each
+/// allocated glyph of the private unicode range.
+#[derive(Clone, Debug, Default)]
+pub struct GlyphMap {
+    /// The owners, postures and pair, by glyph.
+    glyph: BTreeMap<char, Vec<(Sheet, Tuple)>>,
+}
+
+impl GlyphMap {
+    /// The constructor `new` returns the map of the texels of the Graphic.
+    pub fn new(graphic: &Graphic) -> Self {
+        let mut map: GlyphMap = GlyphMap::default();
+
+        graphic.get_texel_dictionary()
+               .iter()
+               .flat_map(|(&sheet, texels)|
+                         texels.iter()
+                               .flat_map(move |(&tuple, texels)|
+                                         texels.iter()
+                                               .map(move |texel|
+                                                    (texel.get_glyph(), sheet, tuple))))
+               .all(|(glyph, sheet, tuple)| {
+                   let owners = map.glyph.entry(glyph).or_insert_with(Vec::new);
+
+                   if owners.contains(&(sheet, tuple)).not() {
+                       owners.push((sheet, tuple));
+                       owners.sort_by_key(|&(sheet, _)| sheet.get_name());
+                   }
+                   true
+               });
+        map
+    }
+
+    /// The accessor method `get_owners` returns the postures and pairs
+    /// which use the glyph.
+    pub fn get_owners(&self, glyph: char) -> Option<&Vec<(Sheet, Tuple)>> {
+        self.glyph.get(&glyph)
+    }
+
+    /// The accessor method `get_pairs` returns the different pairs
+    /// which use the glyph.
+    pub fn get_pairs(&self, glyph: char) -> Vec<Tuple> {
+        let mut pairs: Vec<Tuple> = Vec::new();
+
+        self.glyph.get(&glyph)
+                  .map(|owners|
+                       owners.iter()
+                             .all(|&(_, tuple)| {
+                                 if pairs.contains(&tuple).not() {
+                                     pairs.push(tuple);
+                                 }
+                                 true
+                             }));
+        pairs
+    }
+
+    /// The method `collisions` returns the glyphs which are used
+    /// by more than one pair of Part and Emotion.
+    pub fn collisions(&self) -> Vec<(char, Vec<Tuple>)> {
+        self.glyph.keys()
+                  .map(|&glyph| (glyph, self.get_pairs(glyph)))
+                  .filter(|&(_, ref pairs)| pairs.len() > 1)
+                  .collect::<Vec<(char, Vec<Tuple>)>>()
+    }
+
+    /// The method `unused` returns the ranges of the private unicode
+    /// range without texel.
+    pub fn unused(&self) -> Vec<(char, char)> {
+        let mut ranges: Vec<(char, char)> = Vec::new();
+
+        (SPEC_GLYPH_MIN as u32..SPEC_GLYPH_MAX as u32 + 1)
+            .filter_map(char::from_u32)
+            .filter(|glyph| self.glyph.contains_key(glyph).not())
+            .all(|glyph| {
+                match ranges.last_mut() {
+                    Some(&mut (_, ref mut last)) if (*last as u32) + 1 == glyph as u32 => {
+                        *last = glyph;
+                    },
+                    _ => ranges.push((glyph, glyph)),
+                }
+                true
+            });
+        ranges
+    }
+
+    /// The method `suggest` returns the first free run of `count` glyphs,
+    /// or else the first `count` free glyphs.
+    pub fn suggest(&self, count: usize) -> Vec<char> {
+        let unused: Vec<(char, char)> = self.unused();
+
+        unused.iter()
+              .find(|&&(first, last)| (last as u32 - first as u32) as usize + 1 >= count)
+              .map(|&(first, _)|
+                   (first as u32..first as u32 + count as u32)
+                       .filter_map(char::from_u32)
+                       .collect::<Vec<char>>())
+              .unwrap_or_else(||
+                   unused.iter()
+                         .flat_map(|&(first, last)|
+                                   (first as u32..last as u32 + 1).filter_map(char::from_u32))
+                         .take(count)
+                         .collect::<Vec<char>>())
+    }
+
+    /// The method `write_text` writes a line by glyph with its owners,
+    /// the collisions are marked by `!`.
+    pub fn write_text<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        self.glyph.iter()
+                  .map(|(&glyph, owners)|
+                       writeln!(output, "{}U+{:04X} {} {}",
+                                if self.get_pairs(glyph).len() > 1 { '!' } else { ' ' },
+                                glyph as u32,
+                                glyph,
+                                owners.iter()
+                                      .map(|&(sheet, Tuple { part, emotion })|
+                                           format!("{}/{:?}:{:?}", sheet, part, emotion))
+                                      .collect::<Vec<String>>()
+                                      .join(" ")))
+                  .find(|line| line.is_err())
+                  .unwrap_or_else(|| Ok(()))
+                  .and(writeln!(output, "unused: {}",
+                                self.unused()
+                                    .iter()
+                                    .map(|&(first, last)|
+                                         if first.eq(&last) {
+                                             format!("U+{:04X}", first as u32)
+                                         } else {
+                                             format!("U+{:04X}..U+{:04X}", first as u32, last as u32)
+                                         })
+                                    .collect::<Vec<String>>()
+                                    .join(", ")))
+    }
+}
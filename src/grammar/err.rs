@@ -0,0 +1,65 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+pub type Result<T> = ::std::result::Result<T, GrammarError>;
+
+/// The enum `GrammarError` defines the possible errors
+/// from the grammar of the asset files.
+#[derive(Debug)]
+pub enum GrammarError {
+    /// Can't read the source.
+    ReadFile(io::Error),
+    /// Can't open the included file.
+    OpenInclude(io::Error),
+    /// The file includes itself.
+    CycleInclude(String),
+    /// Unvalid version header.
+    UnvalidVersion(String),
+    /// The draw hasn't all its pairs before the separator.
+    SyntaxDraw(usize),
+}
+
+impl fmt::Display for GrammarError {
+    /// The function `fmt` formats the value using
+    /// the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GrammarError::ReadFile(_) => write!(f, "can't read the source"),
+            GrammarError::OpenInclude(_) => write!(f, "can't open the included file"),
+            GrammarError::CycleInclude(ref path) => {
+                write!(f, "the file `{}` includes itself", path)
+            },
+            GrammarError::UnvalidVersion(ref version) => {
+                write!(f, "the version `{}` is unknown", version)
+            },
+            GrammarError::SyntaxDraw(line) => {
+                write!(f, "the draw of the line {} hasn't all its pairs", line)
+            },
+        }
+    }
+}
+
+impl Error for GrammarError {
+    /// The function `description` returns a short description of
+    /// the error.
+    fn description(&self) -> &str {
+        match *self {
+            GrammarError::ReadFile(_) => "Can't read the source.",
+            GrammarError::OpenInclude(_) => "Can't open the included file.",
+            GrammarError::CycleInclude(ref path) => path,
+            GrammarError::UnvalidVersion(ref version) => version,
+            GrammarError::SyntaxDraw(_) => "The draw hasn't all its pairs before the separator.",
+        }
+    }
+
+    /// The function `source` returns the lower-level source of
+    /// this error if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            GrammarError::ReadFile(ref why) |
+            GrammarError::OpenInclude(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
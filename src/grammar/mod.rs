@@ -0,0 +1,277 @@
+pub mod err;
+
+pub use self::err::{GrammarError, Result};
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::prelude::*;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::sprite::draw::SPEC_MAX_XY;
+use super::GraphicError;
+
+/// The last version of the grammar.
+pub const SPEC_VERSION: u8 = 2;
+/// The keyword of the version header.
+pub const SPEC_KEYWORD_VERSION: &'static str = "version";
+/// The keyword of the line which includes a shared texel file.
+pub const SPEC_KEYWORD_INCLUDE: &'static str = "include";
+/// The keyword of the line which declares the parent of postures.
+pub const SPEC_KEYWORD_INHERIT: &'static str = "inherit";
+/// The keyword of the line which applies a template on the draws
+/// of a sprite.
+pub const SPEC_KEYWORD_TEMPLATE: &'static str = "template";
+/// The line between two draws of a sprite from the version 2.
+pub const SPEC_SEPARATOR: &'static str = "---";
+/// The line before the cells of an upper layer of the previous draw.
+pub const SPEC_LAYER_SEPARATOR: &'static str = "+++";
+/// The character which comments the end of a line, at the start
+/// of the line or after a whitespace.
+pub const SPEC_COMMENT: char = '#';
+/// The number of words of a draw, the posture, the duration
+/// and the part with the emotion of each cell.
+pub const SPEC_DRAW_WORD: usize = SPEC_MAX_XY*2+2;
+/// The number of words of a layer, the part with the emotion
+/// of each cell.
+pub const SPEC_LAYER_WORD: usize = SPEC_MAX_XY*2;
+
+/// The struct `Word` is a word of a sprite source with its line
+/// from zero and its columns.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Word<'a> {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub text: &'a str,
+}
+
+/// The function `strip` returns the line without its comment,
+/// its carriage return and its trailing whitespaces, a comment character
+/// inside a word like `ascii=#` is kept.
+pub fn strip(line: &str) -> &str {
+    line.char_indices()
+        .find(|&(index, character)|
+              character.eq(&SPEC_COMMENT) &&
+              line.split_at(index).0.chars().last().map_or(true, char::is_whitespace))
+        .map_or(line, |(index, _)| line.split_at(index).0)
+        .trim_right()
+}
+
+/// The function `version` returns the version of a header line.
+fn version(line: &str) -> Option<Result<u8>> {
+    match *line.split_whitespace().collect::<Vec<&str>>().as_slice() {
+        [SPEC_KEYWORD_VERSION, number] => Some(
+            match number.parse::<u8>() {
+                Ok(version @ 1...SPEC_VERSION) => Ok(version),
+                _ => Err(GrammarError::UnvalidVersion(number.to_string())),
+            }
+        ),
+        _ => None,
+    }
+}
+
+/// The function `lines` returns the version of a source, 1 without
+/// header, with its significant lines numbered from one.
+pub fn lines(buffer: &str) -> Result<(u8, Vec<(usize, &str)>)> {
+    let lines: Vec<(usize, &str)> = buffer.lines()
+                                          .enumerate()
+                                          .map(|(line, content)| (line + 1, strip(content)))
+                                          .filter(|&(_, content)| content.is_empty().not())
+                                          .collect::<Vec<(usize, &str)>>();
+
+    match lines.first().and_then(|&(_, content)| version(content)) {
+        None => Ok((1, lines)),
+        Some(Err(why)) => Err(why),
+        Some(Ok(version)) => Ok((version, lines.into_iter().skip(1).collect())),
+    }
+}
+
+/// The function `definitions` returns the definition of each significant
+/// line of a library file, like the expressions, from a readable source.
+pub fn definitions<R, T, F>(mut source: R, error: F) -> ::Result<Vec<T>>
+    where R: Read, T: FromStr, F: Fn(T::Err) -> GraphicError {
+    let mut buffer: String = String::new();
+
+    try!(source.read_to_string(&mut buffer)
+               .map_err(|why| GraphicError::ReadFile(None, why)));
+    lines(&buffer)
+        .map_err(GraphicError::Grammar)
+        .and_then(|(_, lines)|
+             lines.into_iter()
+                  .map(|(_, line)| line.parse::<T>().map_err(&error))
+                  .collect::<::Result<Vec<T>>>())
+}
+
+/// The function `write_definitions` writes a line by definition.
+pub fn write_definitions<W: Write, T: fmt::Display>(mut output: W, definitions: &[T]) -> io::Result<()> {
+    definitions.iter()
+               .map(|definition| writeln!(output, "{}", definition))
+               .find(|line| line.is_err())
+               .unwrap_or_else(|| Ok(()))
+}
+
+/// The function `include` returns the path of an include line.
+pub fn include(line: &str) -> Option<&str> {
+    let mut words = line.trim().splitn(2, char::is_whitespace);
+
+    match (words.next(), words.next().map(|path| path.trim().trim_matches('"'))) {
+        (Some(SPEC_KEYWORD_INCLUDE), Some(path)) if path.is_empty().not() => Some(path),
+        _ => None,
+    }
+}
+
+/// The function `template` returns the name of a template line.
+pub fn template(line: &str) -> Option<&str> {
+    match *line.split_whitespace().collect::<Vec<&str>>().as_slice() {
+        [SPEC_KEYWORD_TEMPLATE, name] => Some(name),
+        _ => None,
+    }
+}
+
+/// The function `layer` returns the name of a layer line.
+pub fn layer(line: &str) -> Option<&str> {
+    match *line.split_whitespace().collect::<Vec<&str>>().as_slice() {
+        [SPEC_LAYER_SEPARATOR, name] => Some(name),
+        _ => None,
+    }
+}
+
+/// The function `templates` returns the names of the templates
+/// applied on a sprite source.
+pub fn templates(buffer: &str) -> Result<Vec<&str>> {
+    lines(buffer).map(|(_, lines)|
+        lines.into_iter()
+             .filter_map(|(_, content)| template(content))
+             .collect::<Vec<&str>>())
+}
+
+/// The function `inherit` returns the parent and the children
+/// of an inherit line.
+pub fn inherit(line: &str) -> Option<(&str, Vec<&str>)> {
+    match *line.split(|c: char| c.is_whitespace() || "[,]".contains(c))
+               .filter(|word| word.is_empty().not())
+               .collect::<Vec<&str>>()
+               .as_slice() {
+        [SPEC_KEYWORD_INHERIT, parent, ref children..] if children.is_empty().not() => {
+            Some((parent, children.to_vec()))
+        },
+        _ => None,
+    }
+}
+
+/// The function `canonical` returns the key of a file in the
+/// stack of includes.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// The function `include_file` returns the lines of an included
+/// texel file and of its own includes.
+fn include_file(path: &Path, stack: &mut Vec<PathBuf>) -> Result<Vec<(usize, String)>> {
+    let key: PathBuf = canonical(path);
+
+    if stack.contains(&key) {
+        Err(GrammarError::CycleInclude(path.display().to_string()))
+    } else {
+        fs::File::open(path).map_err(GrammarError::OpenInclude)
+                            .and_then(|file| {
+                                stack.push(key);
+                                let lines = texel_with_stack(
+                                    file, path.parent().unwrap_or(Path::new("")), stack
+                                );
+                                stack.pop();
+                                lines
+                            })
+    }
+}
+
+fn texel_with_stack<R: Read>(
+    mut source: R, base: &Path, stack: &mut Vec<PathBuf>
+) -> Result<Vec<(usize, String)>> {
+    let mut buffer: String = String::new();
+
+    try!(source.read_to_string(&mut buffer).map_err(GrammarError::ReadFile));
+    let (_, lines): (u8, Vec<(usize, &str)>) = try!(lines(&buffer));
+
+    lines.into_iter()
+         .map(|(number, line)|
+              match include(line) {
+                  None => Ok(vec![(number, line.to_string())]),
+                  Some(path) => include_file(&base.join(path), stack),
+              })
+         .collect::<Result<Vec<Vec<(usize, String)>>>>()
+         .map(|lines| lines.concat())
+}
+
+/// The function `texel_lines` returns the significant lines of a texel
+/// source with the lines of its includes, which are relative to the
+/// directory of the source's path.
+pub fn texel_lines<R: Read>(source: R, path: Option<&Path>) -> Result<Vec<(usize, String)>> {
+    let mut stack: Vec<PathBuf> = path.map(canonical)
+                                      .into_iter()
+                                      .collect::<Vec<PathBuf>>();
+
+    texel_with_stack(source,
+                     path.and_then(Path::parent).unwrap_or(Path::new("")),
+                     &mut stack)
+}
+
+/// The function `words` splits a line of sprite in words
+/// with their columns.
+pub fn words(line: usize, content: &str) -> Vec<Word> {
+    let mut column: usize = 0;
+
+    content.split(|c: char| c.is_whitespace() || c == ':')
+           .map(|text| {
+               let start: usize = column;
+
+               column += text.chars().count() + 1;
+               Word { line: line, start: start, end: start + text.chars().count(), text: text }
+           })
+           .filter(|word| word.text.is_empty().not())
+           .collect::<Vec<Word>>()
+}
+
+/// The function `draws` returns the version of a sprite source with
+/// the words of each draw, the posture, the duration and the pairs,
+/// or of each layer with the word of its name and its pairs; the version 1 chunks
+/// the words by draw, the version 2 splits the draws at the separators
+/// and the layers at the layer lines, without the template lines.
+pub fn draws(buffer: &str) -> Result<(u8, Vec<(Option<Word>, Vec<Word>)>)> {
+    lines(buffer).map(|(version, lines)| {
+        let lines: Vec<(usize, &str)> = lines.into_iter()
+                                             .filter(|&(_, content)| template(content).is_none())
+                                             .collect::<Vec<(usize, &str)>>();
+
+        if version < 2 {
+            (version,
+             lines.iter()
+                  .flat_map(|&(line, content)| words(line - 1, content))
+                  .collect::<Vec<Word>>()
+                  .chunks(SPEC_DRAW_WORD)
+                  .map(|draw| (None, draw.to_vec()))
+                  .collect::<Vec<(Option<Word>, Vec<Word>)>>())
+        } else {
+            let mut blocks: Vec<(Option<Word>, Vec<Word>)> = vec![(None, Vec::new())];
+
+            lines.iter().all(|&(line, content)| {
+                if content.trim().eq(SPEC_SEPARATOR) {
+                    blocks.push((None, Vec::new()));
+                } else if layer(content).is_some() {
+                    blocks.push((words(line - 1, content).get(1).cloned(), Vec::new()));
+                } else if let Some(&mut (_, ref mut block)) = blocks.last_mut() {
+                    block.extend(words(line - 1, content));
+                }
+                true
+            });
+            (version,
+             blocks.into_iter()
+                   .filter(|&(name, ref block)| name.is_some() || block.is_empty().not())
+                   .collect::<Vec<(Option<Word>, Vec<Word>)>>())
+        }
+    })
+}
@@ -20,8 +20,12 @@ pub enum EditeurError {
 
 impl fmt::Display for EditeurError {
     /// The function `fmt` formats the value using the given formatter.
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EditeurError::Write(_) => write!(f, "can't write on the output"),
+            EditeurError::Raw(_) => write!(f, "can't enter in raw mode"),
+            EditeurError::Graphic(_) => write!(f, "can't load the root"),
+        }
     }
 }
 
@@ -35,8 +39,8 @@ impl Error for EditeurError {
         }
     }
 
-    /// The function `cause` returns the lower-level cause of this error, if any.
-    fn cause(&self) -> Option<&Error> {
+    /// The function `source` returns the lower-level source of this error, if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
         match *self {
             EditeurError::Write(ref err) |
             EditeurError::Raw(ref err) => Some(err),
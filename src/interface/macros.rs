@@ -1,13 +1,21 @@
 #[macro_export]
 #[cfg(not(feature = "clipboard"))]
 macro_rules! editeur_new {
-    ($graphic: expr, $output: expr) => ({
-        use std::io;
+    ($graphic: expr, $watcher: expr, $input: expr, $output: expr, $conflicts: expr, $fallback: expr) => ({
         Editeur {
             graphic: $graphic,
+            watcher: $watcher,
             output: $output,
-            input: io::stdin().events(),
+            input: $input,
             menu: Menu::default(),
+            conflicts: $conflicts,
+            conflict: 0,
+            selection: Selection::default(),
+            region: None,
+            prefix: None,
+            status: None,
+            visible: Layer::list().to_vec(),
+            fallback: $fallback,
         }
     });
 }
@@ -15,15 +23,23 @@ macro_rules! editeur_new {
 #[macro_export]
 #[cfg(feature = "clipboard")]
 macro_rules! editeur_new {
-    ($graphic: expr, $output: expr) => ({
+    ($graphic: expr, $watcher: expr, $input: expr, $output: expr, $conflicts: expr, $fallback: expr) => ({
         use clipboard::ClipboardContext;
-        use std::io;
         Editeur {
             graphic: $graphic,
+            watcher: $watcher,
             output: $output,
-            input: io::stdin().events(),
+            input: $input,
             kopimism: ClipboardContext::new().unwrap(),
             menu: Menu::default(),
+            conflicts: $conflicts,
+            conflict: 0,
+            selection: Selection::default(),
+            region: None,
+            prefix: None,
+            status: None,
+            visible: Layer::list().to_vec(),
+            fallback: $fallback,
         }
     });
 }
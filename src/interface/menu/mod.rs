@@ -10,7 +10,7 @@ impl fmt::Display for Menu {
     #[cfg(not(feature = "clipboard"))]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f,
-               "{}Quit <q>{}",
+               "{}Quit <q> Save <w> Mirror <m> Select <s/S> Yank <y> Put <p> All <a/A+n> Expression <e/E/x+n> Layer <Tab> Visible <z+n> Fallback <f>{}",
                color::Bg(color::Cyan),
                color::Bg(color::Reset))
     }
@@ -18,7 +18,7 @@ impl fmt::Display for Menu {
     #[cfg(feature = "clipboard")]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f,
-               "{}Quit <q> Copy <c> Past <v>{}",
+               "{}Quit <q> Save <w> Mirror <m> Select <s/S> Yank <y> Put <p> All <a/A+n> Expression <e/E/x+n> Layer <Tab> Visible <z+n> Fallback <f> Copy <c> Past <v>{}",
                color::Bg(color::Cyan),
                color::Bg(color::Reset))
     }
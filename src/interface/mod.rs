@@ -1,11 +1,16 @@
 #[macro_use]
 mod macros;
 mod menu;
+mod selection;
 mod err;
 
+use std::env;
 use std::fmt::{self, Display};
 use std::io;
-use std::ops::{BitAnd, Div, Rem};
+use std::ops::{BitAnd, Div, Not, Range, Rem};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "clipboard")]
 use clipboard::ClipboardContext;
@@ -13,13 +18,17 @@ use clipboard::ClipboardContext;
 pub use self::err::{EditeurError, Result};
 
 use self::menu::Menu;
+use self::selection::{Region, Selection};
 
-use editeur::Graphic;
+use editeur::{Graphic, GraphicBuilder, Watcher, SPEC_MAX_DRAW, report};
 use editeur::sheet::Sheet;
 use editeur::emotion::Emotion;
+use editeur::expression::Expression;
 use editeur::sprite::Sprite;
-use editeur::sprite::draw::{Draw, SPEC_MAX_X};
-use editeur::sprite::texel::Texel;
+use editeur::sprite::merge::{Conflict, Side};
+use editeur::sprite::draw::{Draw, Fallback, Layer, SPEC_MAX_X, SPEC_MAX_XY};
+use editeur::sprite::texel::{Texel, SPEC_GLYPH_MIN};
+use editeur::sprite::texel::fallback::SPEC_ENV_FALLBACK;
 use editeur::sprite::texel::part::Part;
 
 use super::termion;
@@ -28,34 +37,154 @@ use termion::event::{Event, MouseEvent, Key};
 use termion::input::{self, TermRead};
 use termion::raw::{self, IntoRawMode};
 
+/// The delay in milliseconds between two reads of the input.
+pub const SPEC_INPUT_DELAY: u64 = 20;
+
+/// The delay in milliseconds to wait the position of the cursor.
+const SPEC_PROBE_TIMEOUT: u64 = 200;
+
+/// The function `cursor_column` returns the range and the column of the
+/// first answer `ESC [ line ; column R` to a request of the cursor position.
+fn cursor_column(bytes: &[u8]) -> Option<(Range<usize>, u16)> {
+    bytes.iter()
+         .enumerate()
+         .filter(|&(_, byte)| byte.eq(&b'\x1b'))
+         .filter_map(|(start, _)|
+              bytes[start..].iter()
+                            .position(|byte| byte.eq(&b'R'))
+                            .and_then(|end| {
+                                let answer: String =
+                                    String::from_utf8_lossy(&bytes[start + 1..start + end])
+                                           .into_owned();
+
+                                if answer.starts_with('[') &&
+                                   answer[1..].chars().all(|c| c.is_digit(10) || c.eq(&';')) {
+                                    answer[1..].split(';')
+                                               .nth(1)
+                                               .and_then(|x| x.parse::<u16>().ok())
+                                               .map(|x| (start..start + end + 1, x))
+                                } else {
+                                    None
+                                }
+                            }))
+         .next()
+}
+
 pub struct Editeur {
     graphic: Graphic,
-    input: input::Events<io::Stdin>,
+    watcher: Watcher,
+    input: input::Events<io::Chain<io::Cursor<Vec<u8>>, termion::AsyncReader>>,
     output: input::MouseTerminal<raw::RawTerminal<io::Stdout>>,
     #[cfg(feature = "clipboard")]
     kopimism: ClipboardContext,
     menu: Menu,
+    conflicts: Vec<(Sheet, Conflict)>,
+    /// The number of the conflict under the cursor among the conflicts
+    /// of the current sprite.
+    conflict: usize,
+    selection: Selection,
+    region: Option<Region>,
+    prefix: Option<char>,
+    status: Option<String>,
+    /// The layers shown by the glyphs of the draws.
+    visible: Vec<Layer>,
+    /// The rendering of the glyphs.
+    fallback: Fallback,
 }
 
 impl Editeur {
-    /// The constructor method `new` returns a Editeur interface.
-    pub fn new() -> Result<Self> {
-        match (io::stdout().into_raw_mode(), Graphic::new()) {
-            (Err(why), _) => Err(EditeurError::Raw(why)),
-            (_, Err(why)) => Err(EditeurError::Graphic(why)),
-            (Ok(stdout), Ok(graphic)) => {
-                use std::io::Write;
-                let mut output = input::MouseTerminal::from(stdout);
-                if let Some(why) = write!(output, "{}",
-                    termion::clear::All,
-                )
-                    .err()
-                    .or(output.flush().err()) {
-                    Err(EditeurError::Write(why))
-                } else {
-                    Ok(editeur_new!(graphic, output))
+    /// The constructor method `new` returns a Editeur interface
+    /// for the texel and sprite root with the rendering of the glyphs.
+    pub fn new(root: PathBuf, fallback: Option<Fallback>) -> Result<Self> {
+        match GraphicBuilder::new().root(root)
+                                   .create_dir(true)
+                                   .build()
+                                   .and_then(|graphic|
+            graphic.get_nct()
+                   .and_then(|nct|
+                       graphic.get_ncs()
+                              .and_then(|ncs| Ok(Watcher::new(nct, ncs))))
+                   .and_then(|watcher|
+                       graphic.get_sprite_list()
+                              .iter()
+                              .map(|&(sheet, _)|
+                                   graphic.read_conflictfile(&sheet)
+                                          .and_then(|conflicts| Ok(
+                                              conflicts.into_iter()
+                                                       .map(|conflict| (sheet, conflict))
+                                                       .collect::<Vec<(Sheet, Conflict)>>())))
+                              .collect::<::editeur::Result<Vec<Vec<(Sheet, Conflict)>>>>()
+                              .and_then(|conflicts| Ok((watcher, conflicts.concat()))))
+                   .and_then(|(watcher, conflicts)| Ok((graphic, watcher, conflicts)))) {
+            Err(why) => Err(EditeurError::Graphic(why)),
+            Ok((graphic, watcher, conflicts)) => match io::stdout().into_raw_mode() {
+                Err(why) => Err(EditeurError::Raw(why)),
+                Ok(stdout) => {
+                    use std::io::{Read, Write};
+                    let mut output = input::MouseTerminal::from(stdout);
+                    let mut stdin: termion::AsyncReader = termion::async_stdin();
+                    let (fallback, pending): (Fallback, Vec<u8>) = match fallback {
+                        Some(fallback) => (fallback, Vec::new()),
+                        None => Editeur::probe(&mut output, &mut stdin, Fallback::probe()),
+                    };
+                    if let Some(why) = write!(output, "{}",
+                        termion::clear::All,
+                    )
+                        .err()
+                        .or(output.flush().err()) {
+                        Err(EditeurError::Write(why))
+                    } else {
+                        Ok(editeur_new!(graphic, watcher,
+                                        io::Cursor::new(pending).chain(stdin).events(),
+                                        output, conflicts, fallback))
+                    }
+                },
+            },
+        }
+    }
+
+    /// The function `probe` measures a private glyph on the terminal when
+    /// the environment leaves the glyphs: a glyph which doesn't take one
+    /// column is rendered in ASCII. The position of the cursor is asked
+    /// on the input of the editor for a while, the bytes around the answer
+    /// are kept for the events and a late answer is skipped as an
+    /// unsupported event. The terminal can't tell if its font draws the
+    /// glyph, a missing glyph takes one column too, so the rendering must
+    /// then be chosen by `--fallback` or `NEKO_FALLBACK`.
+    fn probe<W: io::Write>(output: &mut W, input: &mut termion::AsyncReader,
+                           fallback: Fallback) -> (Fallback, Vec<u8>) {
+        use std::io::Read;
+        let forced: bool = env::var(SPEC_ENV_FALLBACK).map(|value| value.is_empty().not())
+                                                      .unwrap_or_default();
+        let mut bytes: Vec<u8> = Vec::new();
+
+        if forced || fallback.ne(&Fallback::Glyph) ||
+           write!(output, "\r{}\x1b[6n", SPEC_GLYPH_MIN).and(output.flush()).is_err() {
+            (fallback, bytes)
+        } else {
+            let start: Instant = Instant::now();
+            let mut answer: Option<(Range<usize>, u16)> = None;
+
+            while answer.is_none() &&
+                  start.elapsed() < Duration::from_millis(SPEC_PROBE_TIMEOUT) {
+                let mut buffer: [u8; 32] = [0; 32];
+
+                match input.read(&mut buffer) {
+                    Ok(0) | Err(_) => thread::sleep(Duration::from_millis(SPEC_INPUT_DELAY)),
+                    Ok(count) => {
+                        bytes.extend_from_slice(&buffer[..count]);
+                        answer = cursor_column(&bytes);
+                    },
                 }
             }
+            let _ = write!(output, "\r{}", termion::clear::CurrentLine).and(output.flush());
+            match answer {
+                None => (fallback, bytes),
+                Some((range, x)) => {
+                    bytes.drain(range);
+                    (if x == 2 { Fallback::Glyph } else { Fallback::Ascii }, bytes)
+                },
+            }
         }
     }
 
@@ -91,6 +220,170 @@ impl Editeur {
                       .set_contents(command).ok())
     }
 
+    /// The accessor method `get_conflict` returns the conflict under the
+    /// cursor of conflict of the current sprite with its index, its
+    /// number and the count.
+    fn get_conflict(&self) -> Option<(usize, usize, usize, Conflict)> {
+        self.graphic
+            .get_current_sprite()
+            .and_then(|&(sheet, _)| {
+                let indexes: Vec<usize> =
+                    self.conflicts.iter()
+                                  .enumerate()
+                                  .filter(|&(_, &(ref name, _))| name.eq(&sheet))
+                                  .map(|(index, _)| index)
+                                  .collect::<Vec<usize>>();
+
+                indexes.len()
+                       .checked_sub(1)
+                       .map(|last| self.conflict.min(last))
+                       .map(|nth| (indexes[nth], nth, indexes.len(),
+                                   self.conflicts[indexes[nth]].1))
+            })
+    }
+
+    /// The mutator method `goto_conflict` moves the cursors on the
+    /// conflict under the cursor of conflict of the current sprite.
+    fn goto_conflict(&mut self) -> Option<()> {
+        self.get_conflict()
+            .and_then(|(_, nth, _, conflict)| {
+                self.conflict = nth;
+                self.graphic.set_position_sprite_cell(conflict.get_draw(),
+                                                      conflict.get_cell()
+                                                              .unwrap_or_default())
+            })
+    }
+
+    /// The mutator method `next_conflict` advances the cursor of conflict
+    /// to the next conflict of the current sprite, after the last one
+    /// comes the first, and moves the cursors on it.
+    fn next_conflict(&mut self) -> Option<()> {
+        if let Some((_, nth, count, _)) = self.get_conflict() {
+            self.conflict = (nth + 1) % count;
+        }
+        self.goto_conflict()
+    }
+
+    /// The mutator method `resolve_conflict` removes the conflict under
+    /// the cursor of conflict and applies the side, the status tells
+    /// why when there is nothing to resolve or it fails.
+    fn resolve_conflict(&mut self, side: Side) -> Option<()> {
+        match self.get_conflict() {
+            None => self.status = Some("No conflict to resolve".to_string()),
+            Some((index, _, _, conflict)) => {
+                let sheet: Sheet = self.conflicts[index].0;
+
+                if self.graphic.resolve_conflict(&sheet, &conflict, side).is_none() {
+                    self.status = Some(format!("Can't take {} of {}", side, conflict));
+                } else {
+                    self.conflicts.remove(index);
+                    if conflict.is_removal(side) {
+                        let draw: usize = conflict.get_draw();
+
+                        self.conflicts.iter_mut()
+                                      .filter(|&&mut (ref name, ref conflict)|
+                                              name.eq(&sheet) && conflict.get_draw() > draw)
+                                      .all(|&mut (_, ref mut conflict)| {
+                                          *conflict = match *conflict {
+                                              Conflict::Cell { draw, layer, index, ours, theirs } => {
+                                                  Conflict::Cell { draw: draw - 1, layer: layer,
+                                                                   index: index,
+                                                                   ours: ours, theirs: theirs }
+                                              },
+                                              Conflict::Duration { draw, ours, theirs } => {
+                                                  Conflict::Duration { draw: draw - 1,
+                                                                       ours: ours, theirs: theirs }
+                                              },
+                                              Conflict::Posture { draw, ours, theirs } => {
+                                                  Conflict::Posture { draw: draw - 1,
+                                                                      ours: ours, theirs: theirs }
+                                              },
+                                              Conflict::Draw { draw, removed } => {
+                                                  Conflict::Draw { draw: draw - 1,
+                                                                   removed: removed }
+                                              },
+                                              Conflict::Added { draw } => {
+                                                  Conflict::Added { draw: draw - 1 }
+                                              },
+                                          };
+                                          true
+                                      });
+                    }
+                    self.goto_conflict();
+                }
+            },
+        }
+        Some(())
+    }
+
+    /// The method `save` writes the current sprite and its conflicts left,
+    /// the status tells why it can't.
+    fn save(&mut self) -> Option<()> {
+        self.graphic
+            .get_current_sprite()
+            .map(|&(sheet, _)| sheet)
+            .and_then(|sheet| {
+                let conflicts: Vec<Conflict> = self.conflicts.iter()
+                                                   .filter(|&&(ref name, _)| name.eq(&sheet))
+                                                   .map(|&(_, conflict)| conflict)
+                                                   .collect::<Vec<Conflict>>();
+
+                match self.graphic.write_spritefile(&sheet)
+                                  .and(self.graphic.write_conflictfile(&sheet, &conflicts)) {
+                    Err(why) => {
+                        self.status = Some(format!("can't save: {}", report(&why)));
+                        None
+                    },
+                    Ok(()) => Some(()),
+                }
+            })
+    }
+
+    /// The printer method `write_conflict` writes the conflict under the
+    /// cursor of conflict with the keys of its resolution.
+    fn write_conflict(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.get_conflict() {
+            None => Ok(()),
+            Some((_, nth, count, conflict)) => {
+                let (ours, theirs): (String, String) = match conflict {
+                    Conflict::Cell { ours: (ours_emotion, ours_texel),
+                                     theirs: (theirs_emotion, theirs_texel), .. } => (
+                        format!("{:?}:{:?}", ours_texel.get_part(), ours_emotion),
+                        format!("{:?}:{:?}", theirs_texel.get_part(), theirs_emotion),
+                    ),
+                    Conflict::Duration { ours, theirs, .. } => {
+                        (format!("{}ms", ours), format!("{}ms", theirs))
+                    },
+                    Conflict::Posture { ours, theirs, .. } => {
+                        (ours.get_name().to_string(), theirs.get_name().to_string())
+                    },
+                    Conflict::Draw { removed: Side::Ours, .. } => {
+                        ("removed".to_string(), "kept".to_string())
+                    },
+                    Conflict::Draw { removed: Side::Theirs, .. } => {
+                        ("kept".to_string(), "removed".to_string())
+                    },
+                    Conflict::Added { draw } => {
+                        (format!("added draw {}", draw), format!("added draw {}", draw + 1))
+                    },
+                };
+
+                write!(f, "{}conflict {}/{} draw {}{}: ours {} <o> theirs {} <t> next <n>{}{}\n\r",
+                       termion::style::Invert,
+                       nth + 1,
+                       count,
+                       conflict.get_draw(),
+                       conflict.get_cell()
+                               .map(|cell| format!(" cell {}", cell))
+                               .unwrap_or_default(),
+                       ours,
+                       theirs,
+                       termion::style::Reset,
+                       termion::clear::AfterCursor)
+            },
+        }
+    }
+
     /// The printer method `write_filename` writes the file name on
     /// the current line.
     fn write_filename(&self, f: &mut fmt::Formatter,
@@ -101,29 +394,273 @@ impl Editeur {
     }
 
     fn draw_cell(&self, f: &mut fmt::Formatter,
-                 part: &Part, current: bool) -> fmt::Result {
+                 part: &Part, current: bool, selected: bool) -> fmt::Result {
         write!(f, "{}",
-            if current {
-                format!("{}{}{}",
-                        termion::style::Bold,
-                        part,
-                        termion::style::Reset)
-            } else {
-                format!("{}", part)
+            match (current, selected) {
+                (true, _) => format!("{}{}{}",
+                                     termion::style::Bold,
+                                     part,
+                                     termion::style::Reset),
+                (false, true) => format!("{}{}{}",
+                                         termion::style::Invert,
+                                         part,
+                                         termion::style::Reset),
+                (false, false) => format!("{}", part),
             })
     }
 
+    /// The accessor method `get_current_draw` returns the current draw
+    /// of the current sprite.
+    fn get_current_draw(&self) -> Option<&Draw> {
+        self.graphic
+            .get_current_sprite()
+            .and_then(|&(_, ref sprite)| sprite.get_current_draw())
+    }
+
+    /// The accessor method `get_selected` returns the selected cells
+    /// of the current draw.
+    fn get_selected(&self) -> Vec<usize> {
+        self.get_current_draw()
+            .map(|draw| self.selection.get_cells(draw))
+            .unwrap_or_default()
+    }
+
+    /// The mutator method `select_rectangle` starts a rectangular selection
+    /// from the cursor or stops the rectangular selection.
+    fn select_rectangle(&mut self) -> Option<()> {
+        self.selection = match (self.selection, self.get_current_draw()) {
+            (Selection::Rectangle(_), _) | (_, None) => Selection::None,
+            (_, Some(draw)) => Selection::Rectangle(draw.get_position()),
+        };
+        Some(())
+    }
+
+    /// The mutator method `select_part` selects every cell of the part
+    /// under the cursor or stops the part selection.
+    fn select_part(&mut self) -> Option<()> {
+        self.selection = match (self.selection,
+                                self.get_current_draw()
+                                    .and_then(|draw| draw.get_current_part())
+                                    .cloned()) {
+            (Selection::Part(_), _) | (_, None) => Selection::None,
+            (_, Some(part)) => Selection::Part(part),
+        };
+        Some(())
+    }
+
+    /// The mutator method `set_missing` reports the cells of the current
+    /// draw without texel in the status.
+    fn set_missing(&mut self, missing: Vec<usize>) {
+        if missing.is_empty().not() {
+            self.status = Some(format!(
+                "{} cell(s) without texel: {}",
+                missing.len(),
+                missing.iter()
+                       .map(|cell| cell.to_string())
+                       .collect::<Vec<String>>()
+                       .join(", ")));
+        }
+    }
+
+    /// The mutator method `fill` sets the emotion of the number for
+    /// the part under the cursor on every selected cell, and reports
+    /// the cells without texel in the status.
+    fn fill(&mut self, index: usize) -> Option<()> {
+        let cells: Vec<usize> = self.get_selected();
+
+        self.graphic
+            .get_current_cell_number(index)
+            .and_then(|(emotion, _)| self.graphic.fill_current_draw(&cells, &emotion))
+            .map(|missing| self.set_missing(missing))
+            .or(Some(()))
+    }
+
+    /// The mutator method `set_part_emotion_all` sets the emotion of the
+    /// number for the part under the cursor on every draw, or from the
+    /// current draw, and reports the cells without texel in the status.
+    fn set_part_emotion_all(&mut self, from_current: bool, index: usize) -> Option<()> {
+        let range: Option<Range<usize>> =
+            self.graphic.get_current_sprite()
+                        .and_then(|&(_, ref sprite)|
+                                  if from_current {
+                                      Some(sprite.get_position()..SPEC_MAX_DRAW)
+                                  } else {
+                                      None
+                                  });
+        let part: Option<Part> = self.get_current_draw()
+                                     .and_then(|draw| draw.get_current_part())
+                                     .cloned();
+
+        match (part, self.graphic.get_current_cell_number(index)) {
+            (Some(part), Some((emotion, _))) => {
+                self.graphic.set_part_emotion_all(&part, &emotion, range)
+                    .map(|missing|
+                         if missing.is_empty().not() {
+                             self.status = Some(format!(
+                                 "{} cell(s) without texel {:?}:{:?}: {}",
+                                 missing.len(), part, emotion,
+                                 missing.iter()
+                                        .map(|missing| missing.to_string())
+                                        .collect::<Vec<String>>()
+                                        .join(", ")));
+                         })
+            },
+            _ => Some(()),
+        }
+    }
+
+    /// The accessor method `get_expression_list` returns the numbered
+    /// expressions of the library.
+    fn get_expression_list(&self) -> String {
+        self.graphic.get_expression_list()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, expression)| format!("{} {}", index, expression))
+                    .collect::<Vec<String>>()
+                    .join(" | ")
+    }
+
+    /// The accessor method `get_layer_list` returns the numbered
+    /// layers with the hidden ones.
+    fn get_layer_list(&self) -> String {
+        Layer::list().iter()
+                     .enumerate()
+                     .map(|(index, layer)|
+                          if self.visible.contains(layer) {
+                              format!("{} {}", index, layer)
+                          } else {
+                              format!("{} {} (hidden)", index, layer)
+                          })
+                     .collect::<Vec<String>>()
+                     .join(" | ")
+    }
+
+    /// The mutator method `toggle_layer` shows or hides the layer
+    /// of the number.
+    fn toggle_layer(&mut self, index: usize) -> Option<()> {
+        if let Some(layer) = Layer::list().get(index) {
+            match self.visible.iter().position(|visible| visible.eq(layer)) {
+                Some(position) => { self.visible.remove(position); },
+                None => self.visible.push(*layer),
+            }
+        }
+        Some(())
+    }
+
+    /// The mutator method `next_layer` activates the layer above
+    /// on the current draw, or the body after the highest.
+    fn next_layer(&mut self) -> Option<()> {
+        let layer: Option<Layer> = self.graphic
+                                       .get_current_sprite()
+                                       .and_then(|&(_, ref sprite)| sprite.get_current_draw())
+                                       .map(|draw| draw.get_layer().get_next());
+
+        layer.and_then(|layer| self.graphic.set_layer_current_draw(layer))
+             .or(Some(()))
+    }
+
+    /// The mutator method `apply_expression` applies the expression
+    /// of the number on the current draw or on every draw.
+    fn apply_expression(&mut self, all: bool, index: usize) -> Option<()> {
+        let name: Option<String> = self.graphic.get_expression_list()
+                                               .get(index)
+                                               .map(|expression|
+                                                    expression.get_name().to_string());
+        let current: Option<(Sheet, usize)> = self.graphic
+                                                  .get_current_sprite()
+                                                  .map(|&(sheet, ref sprite)|
+                                                       (sheet, sprite.get_position()));
+
+        match (name, current) {
+            (Some(name), Some((sheet, position))) => {
+                self.graphic.apply_expression(&sheet, &name,
+                                              if all {
+                                                  None
+                                              } else {
+                                                  Some(position..position + 1)
+                                              })
+                            .and(Some(()))
+            },
+            _ => Some(()),
+        }
+    }
+
+    /// The mutator method `define_expression` replaces the expression
+    /// of the number, or adds an expression, with the emotion of each
+    /// part of the current draw and writes the expression file.
+    fn define_expression(&mut self, index: usize) -> Option<()> {
+        let name: String = self.graphic.get_expression_list()
+                                       .get(index)
+                                       .map(|expression| expression.get_name().to_string())
+                                       .unwrap_or_else(|| format!("Expression{}", index));
+
+        match self.get_current_draw()
+                  .map(|draw| Expression::from_draw(&name, draw)) {
+            None => Some(()),
+            Some(Err(why)) => {
+                self.status = Some(format!("can't define {}: {}", name, report(&why)));
+                Some(())
+            },
+            Some(Ok(expression)) => {
+                self.status = Some(format!("{}", expression));
+                self.graphic.define_expression(expression);
+                match self.graphic.write_expressionfile() {
+                    Err(why) => {
+                        self.status = Some(format!("can't write: {}", report(&why)));
+                        Some(())
+                    },
+                    Ok(()) => Some(()),
+                }
+            },
+        }
+    }
+
+    /// The mutator method `copy` keeps the selected cells of the current draw.
+    fn copy(&mut self) -> Option<()> {
+        let cells: Vec<usize> = self.get_selected();
+
+        self.region = self.get_current_draw()
+                          .and_then(|draw| Region::new(draw, &cells))
+                          .or_else(|| self.region.take());
+        self.selection = Selection::None;
+        Some(())
+    }
+
+    /// The mutator method `paste` pastes the copied cells from the cursor
+    /// in the current draw, and reports the cells without texel in the status.
+    fn paste(&mut self) -> Option<()> {
+        let cells: Vec<(usize, (Part, Emotion))> =
+            match (self.region.as_ref(), self.get_current_draw()) {
+                (Some(region), Some(draw)) => region.get_cells(draw.get_position()),
+                _ => Vec::new(),
+            };
+
+        self.graphic.paste_current_draw(&cells)
+                    .map(|missing| self.set_missing(missing))
+                    .or(Some(()))
+    }
+
+    /// The printer method `write_glyph` writes the glyph of a texel or its
+    /// fallback with its foreground and background colors.
+    fn write_glyph(&self, f: &mut fmt::Formatter, texel: &Texel) -> fmt::Result {
+        texel.get_painted(&self.fallback).fmt(f)
+    }
+
     /// The printer method `write_draw_line` writes the line by
-    /// glyph, part and emotion.
+    /// glyph of the visible layers, part and emotion of the active layer.
     fn write_draw_line(&self, f: &mut fmt::Formatter,
+                       glyphs: &[(Emotion, Texel)],
                        line: &[(Emotion, Texel)],
-                       is_y: bool, current_x: usize) -> fmt::Result {
-        line.iter().map(|&(_, texel): &(Emotion, Texel)|
-                        texel.get_glyph())
-            .collect::<String>().fmt(f).and(" ".fmt(f))
+                       is_y: bool, current_x: usize,
+                       start: usize, selected: &[usize]) -> fmt::Result {
+        glyphs.iter().map(|&(_, texel): &(Emotion, Texel)|
+                          self.write_glyph(f, &texel))
+            .find(|d| d.is_err())
+            .unwrap_or_else(|| " ".fmt(f))
             .and(line.iter().enumerate()
                  .map(|(x, &(_, texel)): (usize, &(Emotion, Texel))|
-                      self.draw_cell(f, texel.get_part(), is_y.bitand(&current_x.eq(&x))))
+                      self.draw_cell(f, texel.get_part(), is_y.bitand(&current_x.eq(&x)),
+                                     selected.contains(&(start + x))))
                  .find(|d| d.is_err())
                  .unwrap_or_else(|| " ".fmt(f))
                  .and(line.iter().map(|&(emotion, _): &(Emotion, Texel)|
@@ -147,9 +684,10 @@ impl Editeur {
     }
 
     /// The printer method `write_draw` writes the draw
-    /// line by line.
+    /// line by line with its active layer.
     fn write_draw(&self, f: &mut fmt::Formatter,
-                  draw: &Draw) -> fmt::Result {
+                  draw: &Draw, selected: &[usize]) -> fmt::Result {
+        let glyphs: [(Emotion, Texel); SPEC_MAX_XY] = draw.composite(&self.visible);
         let current_position: usize = draw.get_position();
         let (current_x, current_y): (usize, usize) = (
             current_position.rem(SPEC_MAX_X),
@@ -157,13 +695,17 @@ impl Editeur {
         );
 
         draw.get_posture().fmt(f)
+            .and(" ".fmt(f))
+            .and(draw.get_layer().fmt(f))
             .and("\n\r".fmt(f))
             .and(draw.into_iter()
                  .as_slice()
                  .chunks(SPEC_MAX_X)
+                 .zip(glyphs.chunks(SPEC_MAX_X))
                  .enumerate()
-                 .map(|(y, line): (usize, &[(Emotion, Texel)])|
-                      self.write_draw_line(f, line, current_y.eq(&y), current_x))
+                 .map(|(y, (line, glyphs)): (usize, (&[(Emotion, Texel)], &[(Emotion, Texel)]))|
+                      self.write_draw_line(f, glyphs, line, current_y.eq(&y), current_x,
+                                           y * SPEC_MAX_X, selected))
                  .find(|f| f.is_err())
                  .unwrap_or_else(|| Ok(()))
                  .and("\n\r".fmt(f)))
@@ -206,10 +748,13 @@ impl Editeur {
 
     /// The printer method `write_sprite` writes all the draw and the command.
     fn write_sprite(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let selected: Vec<usize> = self.get_selected();
+
         self.graphic
             .get_current_sprite()
             .and_then(|&(ref path, ref sprite): &(Sheet, Sprite)|
                 Some(self.write_filename(f, path)
+                    .and(self.write_conflict(f))
                     .and(sprite.into_iter()
                         .enumerate()
                         .map(|(index, draw)|
@@ -217,7 +762,12 @@ impl Editeur {
                              .and(" - ".fmt(f))
                              .and(draw.get_duration().fmt(f))
                              .and(": ".fmt(f))
-                             .and(self.write_draw(f, &draw))
+                             .and(self.write_draw(f, &draw,
+                                                  if index.eq(&sprite.get_position()) {
+                                                      &selected
+                                                  } else {
+                                                      &[]
+                                                  }))
                         )
                         .find(|f| f.is_err())
                         .unwrap_or_else(|| self.write_sprite_command(f, sprite)))))
@@ -237,9 +787,12 @@ impl io::Write for Editeur {
 
 impl Display for Editeur {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}{}\n\r",
+        write!(f, "{}{}{}\n\r{}{}\n\r",
                termion::cursor::Goto(1, 1),
-               self.menu)
+               self.menu,
+               termion::clear::AfterCursor,
+               self.status.as_ref().map_or("", |status| status.as_str()),
+               termion::clear::UntilNewline)
             .and(self.write_sprite(f))
     }
 }
@@ -254,66 +807,147 @@ impl Iterator for Editeur {
     type Item = ();
 
     fn next(&mut self) -> Option<()> {
-        self.input
-            .next()
-            .and_then(|event| event.ok())
+        let mut event: Option<io::Result<Event>> = self.input.next();
+
+        while event.is_none() {
+            match self.graphic.reload(&mut self.watcher) {
+                (false, _) => {},
+                (true, ref errors) if errors.is_empty() => return Some(()),
+                (true, errors) => {
+                    self.status = Some(format!("can't reload: {}",
+                                               errors.iter()
+                                                     .map(|why| report(why))
+                                                     .collect::<Vec<String>>()
+                                                     .join("; ")));
+                    return Some(());
+                },
+            }
+            thread::sleep(Duration::from_millis(SPEC_INPUT_DELAY));
+            event = self.input.next();
+        }
+        event.map(|event| event.unwrap_or_else(|_| Event::Unsupported(Vec::new())))
             .and_then(|event| match event {
                 Event::Key(Key::Ctrl('q')) |
                 Event::Key(Key::Char('q')) |
                 Event::Mouse(MouseEvent::Release(0...8, 1)) => None,
                 event => {
-                    match event {
-                        #[cfg(feature = "clipboard")]
-                        Event::Key(Key::Ctrl('c')) |
-                        Event::Key(Key::Char('c')) |
-                        Event::Mouse(MouseEvent::Release(10...18, 1)) => {
-                           self.kopimism_command()
-                        },
-                        Event::Key(Key::Char('g')) |
-                        Event::Key(Key::Home) => {
-                            Some(self.graphic.start_position(0))
-                        },
-                        Event::Key(Key::Char('G')) |
-                        Event::Key(Key::End) => {
-                            Some(self.graphic.end_position(0))
-                        },
-                        Event::Key(Key::Char('H')) |
-                        Event::Key(Key::PageUp) => {
-                            Some(self.graphic.sub_position(1))
-                        },
-                        Event::Key(Key::Char('L')) |
-                        Event::Key(Key::PageDown) => {
-                            Some(self.graphic.add_position(1))
-                        },
-                        Event::Key(Key::Char('{')) |
-                        Event::Key(Key::Char('[')) => {
-                            Some(self.graphic.sub_position_sprite(1))
-                        },
-                        Event::Key(Key::Char('}')) |
-                        Event::Key(Key::Char(']')) => {
-                            Some(self.graphic.add_position_sprite(1))
-                        },
-                        Event::Key(Key::Char('h')) |
-                        Event::Key(Key::Left) => {
-                            Some(self.graphic.sub_position_sprite_draw(1))
+                    self.status = None;
+                    match (self.prefix.take(), event) {
+                        (Some(prefix @ 'a'), Event::Key(Key::Char(nbr @ '0'...'9'))) |
+                        (Some(prefix @ 'A'), Event::Key(Key::Char(nbr @ '0'...'9'))) => {
+                            self.set_part_emotion_all(prefix.eq(&'A'),
+                                                      nbr as usize - '0' as usize)
                         },
-                        Event::Key(Key::Char('k')) |
-                        Event::Key(Key::Up) => {
-                            Some(self.graphic.sub_position_sprite_draw(SPEC_MAX_X))
+                        (Some(prefix @ 'e'), Event::Key(Key::Char(nbr @ '0'...'9'))) |
+                        (Some(prefix @ 'E'), Event::Key(Key::Char(nbr @ '0'...'9'))) => {
+                            self.apply_expression(prefix.eq(&'E'),
+                                                  nbr as usize - '0' as usize)
                         },
-                        Event::Key(Key::Char('j')) |
-                        Event::Key(Key::Down) => {
-                            Some(self.graphic.add_position_sprite_draw(SPEC_MAX_X))
+                        (Some('x'), Event::Key(Key::Char(nbr @ '0'...'9'))) => {
+                            self.define_expression(nbr as usize - '0' as usize)
                         },
-                        Event::Key(Key::Char('l')) |
-                        Event::Key(Key::Right) => {
-                            Some(self.graphic.add_position_sprite_draw(1))
+                        (Some('z'), Event::Key(Key::Char(nbr @ '0'...'9'))) => {
+                            self.toggle_layer(nbr as usize - '0' as usize)
                         },
-                        Event::Key(Key::Char(nbr @ '0'...'9')) => {
-                            Some(self.graphic.set_current_emotion(
-                                    nbr as usize - '0' as usize))
+                        (_, event) => match event {
+                            #[cfg(feature = "clipboard")]
+                            Event::Key(Key::Ctrl('c')) |
+                            Event::Key(Key::Char('c')) |
+                            Event::Mouse(MouseEvent::Release(10...18, 1)) => {
+                               self.kopimism_command()
+                            },
+                            Event::Key(Key::Char('g')) |
+                            Event::Key(Key::Home) => {
+                                Some(self.graphic.start_position(0))
+                            },
+                            Event::Key(Key::Char('G')) |
+                            Event::Key(Key::End) => {
+                                Some(self.graphic.end_position(0))
+                            },
+                            Event::Key(Key::Char('H')) |
+                            Event::Key(Key::PageUp) => {
+                                Some(self.graphic.sub_position(1))
+                            },
+                            Event::Key(Key::Char('L')) |
+                            Event::Key(Key::PageDown) => {
+                                Some(self.graphic.add_position(1))
+                            },
+                            Event::Key(Key::Char('{')) |
+                            Event::Key(Key::Char('[')) => {
+                                Some(self.graphic.sub_position_sprite(1))
+                            },
+                            Event::Key(Key::Char('}')) |
+                            Event::Key(Key::Char(']')) => {
+                                Some(self.graphic.add_position_sprite(1))
+                            },
+                            Event::Key(Key::Char('h')) |
+                            Event::Key(Key::Left) => {
+                                Some(self.graphic.sub_position_sprite_draw(1))
+                            },
+                            Event::Key(Key::Char('k')) |
+                            Event::Key(Key::Up) => {
+                                Some(self.graphic.sub_position_sprite_draw(SPEC_MAX_X))
+                            },
+                            Event::Key(Key::Char('j')) |
+                            Event::Key(Key::Down) => {
+                                Some(self.graphic.add_position_sprite_draw(SPEC_MAX_X))
+                            },
+                            Event::Key(Key::Char('l')) |
+                            Event::Key(Key::Right) => {
+                                Some(self.graphic.add_position_sprite_draw(1))
+                            },
+                            Event::Key(Key::Char('m')) => {
+                                self.graphic.mirror_current_draw()
+                                            .map(|missing| self.set_missing(missing))
+                                            .or(Some(()))
+                            },
+                            Event::Key(Key::Char('n')) => {
+                                self.next_conflict().or(Some(()))
+                            },
+                            Event::Key(Key::Char('o')) => self.resolve_conflict(Side::Ours),
+                            Event::Key(Key::Char('t')) => self.resolve_conflict(Side::Theirs),
+                            Event::Key(Key::Char('w')) => self.save().or(Some(())),
+                            Event::Key(Key::Char(prefix @ 'a')) |
+                            Event::Key(Key::Char(prefix @ 'A')) => {
+                                self.prefix = Some(prefix);
+                                Some(())
+                            },
+                            Event::Key(Key::Char(prefix @ 'e')) |
+                            Event::Key(Key::Char(prefix @ 'E')) |
+                            Event::Key(Key::Char(prefix @ 'x')) => {
+                                self.prefix = Some(prefix);
+                                self.status = Some(self.get_expression_list());
+                                Some(())
+                            },
+                            Event::Key(Key::Char(prefix @ 'z')) => {
+                                self.prefix = Some(prefix);
+                                self.status = Some(self.get_layer_list());
+                                Some(())
+                            },
+                            Event::Key(Key::Char('\t')) => self.next_layer(),
+                            Event::Key(Key::Char('f')) => {
+                                self.fallback = self.fallback.get_next();
+                                self.status = Some(format!("Fallback {}", self.fallback));
+                                Some(())
+                            },
+                            Event::Key(Key::Char('s')) => self.select_rectangle(),
+                            Event::Key(Key::Char('S')) => self.select_part(),
+                            Event::Key(Key::Esc) => {
+                                self.selection = Selection::None;
+                                Some(())
+                            },
+                            Event::Key(Key::Char('y')) => self.copy(),
+                            Event::Key(Key::Char('p')) => self.paste(),
+                            Event::Key(Key::Char(nbr @ '0'...'9')) => {
+                                if self.selection.eq(&Selection::None) {
+                                    Some(self.graphic.set_current_emotion(
+                                            nbr as usize - '0' as usize))
+                                } else {
+                                    self.fill(nbr as usize - '0' as usize).or(Some(()))
+                                }
+                            },
+                            _ => Some(()),
                         },
-                        _ => Some(()),
                     }
                 }
             })
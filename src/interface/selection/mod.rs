@@ -0,0 +1,112 @@
+use std::ops::{Div, Rem};
+
+use editeur::emotion::Emotion;
+use editeur::sprite::draw::{Draw, SPEC_MAX_X, SPEC_MAX_Y};
+use editeur::sprite::texel::Texel;
+use editeur::sprite::texel::part::Part;
+
+/// The enum `Selection` defines the visual selection of the current draw.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Selection {
+    /// Nothing is selected.
+    None,
+    /// The rectangle from the anchor cell to the cursor.
+    Rectangle(usize),
+    /// Every cell of the part.
+    Part(Part),
+}
+
+impl Selection {
+    /// The method `get_cells` returns the selected cells of the draw.
+    pub fn get_cells(&self, draw: &Draw) -> Vec<usize> {
+        match *self {
+            Selection::None => Vec::new(),
+            Selection::Rectangle(anchor) => {
+                let position: usize = draw.get_position();
+                let (left, right): (usize, usize) = (
+                    anchor.rem(SPEC_MAX_X).min(position.rem(SPEC_MAX_X)),
+                    anchor.rem(SPEC_MAX_X).max(position.rem(SPEC_MAX_X)),
+                );
+                let (top, bottom): (usize, usize) = (
+                    anchor.div(SPEC_MAX_X).min(position.div(SPEC_MAX_X)),
+                    anchor.div(SPEC_MAX_X).max(position.div(SPEC_MAX_X)),
+                );
+
+                (top..bottom + 1).flat_map(|y|
+                                     (left..right + 1).map(move |x| y * SPEC_MAX_X + x))
+                                 .collect::<Vec<usize>>()
+            },
+            Selection::Part(part) => {
+                draw.into_iter()
+                    .enumerate()
+                    .filter(|&(_, &(_, ref texel))| texel.get_part().eq(&part))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<usize>>()
+            },
+        }
+    }
+}
+
+impl Default for Selection {
+    fn default() -> Selection {
+        Selection::None
+    }
+}
+
+/// The struct `Region` is a copied rectangle of cells, the cells
+/// out of the selection are holes which aren't pasted.
+#[derive(Clone, Debug, Default)]
+pub struct Region {
+    width: usize,
+    cells: Vec<Option<(Part, Emotion)>>,
+}
+
+impl Region {
+    /// The constructor `new` returns the region which bounds
+    /// the selected cells of the draw.
+    pub fn new(draw: &Draw, cells: &[usize]) -> Option<Self> {
+        let board: &[(Emotion, Texel)] = draw.into_iter().as_slice();
+
+        match (cells.iter().map(|index| index.rem(SPEC_MAX_X)).min(),
+               cells.iter().map(|index| index.rem(SPEC_MAX_X)).max(),
+               cells.iter().map(|index| index.div(SPEC_MAX_X)).min(),
+               cells.iter().map(|index| index.div(SPEC_MAX_X)).max()) {
+            (Some(left), Some(right), Some(top), Some(bottom)) => Some(Region {
+                width: right - left + 1,
+                cells: (top..bottom + 1).flat_map(|y|
+                                            (left..right + 1).map(move |x| y * SPEC_MAX_X + x))
+                                        .map(|index|
+                                             if cells.contains(&index) {
+                                                 board.get(index)
+                                                      .map(|&(emotion, ref texel)|
+                                                           (*texel.get_part(), emotion))
+                                             } else {
+                                                 None
+                                             })
+                                        .collect::<Vec<Option<(Part, Emotion)>>>(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The method `get_cells` returns the cells of the region pasted
+    /// from the top left cell, the cells out of the draw are cut.
+    pub fn get_cells(&self, position: usize) -> Vec<(usize, (Part, Emotion))> {
+        let (left, top): (usize, usize) = (position.rem(SPEC_MAX_X), position.div(SPEC_MAX_X));
+
+        self.cells.iter()
+                  .enumerate()
+                  .filter_map(|(index, cell)|
+                      cell.and_then(|cell| {
+                          let (x, y): (usize, usize) = (left + index.rem(self.width),
+                                                        top + index.div(self.width));
+
+                          if x < SPEC_MAX_X && y < SPEC_MAX_Y {
+                              Some((y * SPEC_MAX_X + x, cell))
+                          } else {
+                              None
+                          }
+                      }))
+                  .collect::<Vec<(usize, (Part, Emotion))>>()
+    }
+}
@@ -25,31 +25,56 @@ pub mod tuple;
 pub mod sheet;
 pub mod sprite;
 pub mod emotion;
+pub mod expression;
 pub mod util;
+pub mod grammar;
 mod err;
 pub mod cursor;
+pub mod builder;
+pub mod watcher;
+pub mod lint;
+pub mod coverage;
+pub mod glyph;
+pub mod transition;
+pub mod template;
+pub mod effect;
+pub mod player;
+pub mod machine;
 
 pub mod prelude;
 
 pub use self::cursor::Cursor;
 pub use self::emotion::Emotion;
-use self::sprite::Sprite;
+use self::emotion::Intensity;
+use self::emotion::intensity::SPEC_LEVEL_DEFAULT;
+use self::grammar::{GrammarError, Word, SPEC_DRAW_WORD, SPEC_LAYER_WORD};
+use self::expression::Expression;
+use self::transition::{Kind, Transition};
+use self::template::{Template, TemplateError};
+use self::effect::{Effect, EffectError};
+use self::machine::Machine;
+use self::sprite::{Missing, Sprite};
+use self::sprite::draw::DrawError;
+use self::sprite::merge::{self, Conflict, Side};
 use self::sheet::Sheet;
 
-pub use self::err::{GraphicError, Result};
+pub use self::err::{GraphicError, Result, report};
+pub use self::builder::GraphicBuilder;
+pub use self::watcher::Watcher;
 
 pub use self::tuple::Tuple;
-pub use self::sprite::draw::{Draw, SPEC_MAX_XY};
+pub use self::sprite::draw::{Draw, Layer, SPEC_MAX_XY};
 pub use self::sprite::texel::Texel;
+use self::sprite::texel::Attributes;
+pub use self::sprite::texel::fallback::Fallback;
 pub use self::sprite::texel::part::Part;
 pub use self::sprite::SPEC_MAX_DRAW;
 
-use std::collections::HashMap;
-use std::env;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::io;
 use std::io::prelude::*;
-use std::ops::Not;
+use std::ops::{Not, Range};
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 
@@ -62,6 +87,30 @@ pub const SPEC_CAPACITY_SPRITE: usize = 5;
 pub const SPEC_SUBD_NCT: &'static str = "texels";
 /// The sub-directory sprite.
 pub const SPEC_SUBD_NCS: &'static str = "sprites";
+/// The extension of texel file.
+pub const SPEC_EXT_NCT: &'static str = "cnt";
+/// The extension of sprite file.
+pub const SPEC_EXT_NCS: &'static str = "cns";
+/// The file of expression in the root.
+pub const SPEC_FILE_NCE: &'static str = "expressions";
+/// The extension of expression file.
+pub const SPEC_EXT_NCE: &'static str = "cne";
+/// The file of transition in the root.
+pub const SPEC_FILE_NCR: &'static str = "transitions";
+/// The extension of transition file.
+pub const SPEC_EXT_NCR: &'static str = "cnr";
+/// The file of state machine in the root.
+pub const SPEC_FILE_NCM: &'static str = "machine";
+/// The extension of state machine file.
+pub const SPEC_EXT_NCM: &'static str = "cnm";
+/// The file of sprite template in the root.
+pub const SPEC_FILE_NCP: &'static str = "templates";
+/// The extension of sprite template file.
+pub const SPEC_EXT_NCP: &'static str = "cnp";
+/// The file of effect track in the root.
+pub const SPEC_FILE_NCO: &'static str = "effects";
+/// The extension of effect track file.
+pub const SPEC_EXT_NCO: &'static str = "cno";
 /// The sub-directory font.
 pub const SPEC_SUBD_NCF: &'static str = "fonts";
 /// The first directory.
@@ -72,8 +121,24 @@ pub const SPEC_ROOT_DEFAULT: &'static str = "assets";
 pub struct Graphic {
     /// Dictionary of texel.
     texel: HashMap<Sheet, HashMap<Tuple, Vec<Texel>>>,
+    /// Parent of the postures which inherit its texels.
+    parent: HashMap<Sheet, Sheet>,
+    /// Dictionary of texel by level of intensity.
+    level: HashMap<Sheet, HashMap<Tuple, BTreeMap<u8, Vec<Texel>>>>,
+    /// Library of named expression.
+    expression: Vec<Expression>,
+    /// Table of transition between postures.
+    transition: Vec<Transition>,
+    /// Library of sprite template.
+    template: Vec<Template>,
+    /// Library of effect track.
+    effect: Vec<Effect>,
     /// Dictionary of primitive's sprite.
     sprite: io::Cursor<Vec<(Sheet, Sprite)>>,
+    /// The root with the texel and sprite sub-directories.
+    root: Option<PathBuf>,
+    /// Creates the missing sub-directories of the root.
+    mkdir: bool,
 }
 
 impl Graphic {
@@ -100,78 +165,229 @@ impl Graphic {
                 Some(&*sprite)})
     }
 
-    /// The constructor `new` returns a Graphic prepared with
-    /// the texel and sprite root.
-    pub fn new() -> Result<Self> {
-        let mut manager = Graphic::default();
-
-        manager.nct_with_ncs()
-            .and_then(|(texel, sprite)|
-                match (fs::read_dir(texel), fs::read_dir(sprite)) {
-                    (Err(why), _) | (_, Err(why)) => Err(GraphicError::ReadDir(why)),
-                    (Ok(entry_nct), Ok(entry_ncs)) => {
-                        if let Some(why) = entry_nct.filter_map(|texel| texel.ok())
-                                                    .filter_map(|entry|
-                            manager.insert_from_texelfile(&entry.path()).err()
-                        ).next() {
-                                Err(why)
-                        } else if let Some(why) = entry_ncs.filter_map(|sprite|
-                                                               sprite.ok())
-                                                           .filter_map(|entry|
-                            manager.insert_from_spritefile(&entry.path()).err()
-                        ).next() {
-                            Err(why)
-                        } else {
-                            Ok(manager)
-                        }
+    /// The accessor method `get_expression_list` returns the library
+    /// of expression.
+    pub fn get_expression_list(&self) -> &Vec<Expression> {
+        &self.expression
+    }
+
+    /// The accessor method `get_expression` returns the expression
+    /// of the name.
+    pub fn get_expression(&self, name: &str) -> Option<&Expression> {
+        self.expression.iter()
+                       .find(|expression| expression.get_name().eq(name))
+    }
+
+    /// The mutator method `define_expression` adds the expression or
+    /// replaces the expression of the same name.
+    pub fn define_expression(&mut self, expression: Expression) {
+        define(&mut self.expression, expression, |other, expression|
+               other.get_name().eq(expression.get_name()))
+    }
+
+    /// The mutator method `remove_expression` removes and returns
+    /// the expression of the name.
+    pub fn remove_expression(&mut self, name: &str) -> Option<Expression> {
+        self.expression.iter()
+                       .position(|expression| expression.get_name().eq(name))
+                       .map(|index| self.expression.remove(index))
+    }
+
+    /// The method `apply_expression` returns the sprite modified by the
+    /// expression of the name on the range of draws or on every draw.
+    pub fn apply_expression(&mut self,
+        sheet: &Sheet,
+        name: &str,
+        range: Option<Range<usize>>,
+    ) -> Option<&Sprite> {
+        match self.get_expression(name)
+                  .map(|expression| expression.get_change(range.unwrap_or(0..SPEC_MAX_DRAW))) {
+            None => None,
+            Some(change) => self.explicite_emotion(sheet, &change),
+        }
+    }
+
+    /// The accessor method `get_transition_list` returns the table
+    /// of transition.
+    pub fn get_transition_list(&self) -> &Vec<Transition> {
+        &self.transition
+    }
+
+    /// The accessor method `get_transition` returns the transition
+    /// from the posture to the target posture.
+    pub fn get_transition(&self, current: &Sheet, target: &Sheet) -> Option<&Transition> {
+        self.transition.iter()
+                       .find(|transition| transition.is_between(current, target))
+    }
+
+    /// The mutator method `define_transition` adds the transition or
+    /// replaces the transition between the same postures.
+    pub fn define_transition(&mut self, transition: Transition) {
+        define(&mut self.transition, transition, |other, transition|
+               other.is_between(transition.get_from(), transition.get_to()))
+    }
+
+    /// The method `get_transition_draws` returns the draws to play from
+    /// the sprite of the posture to the sprite of the target posture,
+    /// nothing without transition or None if a sprite is missing.
+    pub fn get_transition_draws(&self, current: &Sheet, target: &Sheet) -> Option<Vec<Draw>> {
+        match self.get_transition(current, target).map(|transition| *transition.get_kind()) {
+            None => Some(Vec::new()),
+            Some(Kind::Sprite(sheet)) => {
+                self.get_sprite(&sheet)
+                    .map(|sprite| sprite.into_iter().cloned().collect::<Vec<Draw>>())
+            },
+            Some(Kind::Crossfade { step, duration }) => {
+                match (self.get_sprite(current).and_then(|sprite| sprite.into_iter().last()),
+                       self.get_sprite(target).and_then(|sprite| sprite.into_iter().next())) {
+                    (Some(from), Some(to)) => {
+                        Some(transition::crossfade(from, to, step, duration))
                     },
+                    _ => None,
                 }
-            )
+            },
+        }
     }
 
-    /// The accessor method `get_nct` returns the texel sub-directory.
-    pub fn get_nct(&self) -> Result<PathBuf> {
-        let path: PathBuf =
-            env::var(SPEC_ROOT).ok()
-                .and_then(|repertory: String|
-                          Some(PathBuf::from(repertory)))
-                .unwrap_or_else(||
-                          PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-                                            .join(SPEC_ROOT_DEFAULT))
-                .join(SPEC_SUBD_NCT);
-
-        match fs::create_dir_all(&path) {
-            Ok(_) => Ok(path),
-            Err(why) => {
-                if why.kind().eq(&io::ErrorKind::AlreadyExists) {
-                    Ok(path)
-                } else {
-                    Err(GraphicError::MkDirTexel(why))
-                }
+    /// The accessor method `get_template_list` returns the library
+    /// of sprite template.
+    pub fn get_template_list(&self) -> &Vec<Template> {
+        &self.template
+    }
+
+    /// The accessor method `get_template` returns the template of the name.
+    pub fn get_template(&self, name: &str) -> Option<&Template> {
+        self.template.iter()
+                     .find(|template| template.get_name().eq(name))
+    }
+
+    /// The mutator method `define_template` adds a template or replaces
+    /// the template of the same name.
+    pub fn define_template(&mut self, template: Template) {
+        define(&mut self.template, template, |other, template|
+               other.get_name().eq(template.get_name()))
+    }
+
+    /// The method `expand_template` returns the sprite of the Sheet key
+    /// with the frames of the template expanded from each of its draws,
+    /// or from its base draws when it's already expanded.
+    pub fn expand_template(&self, sheet: &Sheet, name: &str) -> Result<Sprite> {
+        match self.get_sprite(sheet) {
+            None => Err(GraphicError::FoundSprite(sheet.get_name().to_string())),
+            Some(sprite) => {
+                self.sprite_with_template(sprite.get_base().unwrap_or_else(|| sprite.clone()),
+                                          &[name])
             },
         }
     }
 
+    /// The function `is_edited` returns true when the frames of an
+    /// expanded sprite aren't the expansion of its base draws.
+    fn is_edited(&self, sprite: &Sprite) -> bool {
+        let frames = |sprite: &Sprite| sprite.into_iter()
+                                             .map(|draw| draw.to_string())
+                                             .collect::<Vec<String>>();
+
+        sprite.get_base().map_or(false, |base| {
+            let names: Vec<&str> = sprite.get_template().iter()
+                                                        .map(|name| name.as_str())
+                                                        .collect::<Vec<&str>>();
+
+            self.sprite_with_template(base, &names)
+                .map(|expanded| frames(&expanded).ne(&frames(sprite)))
+                .unwrap_or(true)
+        })
+    }
+
+    /// The function `sprite_with_template` returns the sprite with the
+    /// templates of the names expanded in order.
+    fn sprite_with_template(&self, sprite: Sprite, names: &[&str]) -> Result<Sprite> {
+        let texels: HashMap<Sheet, HashMap<Tuple, Vec<Texel>>> = self.texel_resolved();
+
+        names.iter()
+             .fold(Ok(sprite), |sprite: Result<Sprite>, name|
+                   sprite.and_then(|sprite|
+                       match self.get_template(name) {
+                           None => Err(TemplateError::UnknownTemplate(name.to_string())),
+                           Some(template) => template.expand_sprite(&sprite, &texels),
+                       }.map_err(GraphicError::Template)))
+    }
+
+    /// The accessor method `get_effect_list` returns the library
+    /// of effect track.
+    pub fn get_effect_list(&self) -> &Vec<Effect> {
+        &self.effect
+    }
+
+    /// The accessor method `get_effect` returns the effect of the name.
+    pub fn get_effect(&self, name: &str) -> Option<&Effect> {
+        self.effect.iter()
+                   .find(|effect| effect.get_name().eq(name))
+    }
+
+    /// The mutator method `define_effect` adds an effect or replaces
+    /// the effect of the same name.
+    pub fn define_effect(&mut self, effect: Effect) {
+        define(&mut self.effect, effect, |other, effect| other.get_name().eq(effect.get_name()))
+    }
+
+    /// The method `compose_effects` returns the draw with the frames of
+    /// the effects of the names played after their elapsed milliseconds,
+    /// with the texels of its posture.
+    pub fn compose_effects(&self, draw: &Draw, effects: &[(&str, i64)]) -> Result<Draw> {
+        effects.iter()
+               .map(|&(name, elapsed)|
+                    self.get_effect(name)
+                        .map(|effect| (effect, elapsed))
+                        .ok_or_else(|| GraphicError::Effect(EffectError::UnknownEffect(name.to_string()))))
+               .collect::<Result<Vec<(&Effect, i64)>>>()
+               .map(|effects|
+                    effect::compose_all(draw, &effects,
+                                        &self.get_texel_resolved(draw.get_posture())
+                                             .unwrap_or_default()))
+    }
+
+    /// The constructor `new` returns a Graphic prepared with
+    /// the texel and sprite root of the `NEKO_PATH` environment variable.
+    pub fn new() -> Result<Self> {
+        GraphicBuilder::new()
+            .root(GraphicBuilder::get_env_root())
+            .create_dir(true)
+            .build()
+    }
+
+    /// The accessor method `get_root` returns the root of
+    /// the texel and sprite sub-directories.
+    pub fn get_root(&self) -> PathBuf {
+        self.root.clone()
+            .unwrap_or_else(|| GraphicBuilder::get_env_root())
+    }
+
+    /// The function `subdirectory` returns a sub-directory of the root,
+    /// created if missing when the Graphic was built to create it.
+    fn subdirectory(&self, sub: &str, error: fn(io::Error) -> GraphicError
+    ) -> Result<PathBuf> {
+        let path: PathBuf = self.get_root().join(sub);
+
+        if self.mkdir {
+            match fs::create_dir_all(&path) {
+                Err(ref why) if why.kind().eq(&io::ErrorKind::AlreadyExists) => Ok(path),
+                Err(why) => Err(error(why)),
+                Ok(()) => Ok(path),
+            }
+        } else {
+            Ok(path)
+        }
+    }
+
+    /// The accessor method `get_nct` returns the texel sub-directory.
+    pub fn get_nct(&self) -> Result<PathBuf> {
+        self.subdirectory(SPEC_SUBD_NCT, GraphicError::MkDirTexel)
+    }
+
     /// The accessor method `get_ncs` returns the sprite sub-directory.
     pub fn get_ncs(&self) -> Result<PathBuf> {
-        let path: PathBuf =
-            env::var(SPEC_ROOT).ok()
-                .and_then(|repertory: String|
-                          Some(PathBuf::from(repertory)))
-                .unwrap_or_else(||
-                          PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-                                            .join(SPEC_ROOT_DEFAULT))
-                .join(SPEC_SUBD_NCS);
-        match fs::create_dir_all(&path) {
-            Ok(_) => Ok(path),
-            Err(why) => {
-                if why.kind().eq(&io::ErrorKind::AlreadyExists) {
-                    Ok(path)
-                } else {
-                    Err(GraphicError::MkDirTexel(why))
-                }
-            },
-        }
+        self.subdirectory(SPEC_SUBD_NCS, GraphicError::MkDirSprite)
     }
 
     /// The accessor method `nct_with_ncs` returns a couple
@@ -186,40 +402,164 @@ impl Graphic {
     }
 
 
+    /// The accessor method `get_parent` returns the posture
+    /// which the posture inherits from.
+    pub fn get_parent(&self, posture: &Sheet) -> Option<&Sheet> {
+        self.parent.get(posture)
+    }
+
+    /// The accessor method `get_chain` returns the posture
+    /// followed by its parents.
+    pub fn get_chain(&self, posture: &Sheet) -> Vec<Sheet> {
+        let mut chain: Vec<Sheet> = vec![*posture];
+
+        while let Some(parent) = chain.last()
+                                      .and_then(|posture| self.parent.get(posture))
+                                      .cloned() {
+            if chain.contains(&parent) {
+                break ;
+            }
+            chain.push(parent);
+        }
+        chain
+    }
+
+    /// The mutator method `set_parent` declares the parent of the posture,
+    /// a parent which already inherits from the posture is refused.
+    pub fn set_parent(&mut self, posture: Sheet, parent: Sheet) -> Result<()> {
+        if self.get_chain(&parent).contains(&posture) {
+            Err(GraphicError::CycleParent(format!("{} -> {}", posture, parent)))
+        } else {
+            self.parent.insert(posture, parent);
+            Ok(())
+        }
+    }
+
     /// The accessor method `get_emotion_list` returns a list of available emotions
-    /// for the Sheet key and Part sub-key.
+    /// for the Sheet key and Part sub-key, with the emotions inherited
+    /// from the parents.
     pub fn get_emotion_list(&self,
                             posture_key: &Sheet,
                             part_key: &Part,
     ) -> Option<Vec<&Emotion>> {
-        self.texel.get(posture_key)
-            .and_then(|part_by_emotion|
-                      Some(part_by_emotion.keys()
-                           .filter(|&&Tuple { part, .. }| part.eq(part_key))
-                           .map(|&Tuple { part: _, ref emotion }|
-                                emotion).collect::<Vec<&Emotion>>()))
+        self.get_chain(posture_key)
+            .into_iter()
+            .filter_map(|posture| self.texel.get(&posture))
+            .fold(None, |emotions: Option<Vec<&Emotion>>, part_by_emotion| {
+                let mut emotions: Vec<&Emotion> = emotions.unwrap_or_default();
+                let inherited: Vec<&Emotion> =
+                    part_by_emotion.keys()
+                                   .filter(|&&Tuple { part, .. }| part.eq(part_key))
+                                   .map(|&Tuple { part: _, ref emotion }| emotion)
+                                   .filter(|emotion| emotions.contains(emotion).not())
+                                   .collect::<Vec<&Emotion>>();
+
+                emotions.extend(inherited);
+                Some(emotions)
+            })
     }
 
     pub fn get_cell_list(&self,
                             posture_key: &Sheet,
                             part_key: &Part,
     ) -> Option<Vec<(&Emotion, &Vec<Texel>)>> {
-        self.texel.get(posture_key)
-            .and_then(|part_by_emotion|
-                      Some(part_by_emotion.iter()
-                           .filter(|&(&Tuple { part, emotion: _ }, _)| part.eq(part_key))
-                           .map(|(&Tuple { part: _, ref emotion }, texel)|
-                                (emotion, texel))
-                           .collect::<Vec<(&Emotion, &Vec<Texel>)>>()))
+        self.get_chain(posture_key)
+            .into_iter()
+            .filter_map(|posture| self.texel.get(&posture))
+            .fold(None, |cells: Option<Vec<(&Emotion, &Vec<Texel>)>>, part_by_emotion| {
+                let mut cells: Vec<(&Emotion, &Vec<Texel>)> = cells.unwrap_or_default();
+                let inherited: Vec<(&Emotion, &Vec<Texel>)> =
+                    part_by_emotion.iter()
+                                   .filter(|&(&Tuple { part, emotion: _ }, _)| part.eq(part_key))
+                                   .map(|(&Tuple { part: _, ref emotion }, texel)|
+                                        (emotion, texel))
+                                   .filter(|&(emotion, _)|
+                                        cells.iter().any(|&(cell, _)| cell.eq(emotion)).not())
+                                   .collect::<Vec<(&Emotion, &Vec<Texel>)>>();
+
+                cells.extend(inherited);
+                Some(cells)
+            })
+    }
+
+    /// The accessor method `get_texel_origin` returns the posture
+    /// of the chain which declares the texels of the pair.
+    pub fn get_texel_origin(&self, posture: &Sheet, tuple: &Tuple) -> Option<Sheet> {
+        self.get_chain(posture)
+            .into_iter()
+            .find(|posture|
+                  self.texel.get(posture)
+                            .map_or(false, |texels| texels.contains_key(tuple)))
     }
 
-    /// The accessor method `get_texel` returns a reference on texel.
+    /// The accessor method `get_texel` returns a reference on texel,
+    /// declared by the posture or inherited from its parents.
     pub fn get_texel(&self,
                  position: &Sheet,
                  tuple: &Tuple,
     ) -> Option<&Vec<Texel>> {
-        self.texel.get(position).and_then(|sprite|
-                      sprite.get(tuple).and_then(|texel| Some(texel)))
+        self.get_texel_origin(position, tuple)
+            .and_then(|origin| self.texel.get(&origin))
+            .and_then(|sprite| sprite.get(tuple))
+    }
+
+    /// The accessor method `get_texel_resolved` returns the texels
+    /// of the posture with the texels inherited from its parents.
+    pub fn get_texel_resolved(&self, posture: &Sheet) -> Option<HashMap<Tuple, Vec<Texel>>> {
+        self.get_chain(posture)
+            .into_iter()
+            .rev()
+            .filter_map(|posture| self.texel.get(&posture))
+            .fold(None, |resolved: Option<HashMap<Tuple, Vec<Texel>>>, texels| {
+                let mut resolved: HashMap<Tuple, Vec<Texel>> = resolved.unwrap_or_default();
+
+                resolved.extend(texels.iter()
+                                      .map(|(tuple, texels)| (*tuple, texels.clone())));
+                Some(resolved)
+            })
+    }
+
+    /// The function `texel_resolved` returns the texels of every
+    /// posture with the texels inherited from their parents.
+    fn texel_resolved(&self) -> HashMap<Sheet, HashMap<Tuple, Vec<Texel>>> {
+        self.texel.keys()
+                  .chain(self.parent.keys())
+                  .filter_map(|posture|
+                       self.get_texel_resolved(posture)
+                           .map(|texels| (*posture, texels)))
+                  .collect::<HashMap<Sheet, HashMap<Tuple, Vec<Texel>>>>()
+    }
+
+    /// The function `level_origin` returns the texels by level of the
+    /// pair, declared by the posture or inherited from its parents.
+    fn level_origin(&self, posture: &Sheet, tuple: &Tuple) -> Option<&BTreeMap<u8, Vec<Texel>>> {
+        self.get_chain(posture)
+            .into_iter()
+            .filter_map(|posture|
+                 self.level.get(&posture)
+                           .and_then(|texels| texels.get(tuple)))
+            .next()
+    }
+
+    /// The accessor method `get_levels` returns the declared levels
+    /// of intensity of the pair for the posture.
+    pub fn get_levels(&self, posture: &Sheet, tuple: &Tuple) -> Vec<u8> {
+        self.level_origin(posture, tuple)
+            .map(|levels| levels.keys().cloned().collect::<Vec<u8>>())
+            .unwrap_or_default()
+    }
+
+    /// The accessor method `get_texel_level` returns the level and the texels
+    /// of the pair for the posture at the intensity, or else at the nearest
+    /// declared level with the lowest first.
+    pub fn get_texel_level(&self, posture: &Sheet, part: &Part, intensity: &Intensity
+    ) -> Option<(u8, &Vec<Texel>)> {
+        self.level_origin(posture, &Tuple::from((*part, *intensity.get_emotion())))
+            .and_then(|levels|
+                 levels.iter()
+                       .min_by_key(|&(&level, _)|
+                            (level as i16 - intensity.get_level() as i16).abs())
+                       .map(|(&level, texels)| (level, texels)))
     }
 
     /// The accessor method `get_sprite` returns a reference on sprite.
@@ -229,6 +569,18 @@ impl Graphic {
             .and_then(|&(_, ref sprite)| Some(sprite))
     }
 
+    /// The accessor method `get_texel_dictionary` returns the declared
+    /// texels by posture and by pair of Part and Emotion.
+    pub fn get_texel_dictionary(&self) -> &HashMap<Sheet, HashMap<Tuple, Vec<Texel>>> {
+        &self.texel
+    }
+
+    /// The accessor method `get_sprite_list` returns the sprites
+    /// with their Sheet key.
+    pub fn get_sprite_list(&self) -> &Vec<(Sheet, Sprite)> {
+        self.sprite.get_ref()
+    }
+
     /// The function `insert_texel` insert a texel.
     fn insert_texel(&mut self,
                     (position, tuple): (Sheet, Tuple),
@@ -241,30 +593,65 @@ impl Graphic {
             .push(val);
     }
 
+    /// The function `insert_texel_level` inserts a texel by level.
+    fn insert_texel_level(&mut self,
+                          (position, tuple, level): (Sheet, Tuple, u8),
+                          val: Texel,
+    ) {
+        self.level.entry(position)
+            .or_insert_with(|| HashMap::with_capacity(SPEC_CAPACITY_SPRITE))
+            .entry(tuple)
+            .or_insert_with(BTreeMap::new)
+            .entry(level)
+            .or_insert_with(|| Vec::with_capacity(SPEC_MAX_XY))
+            .push(val);
+    }
+
     /// The function `insert_sprite` insert a sprite.
     fn insert_sprite(&mut self, sprite: (Sheet, Sprite)) {
         self.sprite.get_mut().push(sprite)
     }
 
     fn line_with_character(
-        &mut self, posture: &str, part: &str, emotion: &str, character: char
+        &mut self, posture: &str, part: &str, emotion: &str, character: char,
+        (attributes, nth): (&Attributes, usize),
     ) -> Result<()> {
         match (Sheet::new(posture),
                Texel::new(part, character),
-               Emotion::new(emotion)) {
+               Intensity::new(emotion)) {
             (Err(why), _, _) => Err(GraphicError::Sheet(why)),
             (_, Err(why), _) => Err(GraphicError::Texel(why)),
             (_, _, Err(why)) => Err(GraphicError::Emotion(why)),
-            (Ok(posture), Ok(texel), Ok(emotion)) => {
-                self.insert_texel((posture, Tuple::from((*texel.get_part(), emotion))), texel);
+            (Ok(posture), Ok(mut texel), Ok(intensity)) => {
+                attributes.apply(nth, &mut texel);
+                let tuple: Tuple = Tuple::from((*texel.get_part(), *intensity.get_emotion()));
+
+                if intensity.is_default() {
+                    if self.level.get(&posture)
+                                 .and_then(|texels| texels.get(&tuple))
+                                 .map_or(false, |levels|
+                                      levels.contains_key(&SPEC_LEVEL_DEFAULT).not()) {
+                        self.texel.get_mut(&posture).map(|texels| texels.remove(&tuple));
+                    }
+                    self.insert_texel((posture, tuple), texel);
+                }
+                self.insert_texel_level((posture, tuple, intensity.get_level()), texel);
                 Ok(())
             },
         }
     }
 
+    /// The function `texel_with_line` inserts the glyphs of a line of
+    /// texel with the colors and the fallbacks of its attributes after the postures.
     fn texel_with_line(
         &mut self, line: &str
     ) -> Result<()> {
+        let (line, attributes): (&str, &str) = line.rfind(']')
+                                                   .map_or((line, ""), |position|
+                                                        line.split_at(position + 1));
+        let attributes: Attributes =
+            try!(attributes.parse::<Attributes>().map_err(GraphicError::Texel));
+
         if let Some(position) = line.find(':') {
             let (part_for_characters, emotion_and_postures) = line.split_at(position);
             let (part_for_characters, emotion_and_postures) = (
@@ -281,15 +668,15 @@ impl Graphic {
                     postures.iter()
                         .filter_map(|posture: &&str|
                             characters.iter()
-                                 .filter_map(|character: &&str|
-                                     character.chars()
-                                         .filter_map(|glyph|
-                                              self.line_with_character(
-                                                  posture,
-                                                  part,
-                                                  emotion,
-                                                  glyph).err())
-                                         .next())
+                                 .flat_map(|character: &&str| character.chars())
+                                 .enumerate()
+                                 .filter_map(|(nth, glyph)|
+                                      self.line_with_character(
+                                          posture,
+                                          part,
+                                          emotion,
+                                          glyph,
+                                          (&attributes, nth)).err())
                                  .next().and_then(|why| Some(Err(why))))
                         .next().unwrap_or_else(|| Ok(()))
                 },
@@ -300,88 +687,628 @@ impl Graphic {
         }
     }
 
-    /// The function `from_file_texel` insert a texel from a file.
+    /// The function `inherit_with_line` declares the parent of the
+    /// children, a child ending by `*` is every posture of this prefix.
+    fn inherit_with_line(&mut self, parent: &str, children: &[&str]) -> Result<()> {
+        Sheet::new(parent).map_err(GraphicError::Sheet).and_then(|parent|
+            children.iter()
+                    .map(|child|
+                         if child.ends_with('*') {
+                             Ok(Sheet::list().iter()
+                                             .filter(|sheet|
+                                                  sheet.get_name()
+                                                       .starts_with(child.trim_right_matches('*')))
+                                             .filter(|sheet| sheet.ne(&&parent))
+                                             .cloned()
+                                             .collect::<Vec<Sheet>>())
+                         } else {
+                             Sheet::new(child).map(|child| vec![child])
+                                              .map_err(GraphicError::Sheet)
+                         })
+                    .collect::<Result<Vec<Vec<Sheet>>>>()
+                    .and_then(|children|
+                         children.concat()
+                                 .into_iter()
+                                 .map(|child| self.set_parent(child, parent))
+                                 .find(|f| f.is_err())
+                                 .unwrap_or_else(|| Ok(()))))
+    }
+
+    /// The function `texel_with_lines` inserts the significant lines
+    /// of texel.
+    fn texel_with_lines(&mut self, lines: grammar::Result<Vec<(usize, String)>>) -> Result<()> {
+        lines.map_err(GraphicError::Grammar)
+             .and_then(|lines|
+                  lines.iter()
+                       .map(|&(_, ref line)|
+                            match grammar::inherit(line) {
+                                None => self.texel_with_line(line),
+                                Some((parent, children)) => {
+                                    self.inherit_with_line(parent, &children)
+                                },
+                            })
+                       .find(|f| f.is_err())
+                       .unwrap_or_else(|| Ok(())))
+             .and_then(|()| Ok(self.texel_with_levels()))
+    }
+
+    /// The function `texel_with_levels` inserts the texels of the nearest
+    /// level, the lowest first, as the texels of a pair declared without
+    /// the default level; they're replaced when the default level comes.
+    fn texel_with_levels(&mut self) {
+        let defaults: Vec<(Sheet, Tuple, Vec<Texel>)> =
+            self.level.iter()
+                      .flat_map(|(posture, tuples)|
+                           tuples.iter()
+                                 .filter(|&(_, levels)|
+                                      levels.contains_key(&SPEC_LEVEL_DEFAULT).not())
+                                 .filter_map(move |(tuple, levels)|
+                                      levels.iter()
+                                            .min_by_key(|&(&level, _)|
+                                                 (level as i16 - SPEC_LEVEL_DEFAULT as i16).abs())
+                                            .map(|(_, texels)| (*posture, *tuple, texels.clone()))))
+                      .collect::<Vec<(Sheet, Tuple, Vec<Texel>)>>();
+
+        defaults.into_iter()
+                .all(|(posture, tuple, texels)| {
+                    self.texel.entry(posture)
+                        .or_insert_with(|| HashMap::with_capacity(SPEC_CAPACITY_SPRITE))
+                        .insert(tuple, texels);
+                    true
+                });
+    }
+
+    /// The function `insert_from_texel` inserts the texels
+    /// from a readable source, its includes are relative
+    /// to the current directory.
+    pub fn insert_from_texel<R: Read>(&mut self, source: R) -> Result<()> {
+        self.texel_with_lines(grammar::texel_lines(source, None))
+    }
+
+    /// The function `from_file_texel` insert a texel from a file,
+    /// its includes are relative to the directory of the file.
     pub fn insert_from_texelfile<S: AsRef<Path>>(&mut self, source: S) -> Result<()> {
         match fs::OpenOptions::new().read(true).open(source.as_ref()) {
-            Err(why) => Err(GraphicError::OpenFile(why)),
+            Err(why) => Err(GraphicError::OpenFile(source.as_ref().to_path_buf(), why)),
             Ok(buffer) => {
-                let reader = io::BufReader::new(buffer).lines();
-                reader.map(|line: io::Result<String>|
-                           match line {
-                               Err(why) => Err(GraphicError::ReadFile(why)),
-                               Ok(ref line) if line.is_empty() => Ok(()),
-                               Ok(line) => self.texel_with_line(&line),
-                           })
-                    .find(|f| f.is_err())
-                    .unwrap_or_else(|| Ok(()))
-            }
+                self.texel_with_lines(grammar::texel_lines(buffer, Some(source.as_ref())))
+                    .map_err(|why| why.with_path(source.as_ref()))
+            },
         }
     }
 
+    /// The function `tuples_with_pairs` returns the tuples of the pairs
+    /// of part and emotion, an error carries the line of its pair.
+    fn tuples_with_pairs(pairs: &[Word]) -> Result<Vec<Tuple>> {
+        pairs.chunks(2)
+             .map(|pair: &[Word]|
+                  match (pair.first(), pair.get(1)) {
+                      (Some(part), Some(emotion)) => {
+                          match (Part::new(part.text), Emotion::new(emotion.text)) {
+                              (Err(why), _) => Err(GraphicError::Line(
+                                  part.line + 1, Box::new(GraphicError::Part(why))
+                              )),
+                              (_, Err(why)) => Err(GraphicError::Line(
+                                  emotion.line + 1, Box::new(GraphicError::Emotion(why))
+                              )),
+                              (Ok(part), Ok(emotion)) => Ok(Tuple::from((part, emotion))),
+                          }
+                      },
+                      (part, _) => Err(GraphicError::Grammar(
+                          GrammarError::SyntaxDraw(part.map_or(0, |part| part.line + 1))
+                      )),
+                  })
+             .collect::<Result<Vec<Tuple>>>()
+    }
+
+    /// The function `sprite_with_draw` appends a draw of posture to the sprite
+    /// from the duration and the pairs of a draw line.
     fn sprite_with_draw(
-        &self, sprite: &mut Sprite, duration: &str, posture: &Sheet, pairs: &&[&str],
+        &self, sprite: &mut Sprite, duration: &Word, posture: &Sheet, pairs: &[Word],
+    ) -> Result<()> {
+        duration.text.parse::<i64>()
+                .map_err(|_| GraphicError::Line(
+                    duration.line + 1,
+                    Box::new(GraphicError::Draw(
+                        DrawError::UnvalidDuration(duration.text.to_string())
+                    ))
+                ))
+                .and_then(|time|
+                     Graphic::tuples_with_pairs(pairs)
+                             .and_then(|tuples| {
+                                 self.get_texel_resolved(posture)
+                                     .and_then(|texels|
+                                               Some(sprite.extend(&texels)));
+                                 sprite.insert_list(time, posture, tuples.as_slice())
+                                       .map_err(|why| GraphicError::Line(
+                                           duration.line + 1,
+                                           Box::new(GraphicError::Sprite(why))
+                                       ))
+                             }))
+    }
+
+    /// The function `sprite_with_layer` sets an upper layer of the last
+    /// draw of the sprite from the pairs of a layer block.
+    fn sprite_with_layer(
+        &self, sprite: &mut Sprite, name: &Word, pairs: &[Word],
+    ) -> Result<()> {
+        Layer::new(name.text)
+            .map_err(GraphicError::Layer)
+            .and_then(|layer|
+                 Graphic::tuples_with_pairs(pairs)
+                      .and_then(|tuples|
+                           if sprite.into_iter().next().is_none() {
+                               Err(GraphicError::Grammar(GrammarError::SyntaxDraw(name.line + 1)))
+                           } else {
+                               sprite.insert_layer(&layer, &tuples)
+                                     .ok_or_else(|| GraphicError::FoundTexel(name.text.to_string()))
+                           }))
+    }
+
+    /// The function `sprite_from_reader` returns a sprite
+    /// from a readable source, the layer blocks set the upper
+    /// layers of their draw.
+    pub fn sprite_from_reader<R: Read>(&self, mut source: R) -> Result<Sprite> {
+        let mut buffer: String = String::new();
+        let mut sprite: Sprite = Sprite::default();
+
+        if let Some(why) = source.read_to_string(&mut buffer).err() {
+            Err(GraphicError::ReadFile(None, why))
+        } else {
+            grammar::draws(&buffer)
+                .map_err(GraphicError::Grammar)
+                .and_then(|(version, draws)|
+                     draws.iter()
+                          .map(|&(ref layer, ref draw): &(Option<Word>, Vec<Word>)|
+                               match (*layer, draw.first(), draw.get(1)) {
+                                   (Some(ref name), _, _) if draw.len() == SPEC_LAYER_WORD => {
+                                       self.sprite_with_layer(&mut sprite, name, draw)
+                                   },
+                                   (Some(name), _, _) => Err(GraphicError::Grammar(
+                                       GrammarError::SyntaxDraw(name.line + 1)
+                                   )),
+                                   (None, Some(name), Some(duration))
+                                       if version < 2 || draw.len() == SPEC_DRAW_WORD => {
+                                       match Sheet::new(name.text) {
+                                           Err(why) => Err(GraphicError::Sheet(why)),
+                                           Ok(posture) => self.sprite_with_draw(
+                                               &mut sprite, duration, &posture,
+                                               draw.split_at(2).1
+                                           ),
+                                       }
+                                   },
+                                   (None, name, _) => Err(GraphicError::Grammar(
+                                       GrammarError::SyntaxDraw(name.map_or(0, |name| name.line + 1))
+                                   )),
+                               })
+                          .find(|anim| anim.is_err())
+                          .unwrap_or_else(|| Ok(())))
+                .and_then(|()|
+                     grammar::templates(&buffer).map_err(GraphicError::Grammar))
+                .and_then(|names|
+                     if names.is_empty() {
+                         Ok(sprite)
+                     } else {
+                         let base: Vec<Draw> = sprite.into_iter().cloned().collect::<Vec<Draw>>();
+
+                         self.sprite_with_template(sprite, &names)
+                             .map(|mut expanded| {
+                                  expanded.set_template(&names, &base);
+                                  expanded
+                              })
+                     })
+        }
+    }
+
+    /// The function `insert_from_sprite` inserts a sprite
+    /// from a readable source with the Sheet key.
+    pub fn insert_from_sprite<R: Read>(
+        &mut self, sheet: Sheet, source: R
     ) -> Result<()> {
-        self.texel.get(posture)
-            .and_then(|texels|
-                      Some(sprite.extend(texels)));
-        sprite.insert_list(duration.parse::<i64>().unwrap(),
-                        posture,
-                        pairs.into_iter().as_slice().chunks(2)
-                             .map(|pair: &[&str]|
-                                   Tuple::from((Part::new(pair[0]).unwrap(),
-                                    Emotion::new(pair[1]).unwrap())))
-                             .collect::<Vec<Tuple>>()
-                             .as_slice());
-        Ok(())
+        self.sprite_from_reader(source)
+            .and_then(|sprite| Ok(self.insert_sprite((sheet, sprite))))
     }
 
     /// The function `from_file_sprite` insert a sprite from a file.
     pub fn insert_from_spritefile<S: AsRef<OsStr> + AsRef<Path>>(
         &mut self, source: S
     ) -> Result<()> {
-        let mut buffer: String = String::new();
-        let mut sprite: Sprite = Sprite::default();
+        let path: &Path = source.as_ref();
 
-        match fs::OpenOptions::new().read(true).open(&source) {
-            Err(why) => Err(GraphicError::OpenFile(why)),
-            Ok(mut file) => {
-                if let Some(why) = file.read_to_string(&mut buffer).err() {
-                    Err(GraphicError::ReadFile(why))
-                } else {
-                    buffer.split(|c| " \n:".contains(c))
-                        .filter(|x| x.is_empty().not())
-                        .collect::<Vec<&str>>()
-                        .as_slice()
-                        .chunks(SPEC_MAX_XY*2+2)
-                        .map(|sprite_and_draw|
-                             sprite_and_draw.split_first()
-                             .and_then(|(sprite_name, duration_and_draw)|
-                                       duration_and_draw.split_first()
-                                       .and_then(|(duration, draw)| Some(
-                                            match Sheet::new(sprite_name) {
-                                                Err(why) => Err(GraphicError::Sheet(why)),
-                                                Ok(posture) => {
-                                                    self.sprite_with_draw(
-                                                        &mut sprite, duration, &posture, &draw
-                                                    )
-                                                },
-                                            })))
-                             .unwrap())
-                        .find(|anim| anim.is_err())
-                        .unwrap_or_else(|| {
-                            let path: &Path = source.as_ref();
-                            let name: &OsStr = path.file_stem().unwrap_or_default();
-                            let name = name.to_str().unwrap_or_default();
-                            match Sheet::new(name) {
-                                Err(why) => Err(GraphicError::Sheet(why)),
-                                Ok(sheet) => Ok(self.insert_sprite((sheet, sprite))),
-                            }
-                        })
+        match fs::OpenOptions::new().read(true).open(path) {
+            Err(why) => Err(GraphicError::OpenFile(path.to_path_buf(), why)),
+            Ok(file) => {
+                let name: &OsStr = path.file_stem().unwrap_or_default();
+                let name = name.to_str().unwrap_or_default();
+
+                match Sheet::new(name) {
+                    Err(why) => Err(GraphicError::Sheet(why)),
+                    Ok(sheet) => self.insert_from_sprite(sheet, file)
+                                     .map_err(|why| why.with_path(path)),
                 }
             },
         }
     }
 
+    /// The mutator method `swap_sprite` replaces the sprite of the same
+    /// Sheet key while keeping its draw and cell cursors.
+    pub fn swap_sprite(&mut self, (sheet, mut sprite): (Sheet, Sprite)) {
+        match self.sprite.get_ref()
+                         .iter()
+                         .position(|&(ref name, _)| name.eq(&sheet)) {
+            None => self.insert_sprite((sheet, sprite)),
+            Some(index) => {
+                if let Some(&mut (_, ref mut current)) = self.sprite.get_mut()
+                                                                     .get_mut(index) {
+                    sprite.clone_position_from(current);
+                    *current = sprite;
+                }
+            },
+        }
+    }
+
+    /// The method `reload_texelfile` re-parses a texel file, replaces
+    /// the texels of its postures, and of the posture of its name,
+    /// and remaps the sprites in memory.
+    pub fn reload_texelfile<S: AsRef<Path>>(&mut self, source: S) -> Result<()> {
+        let mut texel: Graphic = Graphic::default();
+        let named: Option<Sheet> = source.as_ref()
+                                         .file_stem()
+                                         .and_then(|name| name.to_str())
+                                         .and_then(|name| Sheet::new(name).ok());
+
+        texel.insert_from_texelfile(source).and_then(|()| {
+            let postures: HashSet<Sheet> = texel.texel.keys()
+                                                      .chain(texel.level.keys())
+                                                      .chain(texel.parent.keys())
+                                                      .chain(named.iter())
+                                                      .cloned()
+                                                      .collect::<HashSet<Sheet>>();
+
+            postures.iter()
+                    .all(|posture| {
+                        self.texel.remove(posture);
+                        self.level.remove(posture);
+                        self.parent.remove(posture);
+                        true
+                    });
+            texel.texel.into_iter()
+                 .all(|(posture, texels): (Sheet, HashMap<Tuple, Vec<Texel>>)| {
+                     self.texel.entry(posture)
+                         .or_insert_with(|| HashMap::with_capacity(SPEC_CAPACITY_SPRITE))
+                         .extend(texels);
+                     true
+                 });
+            texel.level.into_iter()
+                 .all(|(posture, texels): (Sheet, HashMap<Tuple, BTreeMap<u8, Vec<Texel>>>)| {
+                     self.level.entry(posture)
+                         .or_insert_with(|| HashMap::with_capacity(SPEC_CAPACITY_SPRITE))
+                         .extend(texels);
+                     true
+                 });
+            texel.parent.into_iter()
+                 .map(|(posture, parent): (Sheet, Sheet)| self.set_parent(posture, parent))
+                 .find(|f| f.is_err())
+                 .unwrap_or_else(|| Ok(()))
+                 .and_then(|()| Ok(self.remap_sprites()))
+        })
+    }
+
+    /// The method `remove_texelfile` drops the texels of a removed texel
+    /// file by reading again the other texel files of the sub-directory,
+    /// and remaps the sprites.
+    pub fn remove_texelfile(&mut self) -> Result<()> {
+        let mut texel: Graphic = Graphic::default();
+
+        self.get_nct()
+            .and_then(|nct|
+                 fs::read_dir(&nct).map_err(|why| GraphicError::ReadDir(nct.clone(), why)))
+            .and_then(|entry| {
+                let mut paths: Vec<PathBuf> =
+                    entry.filter_map(|entry| entry.ok())
+                         .map(|entry| entry.path())
+                         .filter(|path| path.extension().eq(&Some(OsStr::new(SPEC_EXT_NCT))))
+                         .collect::<Vec<PathBuf>>();
+
+                paths.sort();
+                paths.iter()
+                     .map(|path| texel.insert_from_texelfile(path))
+                     .find(|f| f.is_err())
+                     .unwrap_or_else(|| Ok(()))
+            })
+            .and_then(|()| {
+                self.texel = texel.texel;
+                self.level = texel.level;
+                self.parent = texel.parent;
+                Ok(self.remap_sprites())
+            })
+    }
+
+    /// The method `remap_sprites` takes again the glyphs of every sprite
+    /// from the current texels, the edits of the sprites are kept.
+    fn remap_sprites(&mut self) {
+        let texels: HashMap<Sheet, HashMap<Tuple, Vec<Texel>>> = self.texel_resolved();
+
+        self.sprite.get_mut()
+                   .iter_mut()
+                   .all(|&mut (_, ref mut sprite)| {
+                       sprite.remap(&texels);
+                       true
+                   });
+    }
+
+    /// The method `reload_spritefile` re-parses a sprite file and swaps
+    /// it with the current sprite of the same Sheet key.
+    pub fn reload_spritefile<S: AsRef<Path>>(&mut self, source: S) -> Result<()> {
+        let path: &Path = source.as_ref();
+        let name: &OsStr = path.file_stem().unwrap_or_default();
+
+        match (Sheet::new(name.to_str().unwrap_or_default()),
+               fs::OpenOptions::new().read(true).open(path)) {
+            (Err(why), _) => Err(GraphicError::Sheet(why)),
+            (_, Err(why)) => Err(GraphicError::OpenFile(path.to_path_buf(), why)),
+            (Ok(sheet), Ok(file)) => {
+                self.sprite_from_reader(file)
+                    .map_err(|why| why.with_path(path))
+                    .and_then(|sprite| Ok(self.swap_sprite((sheet, sprite))))
+            },
+        }
+    }
+
+    /// The method `remove_spritefile` drops the sprite of a removed
+    /// sprite file, the sprite cursor stays in the list.
+    pub fn remove_spritefile<S: AsRef<Path>>(&mut self, source: S) -> Result<()> {
+        let name: &OsStr = source.as_ref().file_stem().unwrap_or_default();
+
+        Sheet::new(name.to_str().unwrap_or_default())
+              .map_err(GraphicError::Sheet)
+              .and_then(|sheet| {
+                  let position: usize = self.sprite.position() as usize;
+
+                  self.sprite.get_mut().retain(|&(ref name, _)| name.ne(&sheet));
+                  let len: usize = self.sprite.get_ref().len();
+                  self.sprite.set_position(position.min(len.saturating_sub(1)) as u64);
+                  Ok(())
+              })
+    }
+
+    /// The method `reload` polls the watcher and reloads every changed
+    /// texel and sprite file, drops the data of the removed ones, and
+    /// returns true if something was reloaded with the errors of the
+    /// files which are kept changed for the next poll.
+    pub fn reload(&mut self, watcher: &mut Watcher) -> (bool, Vec<GraphicError>) {
+        match watcher.poll() {
+            None => (false, Vec::new()),
+            Some((nct, ncs)) => {
+                let mut results: Vec<(&PathBuf, Result<()>)> =
+                    nct.iter()
+                       .map(|path| (path, if path.exists() {
+                           self.reload_texelfile(path)
+                       } else {
+                           self.remove_texelfile()
+                       }))
+                       .collect::<Vec<(&PathBuf, Result<()>)>>();
+
+                results.extend(ncs.iter()
+                                  .map(|path| (path, if path.exists() {
+                                      self.reload_spritefile(path)
+                                  } else {
+                                      self.remove_spritefile(path)
+                                  })));
+                let errors: Vec<GraphicError> =
+                    results.into_iter()
+                           .filter_map(|(path, result)| match result {
+                               Ok(()) => {
+                                   watcher.loaded(path);
+                                   None
+                               },
+                               Err(why) => Some(why),
+                           })
+                           .collect::<Vec<GraphicError>>();
+
+                (true, errors)
+            },
+        }
+    }
+
+    /// The method `write_spritefile` writes the sprite of the Sheet key
+    /// in its file of the sprite sub-directory, an expanded sprite is
+    /// written as its templates and its base draws and is refused when
+    /// its frames are edited.
+    pub fn write_spritefile(&self, sheet: &Sheet) -> Result<()> {
+        match self.get_sprite(sheet) {
+            None => Err(GraphicError::FoundSprite(sheet.get_name().to_string())),
+            Some(sprite) if self.is_edited(sprite) => Err(GraphicError::Template(
+                TemplateError::EditedSprite(sheet.get_name().to_string())
+            )),
+            Some(sprite) => {
+                self.get_ncs().and_then(|ncs| {
+                    let path: PathBuf = ncs.join(sheet.get_name()).with_extension(SPEC_EXT_NCS);
+
+                    fs::File::create(&path).and_then(|mut file| write!(file, "{}", sprite))
+                                           .map_err(|why| GraphicError::WriteFile(path, why))
+                })
+            },
+        }
+    }
+
+    /// The function `insert_from_expression` defines the expressions
+    /// from a readable source.
+    pub fn insert_from_expression<R: Read>(&mut self, source: R) -> Result<()> {
+        expression::read_expressions(source).map(|expressions| self.define_all(expressions, Graphic::define_expression))
+    }
+
+    /// The accessor method `get_expressionfile` returns the path
+    /// of the expression file of the root.
+    pub fn get_expressionfile(&self) -> PathBuf {
+        self.get_root().join(SPEC_FILE_NCE).with_extension(SPEC_EXT_NCE)
+    }
+
+    /// The method `read_expressionfile` defines the expressions of the
+    /// expression file of the root.
+    pub fn read_expressionfile(&mut self) -> Result<()> {
+        self.read_rootfile(SPEC_FILE_NCE, SPEC_EXT_NCE, Graphic::insert_from_expression)
+    }
+
+    /// The method `write_expressionfile` writes the library of expression
+    /// in the expression file of the root.
+    pub fn write_expressionfile(&self) -> Result<()> {
+        let path: PathBuf = self.get_expressionfile();
+
+        fs::File::create(&path)
+                 .and_then(|file| expression::write_expressions(file, &self.expression))
+                 .map_err(|why| GraphicError::WriteFile(path, why))
+    }
+
+    /// The function `insert_from_transition` defines the transitions
+    /// from a readable source.
+    pub fn insert_from_transition<R: Read>(&mut self, source: R) -> Result<()> {
+        transition::read_transitions(source).map(|transitions| self.define_all(transitions, Graphic::define_transition))
+    }
+
+    /// The accessor method `get_transitionfile` returns the path
+    /// of the transition file of the root.
+    pub fn get_transitionfile(&self) -> PathBuf {
+        self.get_root().join(SPEC_FILE_NCR).with_extension(SPEC_EXT_NCR)
+    }
+
+    /// The method `read_transitionfile` defines the transitions of the
+    /// transition file of the root.
+    pub fn read_transitionfile(&mut self) -> Result<()> {
+        self.read_rootfile(SPEC_FILE_NCR, SPEC_EXT_NCR, Graphic::insert_from_transition)
+    }
+
+    /// The function `insert_from_template` defines the templates
+    /// from a readable source.
+    pub fn insert_from_template<R: Read>(&mut self, source: R) -> Result<()> {
+        template::read_templates(source).map(|templates| self.define_all(templates, Graphic::define_template))
+    }
+
+    /// The accessor method `get_templatefile` returns the path
+    /// of the template file of the root.
+    pub fn get_templatefile(&self) -> PathBuf {
+        self.get_root().join(SPEC_FILE_NCP).with_extension(SPEC_EXT_NCP)
+    }
+
+    /// The method `read_templatefile` defines the templates of the
+    /// template file of the root.
+    pub fn read_templatefile(&mut self) -> Result<()> {
+        self.read_rootfile(SPEC_FILE_NCP, SPEC_EXT_NCP, Graphic::insert_from_template)
+    }
+
+    /// The function `insert_from_effect` defines the effects
+    /// from a readable source.
+    pub fn insert_from_effect<R: Read>(&mut self, source: R) -> Result<()> {
+        effect::read_effects(source).map(|effects| self.define_all(effects, Graphic::define_effect))
+    }
+
+    /// The accessor method `get_effectfile` returns the path
+    /// of the effect file of the root.
+    pub fn get_effectfile(&self) -> PathBuf {
+        self.get_root().join(SPEC_FILE_NCO).with_extension(SPEC_EXT_NCO)
+    }
+
+    /// The method `read_effectfile` defines the effects of the
+    /// effect file of the root.
+    pub fn read_effectfile(&mut self) -> Result<()> {
+        self.read_rootfile(SPEC_FILE_NCO, SPEC_EXT_NCO, Graphic::insert_from_effect)
+    }
+
+    /// The method `define_all` defines each definition of a file
+    /// with the mutator of its library.
+    fn define_all<T, F: Fn(&mut Graphic, T)>(&mut self, definitions: Vec<T>, define: F) {
+        definitions.into_iter()
+                   .all(|definition| {
+                       define(self, definition);
+                       true
+                   });
+    }
+
+    /// The method `read_rootfile` inserts the definitions of the file
+    /// of the root, or nothing without the file.
+    fn read_rootfile<F>(&mut self, name: &str, extension: &str, insert: F) -> Result<()>
+        where F: FnOnce(&mut Graphic, fs::File) -> Result<()> {
+        let path: PathBuf = self.get_root().join(name).with_extension(extension);
+
+        match fs::OpenOptions::new().read(true).open(&path) {
+            Err(ref why) if why.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(why) => Err(GraphicError::OpenFile(path, why)),
+            Ok(file) => insert(self, file).map_err(|why| why.with_path(&path)),
+        }
+    }
+
+    /// The accessor method `get_machinefile` returns the path
+    /// of the state machine file of the root.
+    pub fn get_machinefile(&self) -> PathBuf {
+        self.get_root().join(SPEC_FILE_NCM).with_extension(SPEC_EXT_NCM)
+    }
+
+    /// The method `read_machinefile` returns the state machine
+    /// of the state machine file.
+    pub fn read_machinefile(&self) -> Result<Machine> {
+        let path: PathBuf = self.get_machinefile();
+
+        match fs::OpenOptions::new().read(true).open(&path) {
+            Err(why) => Err(GraphicError::OpenFile(path, why)),
+            Ok(file) => machine::read_machine(file).map_err(|why| why.with_path(&path)),
+        }
+    }
+
+    /// The function `conflictfile` returns the path of the conflict file
+    /// of the sprite of the Sheet key.
+    fn conflictfile(&self, sheet: &Sheet) -> Result<PathBuf> {
+        self.get_ncs().and_then(|ncs| Ok(merge::conflict_path(
+            ncs.join(sheet.get_name()).with_extension(SPEC_EXT_NCS)
+        )))
+    }
+
+    /// The method `read_conflictfile` returns the conflicts left by a merge
+    /// of the sprite of the Sheet key, or nothing without conflict file.
+    pub fn read_conflictfile(&self, sheet: &Sheet) -> Result<Vec<Conflict>> {
+        self.conflictfile(sheet).and_then(|path|
+            match fs::OpenOptions::new().read(true).open(&path) {
+                Err(ref why) if why.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+                Err(why) => Err(GraphicError::OpenFile(path, why)),
+                Ok(file) => merge::read_conflicts(file).map_err(|why| why.with_path(&path)),
+            })
+    }
+
+    /// The method `write_conflictfile` writes the conflicts left of the sprite
+    /// of the Sheet key, the conflict file is removed without conflict.
+    pub fn write_conflictfile(&self, sheet: &Sheet, conflicts: &[Conflict]) -> Result<()> {
+        self.conflictfile(sheet).and_then(|path|
+            if conflicts.is_empty() {
+                match fs::remove_file(&path) {
+                    Err(ref why) if why.kind() == io::ErrorKind::NotFound => Ok(()),
+                    Err(why) => Err(GraphicError::WriteFile(path, why)),
+                    Ok(()) => Ok(()),
+                }
+            } else {
+                fs::File::create(&path).and_then(|file| merge::write_conflicts(file, conflicts))
+                                       .map_err(|why| GraphicError::WriteFile(path, why))
+            })
+    }
+
+    /// The mutator method `resolve_conflict` applies a side of a conflict
+    /// on the sprite of the Sheet key.
+    pub fn resolve_conflict(&mut self, sheet: &Sheet, conflict: &Conflict,
+                            side: Side) -> Option<()> {
+        self.sprite.get_mut()
+                   .iter_mut()
+                   .find(|&&mut (ref name, _)| name.eq(sheet))
+                   .and_then(|&mut (_, ref mut sprite)| sprite.resolve(conflict, side))
+    }
+
+    /// The mutator method `set_position_sprite_cell` moves the cursors
+    /// of the current sprite on the draw and the cell.
+    pub fn set_position_sprite_cell(&mut self, draw: usize, cell: usize) -> Option<()> {
+        let position: usize = self.get_position();
+
+        self.sprite.get_mut()
+                   .get_mut(position)
+                   .and_then(|&mut (_, ref mut sprite)| sprite.set_position_cell(draw, cell))
+    }
+
     /// The accessor method `get_position` returns the position of
     /// the file sprite cursor.
     fn get_position(&self) -> usize {
@@ -486,6 +1413,68 @@ impl Graphic {
                                 sprite.set_current((emotion, texel))));
     }
 
+    /// The function `with_current_draw` calls the function with the
+    /// current draw of the current sprite and the texels of its posture.
+    fn with_current_draw<T, F: FnOnce(&mut Draw, &HashMap<Tuple, Vec<Texel>>) -> T>(
+        &mut self, function: F
+    ) -> Option<T> {
+        let position: usize = self.get_position();
+        let texels: Option<HashMap<Tuple, Vec<Texel>>> =
+            self.sprite.get_ref()
+                       .get(position)
+                       .and_then(|&(_, ref sprite)| sprite.get_posture())
+                       .and_then(|posture| self.get_texel_resolved(posture));
+
+        texels.and_then(|texels|
+            self.sprite.get_mut()
+                       .get_mut(position)
+                       .and_then(|&mut (_, ref mut sprite)|
+                            sprite.with_current_draw(|draw| function(draw, &texels))))
+    }
+
+    /// The mutator method `mirror_current_draw` mirrors horizontally
+    /// the current draw of the current sprite, returns the cells
+    /// without texel for the posture.
+    pub fn mirror_current_draw(&mut self) -> Option<Vec<usize>> {
+        self.with_current_draw(|draw, texels| draw.mirror_horizontal(texels))
+    }
+
+    /// The mutator method `set_layer_current_draw` changes the active
+    /// layer of the current draw, whose cells are edited.
+    pub fn set_layer_current_draw(&mut self, layer: Layer) -> Option<()> {
+        self.with_current_draw(|draw, _| draw.set_layer(layer))
+    }
+
+    /// The mutator method `fill_current_draw` sets the emotion of the cells
+    /// of the current draw, returns the cells without texel for the posture.
+    pub fn fill_current_draw(&mut self, cells: &[usize], emotion: &Emotion
+    ) -> Option<Vec<usize>> {
+        self.with_current_draw(|draw, texels| draw.fill(cells, emotion, texels))
+    }
+
+    /// The mutator method `paste_current_draw` sets the part and the emotion
+    /// of the cells of the current draw, returns the cells without texel
+    /// for the posture.
+    pub fn paste_current_draw(&mut self, cells: &[(usize, (Part, Emotion))]
+    ) -> Option<Vec<usize>> {
+        self.with_current_draw(|draw, texels| draw.paste(cells, texels))
+    }
+
+    /// The mutator method `set_part_emotion_all` sets the emotion of the part
+    /// on the draws of the range of the current sprite or on every draw,
+    /// returns the cells without texel for the posture of their draw.
+    pub fn set_part_emotion_all(&mut self, part: &Part, emotion: &Emotion,
+                                range: Option<Range<usize>>) -> Option<Vec<Missing>> {
+        let position: usize = self.get_position();
+        let texel: HashMap<Sheet, HashMap<Tuple, Vec<Texel>>> = self.texel_resolved();
+
+        self.sprite.get_mut()
+                   .get_mut(position)
+                   .map(|&mut (_, ref mut sprite)|
+                        sprite.set_part_emotion_all(part, emotion,
+                                                    range.unwrap_or(0..SPEC_MAX_DRAW),
+                                                    &texel))
+    }
 
     /// The accessor method `get_sprite` returns a reference on sprite.
     pub fn get_current_sprite(&self) -> Option<&(Sheet, Sprite)> {
@@ -493,6 +1482,15 @@ impl Graphic {
     }
 }
 
+/// The function `define` adds the definition to the library or replaces
+/// the definition of the same key.
+fn define<T, F: Fn(&T, &T) -> bool>(library: &mut Vec<T>, definition: T, same: F) {
+    match library.iter().position(|other| same(other, &definition)) {
+        None => library.push(definition),
+        Some(index) => library[index] = definition,
+    }
+}
+
 /// A trait for giving a type a useful default value.
 impl Default for Graphic {
 
@@ -500,7 +1498,15 @@ impl Default for Graphic {
     fn default() -> Graphic {
         Graphic {
             texel: HashMap::with_capacity(SPEC_CAPACITY_POSITION),
+            level: HashMap::with_capacity(SPEC_CAPACITY_POSITION),
+            parent: HashMap::new(),
+            expression: Vec::new(),
+            transition: Vec::new(),
+            template: Vec::new(),
+            effect: Vec::new(),
             sprite: io::Cursor::new(Vec::with_capacity(SPEC_CAPACITY_SPRITE)),
+            root: None,
+            mkdir: false,
         }
     }
 }
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use super::{Graphic, GraphicError, Result, report};
+use super::grammar::{self, Word, SPEC_DRAW_WORD, SPEC_LAYER_WORD};
+use super::emotion::Emotion;
+use super::sheet::Sheet;
+use super::sprite::draw::{Layer, SPEC_MAX_XY};
+use super::sprite::texel::part::Part;
+use super::tuple::Tuple;
+
+/// The enum `Lint` defines the problems of a sprite file which
+/// the loader drops or refuses without error.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Lint {
+    /// The name of posture is unknown.
+    UnknownSheet(String),
+    /// The name of part is unknown.
+    UnknownPart(String),
+    /// The name of emotion is unknown.
+    UnknownEmotion(String),
+    /// The duration isn't a number of milliseconds.
+    Duration(String),
+    /// The pair hasn't texel for the posture.
+    UnknownPair(Sheet, Tuple),
+    /// The pair has fewer glyphs than cells using its part.
+    ShortGlyph(Sheet, Tuple, usize, usize),
+    /// The draw has only this number of cells and is dropped.
+    DroppedDraw(usize),
+    /// The line of texel is refused by the loader.
+    Texel(String),
+    /// The draw hasn't this number of cells before the separator.
+    UnvalidDraw(usize),
+    /// The name of layer is unknown.
+    UnknownLayer(String),
+    /// The layer hasn't a draw below.
+    OrphanLayer,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Lint::UnknownSheet(ref name) => write!(f, "unknown posture `{}`", name),
+            Lint::UnknownPart(ref name) => write!(f, "unknown part `{}`", name),
+            Lint::UnknownEmotion(ref name) => write!(f, "unknown emotion `{}`", name),
+            Lint::Duration(ref duration) => write!(f, "invalid duration `{}`", duration),
+            Lint::UnknownPair(posture, Tuple { part, emotion }) => {
+                write!(f, "no texel `{:?}:{:?}` for the posture `{}`",
+                       part, emotion, posture)
+            },
+            Lint::ShortGlyph(posture, Tuple { part, emotion }, glyph, cell) => {
+                write!(f, "the texel `{:?}:{:?}` of `{}` has {} glyph(s) for {} cell(s)",
+                       part, emotion, posture, glyph, cell)
+            },
+            Lint::DroppedDraw(cell) => {
+                write!(f, "the draw is dropped with {}/{} cells", cell, SPEC_MAX_XY)
+            },
+            Lint::Texel(ref why) => write!(f, "unvalid texel: {}", why),
+            Lint::UnvalidDraw(cell) => {
+                write!(f, "the draw is refused with {}/{} cells", cell, SPEC_MAX_XY)
+            },
+            Lint::UnknownLayer(ref name) => write!(f, "unknown layer `{}`", name),
+            Lint::OrphanLayer => write!(f, "the layer hasn't a draw"),
+        }
+    }
+}
+
+/// The struct `Report` is a lint with its line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// The line from one.
+    pub line: usize,
+    pub lint: Lint,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.line, self.lint)
+    }
+}
+
+/// The function `lint_pair` checks a pair of part and emotion
+/// with the number of previous cells of this part.
+fn lint_pair(graphic: &Graphic, posture: &Sheet, part: &str, emotion: &str,
+             count: &HashMap<Part, usize>
+) -> ::std::result::Result<(Tuple, Option<usize>), Lint> {
+    match (Part::new(part), Emotion::new(emotion)) {
+        (Err(_), _) => Err(Lint::UnknownPart(part.to_string())),
+        (_, Err(_)) => Err(Lint::UnknownEmotion(emotion.to_string())),
+        (Ok(part), Ok(emotion)) => {
+            let tuple: Tuple = Tuple::from((part, emotion));
+            let index: usize = count.get(&part).cloned().unwrap_or_default();
+
+            match graphic.get_texel(posture, &tuple) {
+                None => Err(Lint::UnknownPair(*posture, tuple)),
+                Some(texels) if texels.len() <= index => Ok((tuple, Some(texels.len()))),
+                Some(_) => Ok((tuple, None)),
+            }
+        },
+    }
+}
+
+/// The function `lint_draw` checks a draw, a chunk of tokens
+/// with the posture, the duration and the pairs.
+fn lint_draw(graphic: &Graphic, chunk: &[(usize, &str)]) -> Vec<Report> {
+    let mut reports: Vec<Report> = Vec::new();
+    let (line, name): (usize, &str) = chunk.first().cloned().unwrap_or_default();
+    let posture: Option<Sheet> = Sheet::new(name).ok();
+
+    if posture.is_none() {
+        reports.push(Report { line: line, lint: Lint::UnknownSheet(name.to_string()) });
+    }
+    if let Some(&(line, duration)) = chunk.get(1) {
+        if duration.parse::<i64>().is_err() {
+            reports.push(Report { line: line, lint: Lint::Duration(duration.to_string()) });
+        }
+    }
+    if let Some(posture) = posture {
+        let mut count: HashMap<Part, usize> = HashMap::new();
+        let mut shorts: Vec<(Tuple, usize, usize, usize)> = Vec::new();
+        let mut kept: Option<usize> = None;
+
+        for (index, pair) in chunk.split_at(2.min(chunk.len())).1
+                                  .chunks(2)
+                                  .enumerate() {
+            if let [(line, part), (_, emotion)] = *pair {
+                match lint_pair(graphic, &posture, part, emotion, &count) {
+                    Err(lint @ Lint::UnknownPair(..)) => {
+                        kept = kept.or(Some(index));
+                        reports.push(Report { line: line, lint: lint });
+                    },
+                    Err(lint) => reports.push(Report { line: line, lint: lint }),
+                    Ok((tuple, short)) => {
+                        let cell: &mut usize = count.entry(tuple.part).or_insert(0);
+
+                        *cell += 1;
+                        if let Some(glyph) = short {
+                            match shorts.iter_mut()
+                                        .find(|&&mut (ref short, ..)| short.eq(&tuple)) {
+                                Some(&mut (_, _, _, ref mut used)) => *used = *cell,
+                                None => shorts.push((tuple, line, glyph, *cell)),
+                            }
+                        }
+                    },
+                }
+            }
+        }
+        reports.extend(shorts.into_iter()
+                             .map(|(tuple, line, glyph, cell)|
+                                  Report {
+                                      line: line,
+                                      lint: Lint::ShortGlyph(posture, tuple, glyph, cell),
+                                  }));
+        if let Some(cell) = kept.or_else(|| if chunk.len() < SPEC_DRAW_WORD {
+                                             Some(chunk.len().saturating_sub(2) / 2)
+                                         } else {
+                                             None
+                                         }) {
+            reports.push(Report { line: line, lint: Lint::DroppedDraw(cell) });
+        }
+    }
+    reports
+}
+
+/// The function `lint_layer` checks a layer, its name with the pairs,
+/// against the texels of the posture of its draw; the pairs without
+/// part are transparent and haven't texel.
+fn lint_layer(graphic: &Graphic, posture: Option<Sheet>, name: &Word,
+              chunk: &[(usize, &str)]) -> Vec<Report> {
+    let mut reports: Vec<Report> = Vec::new();
+    let mut count: HashMap<Part, usize> = HashMap::new();
+
+    if Layer::new(name.text).is_err() {
+        reports.push(Report { line: name.line + 1, lint: Lint::UnknownLayer(name.text.to_string()) });
+    }
+    match posture {
+        None => reports.push(Report { line: name.line + 1, lint: Lint::OrphanLayer }),
+        Some(posture) => {
+            for pair in chunk.chunks(2) {
+                if let [(line, part), (_, emotion)] = *pair {
+                    match Part::new(part) {
+                        Ok(Part::None) => if Emotion::new(emotion).is_err() {
+                            reports.push(Report {
+                                line: line, lint: Lint::UnknownEmotion(emotion.to_string()),
+                            });
+                        },
+                        _ => match lint_pair(graphic, &posture, part, emotion, &count) {
+                            Err(lint) => reports.push(Report { line: line, lint: lint }),
+                            Ok((tuple, short)) => {
+                                let cell: &mut usize = count.entry(tuple.part).or_insert(0);
+
+                                *cell += 1;
+                                if let Some(glyph) = short {
+                                    reports.push(Report {
+                                        line: line,
+                                        lint: Lint::ShortGlyph(posture, tuple, glyph, *cell),
+                                    });
+                                }
+                            },
+                        },
+                    }
+                }
+            }
+        },
+    }
+    if chunk.len() != SPEC_LAYER_WORD {
+        reports.push(Report {
+            line: name.line + 1,
+            lint: Lint::UnvalidDraw(chunk.len() / 2),
+        });
+    }
+    reports
+}
+
+/// The function `lint_sprite` returns the lints of a sprite from
+/// a readable source against the texels of the Graphic.
+pub fn lint_sprite<R: Read>(graphic: &Graphic, mut source: R) -> Result<Vec<Report>> {
+    let mut buffer: String = String::new();
+
+    try!(source.read_to_string(&mut buffer)
+               .map_err(|why| GraphicError::ReadFile(None, why)));
+    grammar::draws(&buffer)
+        .map_err(GraphicError::Grammar)
+        .map(|(version, draws)| {
+             let mut posture: Option<Sheet> = None;
+
+             draws.iter()
+                  .flat_map(|&(ref layer, ref draw)| {
+                      let chunk: Vec<(usize, &str)> = draw.iter()
+                                                          .map(|word| (word.line + 1, word.text))
+                                                          .collect::<Vec<(usize, &str)>>();
+
+                      if let Some(ref name) = *layer {
+                          return lint_layer(graphic, posture, name, &chunk);
+                      }
+                      posture = chunk.first().and_then(|&(_, name)| Sheet::new(name).ok());
+                      let mut reports: Vec<Report> = lint_draw(graphic, &chunk);
+
+                      if version > 1 && chunk.len() != SPEC_DRAW_WORD {
+                          reports.retain(|report| match report.lint {
+                              Lint::DroppedDraw(_) => false,
+                              _ => true,
+                          });
+                          reports.push(Report {
+                              line: chunk.first().map_or(1, |&(line, _)| line),
+                              lint: Lint::UnvalidDraw(chunk.len().saturating_sub(2) / 2),
+                          });
+                      }
+                      reports
+                  })
+                  .collect::<Vec<Report>>()
+        })
+}
+
+/// The function `lint_texel` returns the lints of the lines of texel
+/// from a readable source which the loader refuses, without following
+/// the includes.
+pub fn lint_texel<R: Read>(mut source: R) -> Result<Vec<Report>> {
+    let mut buffer: String = String::new();
+
+    try!(source.read_to_string(&mut buffer)
+               .map_err(|why| GraphicError::ReadFile(None, why)));
+    grammar::lines(&buffer)
+        .map_err(GraphicError::Grammar)
+        .map(|(_, lines)|
+             lines.into_iter()
+                  .filter(|&(_, content)| grammar::include(content).is_none())
+                  .filter_map(|(line, content)|
+                      Graphic::default().insert_from_texel(content.as_bytes())
+                                        .err()
+                                        .map(|why| Report {
+                                            line: line,
+                                            lint: Lint::Texel(report(&why)),
+                                        }))
+                  .collect::<Vec<Report>>())
+}
+
+/// The function `lint_spritefile` returns the lints of a sprite file
+/// against the texels of the Graphic.
+pub fn lint_spritefile<S: AsRef<Path>>(graphic: &Graphic, source: S) -> Result<Vec<Report>> {
+    fs::OpenOptions::new().read(true)
+                          .open(source.as_ref())
+                          .map_err(|why|
+                               GraphicError::OpenFile(source.as_ref().to_path_buf(), why))
+                          .and_then(|file|
+                               lint_sprite(graphic, io::BufReader::new(file))
+                                   .map_err(|why| why.with_path(source.as_ref())))
+}
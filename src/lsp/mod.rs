@@ -0,0 +1,460 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+
+use editeur::{Graphic, SPEC_SUBD_NCT, SPEC_EXT_NCT, SPEC_EXT_NCS};
+use editeur::grammar::{self, Word, SPEC_DRAW_WORD};
+use editeur::lint::{self, Lint, Report};
+use editeur::glyph::GlyphMap;
+use editeur::emotion::Emotion;
+use editeur::sheet::Sheet;
+use editeur::sprite::texel::{SPEC_GLYPH_MIN, SPEC_GLYPH_MAX};
+use editeur::sprite::texel::part::Part;
+use editeur::tuple::Tuple;
+
+use serde_json::{self, Value};
+
+/// The scheme of the document uris.
+pub const SPEC_SCHEME: &'static str = "file://";
+
+/// The JSON-RPC code of an unknown method.
+const SPEC_METHOD_NOT_FOUND: i64 = -32601;
+/// The JSON-RPC code of an unvalid message.
+const SPEC_PARSE_ERROR: i64 = -32700;
+
+/// The function `word_at` returns the words of the draw under the
+/// position with the index of the word under the position, or of the
+/// word which would be typed at the position; the pairs of a layer
+/// follow the posture and the duration of its draw.
+fn word_at(text: &str, line: usize, character: usize) -> (Vec<Word>, usize, bool) {
+    let before = |word: &Word| word.line < line || (word.line == line && word.start < character);
+    let mut head: Vec<Word> = Vec::new();
+    let draw: Vec<Word> = grammar::draws(text).ok()
+                                              .and_then(|(_, draws)|
+                                                   draws.into_iter()
+                                                        .map(|(layer, draw)| match layer {
+                                                            None => {
+                                                                head = draw.iter().take(2).cloned().collect();
+                                                                (draw.first().cloned(), draw)
+                                                            },
+                                                            Some(name) => {
+                                                                (Some(name), head.iter().cloned().chain(draw).collect())
+                                                            },
+                                                        })
+                                                        .take_while(|&(ref first, _)|
+                                                             first.as_ref().map_or(false, |word| before(word)))
+                                                        .last()
+                                                        .map(|(_, draw)| draw))
+                                              .unwrap_or_default();
+    let index: usize = draw.iter().take_while(|word| before(word)).count();
+    let under: bool = index.checked_sub(1)
+                           .and_then(|index| draw.get(index))
+                           .map_or(false, |word| word.line == line && character <= word.end);
+
+    if under {
+        (draw, index - 1, true)
+    } else {
+        (draw, index, false)
+    }
+}
+
+/// The function `pair_at` returns the posture, the pair and the
+/// number of previous cells of its part in the draw, for the pair
+/// of a sprite source under the position.
+fn pair_at(text: &str, line: usize, character: usize) -> Option<(Sheet, Tuple, usize)> {
+    match word_at(text, line, character) {
+        (ref draw, index, true) if index >= 2 => {
+            let pair: usize = 2 + (index - 2) / 2 * 2;
+            let part = |index: usize| draw.get(index).and_then(|word| Part::new(word.text).ok());
+
+            match (draw.first().and_then(|word| Sheet::new(word.text).ok()),
+                   part(pair),
+                   draw.get(pair + 1).and_then(|word| Emotion::new(word.text).ok())) {
+                (Some(sheet), Some(part_key), Some(emotion)) => Some((
+                    sheet,
+                    Tuple::from((part_key, emotion)),
+                    (2..pair).filter(|&index|
+                                  index % 2 == 0 &&
+                                  part(index).eq(&Some(part_key)))
+                             .count(),
+                )),
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+/// The function `path` returns the path of a document uri.
+fn path(uri: &str) -> PathBuf {
+    PathBuf::from(uri.trim_left_matches(SPEC_SCHEME))
+}
+
+/// The function `is_extension` returns true if the document uri
+/// has the extension.
+fn is_extension(uri: &str, extension: &str) -> bool {
+    path(uri).extension().eq(&Some(OsStr::new(extension)))
+}
+
+/// The function `names` returns the completion items of the names.
+fn names<T: ::std::fmt::Debug>(names: &[T], kind: u64) -> Vec<Value> {
+    names.iter()
+         .map(|name| json!({ "label": format!("{:?}", name), "kind": kind }))
+         .collect::<Vec<Value>>()
+}
+
+/// The function `owners` returns the description of a glyph
+/// with its owners.
+fn owners(map: &GlyphMap, glyph: char) -> String {
+    format!("`{}` U+{:X}: {}",
+            glyph,
+            glyph as u32,
+            map.get_owners(glyph)
+               .map(|owners|
+                    owners.iter()
+                          .map(|&(sheet, Tuple { part, emotion })|
+                               format!("{} {:?}:{:?}", sheet, part, emotion))
+                          .collect::<Vec<String>>()
+                          .join(", "))
+               .unwrap_or_else(|| "without owner".to_string()))
+}
+
+/// The function `read_message` returns the next message of the input,
+/// or None at the end of the input.
+fn read_message<R: BufRead>(input: &mut R) -> io::Result<Option<String>> {
+    let mut length: Option<usize> = None;
+    let mut header: String = String::new();
+
+    loop {
+        header.clear();
+        if try!(input.read_line(&mut header)) == 0 {
+            return Ok(None);
+        }
+        match header.trim() {
+            "" if length.is_some() => break,
+            "" => {},
+            header => {
+                if let [name, value] = *header.splitn(2, ':').collect::<Vec<&str>>().as_slice() {
+                    if name.trim().to_lowercase().eq("content-length") {
+                        length = value.trim().parse::<usize>().ok();
+                    }
+                }
+            },
+        }
+    }
+    let mut body: Vec<u8> = vec![0; length.unwrap_or_default()];
+
+    try!(input.read_exact(&mut body));
+    Ok(Some(String::from_utf8_lossy(&body).into_owned()))
+}
+
+/// The function `write_message` writes a message with its header.
+fn write_message<W: Write>(output: &mut W, message: &Value) -> io::Result<()> {
+    let body: String = message.to_string();
+
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)
+        .and(output.flush())
+}
+
+/// The struct `Server` is a language server of the texel and sprite
+/// files of a root.
+#[derive(Debug)]
+pub struct Server {
+    root: PathBuf,
+    graphic: Graphic,
+    /// The text of the open documents by uri.
+    documents: HashMap<String, String>,
+    exit: bool,
+}
+
+impl Server {
+    /// The constructor `new` returns the server of the root, with the
+    /// texels which the loader accepts.
+    pub fn new(root: &Path) -> Self {
+        Server {
+            root: root.to_path_buf(),
+            graphic: Server::graphic(root),
+            documents: HashMap::new(),
+            exit: false,
+        }
+    }
+
+    /// The function `graphic` returns a Graphic with the texel files
+    /// of the root which the loader accepts, the sprite files are
+    /// only linted so a refused sprite can't empty the Graphic.
+    fn graphic(root: &Path) -> Graphic {
+        let mut graphic: Graphic = Graphic::default();
+
+        fs::read_dir(root.join(SPEC_SUBD_NCT)).ok()
+           .into_iter()
+           .flat_map(|entries| entries.filter_map(|entry| entry.ok()))
+           .map(|entry| entry.path())
+           .filter(|path| path.extension().eq(&Some(OsStr::new(SPEC_EXT_NCT))))
+           .all(|path| {
+               graphic.insert_from_texelfile(path).ok();
+               true
+           });
+        graphic
+    }
+
+    /// The method `diagnostics` returns the notification of the lints
+    /// of the document.
+    fn diagnostics(&self, uri: &str) -> Value {
+        let text: &str = self.documents.get(uri).map_or("", |text| text.as_str());
+        let reports: Vec<Report> = if is_extension(uri, SPEC_EXT_NCS) {
+            lint::lint_sprite(&self.graphic, text.as_bytes()).unwrap_or_default()
+        } else if is_extension(uri, SPEC_EXT_NCT) {
+            lint::lint_texel(text.as_bytes()).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": {
+                "uri": uri,
+                "diagnostics": reports.iter().map(|report| json!({
+                    "range": {
+                        "start": { "line": report.line - 1, "character": 0 },
+                        "end": {
+                            "line": report.line - 1,
+                            "character": text.lines()
+                                             .nth(report.line - 1)
+                                             .map_or(0, |line| line.chars().count()),
+                        },
+                    },
+                    "severity": match report.lint {
+                        Lint::ShortGlyph(..) | Lint::DroppedDraw(_) => 2,
+                        _ => 1,
+                    },
+                    "source": "editeur",
+                    "message": report.lint.to_string(),
+                })).collect::<Vec<Value>>(),
+            },
+        })
+    }
+
+    /// The method `completion` returns the names which can be typed
+    /// at the position of the document.
+    fn completion(&self, uri: &str, line: usize, character: usize) -> Vec<Value> {
+        let text: &str = self.documents.get(uri).map_or("", |text| text.as_str());
+
+        if is_extension(uri, SPEC_EXT_NCS) {
+            match word_at(text, line, character).1 % SPEC_DRAW_WORD {
+                0 => names(Sheet::list(), 7),
+                1 => Vec::new(),
+                index if index % 2 == 0 => names(Part::list(), 12),
+                _ => names(Emotion::list(), 20),
+            }
+        } else {
+            let before: String = text.lines()
+                                     .nth(line)
+                                     .unwrap_or_default()
+                                     .chars()
+                                     .take(character)
+                                     .collect::<String>();
+
+            if before.contains('[') {
+                names(Sheet::list(), 7)
+            } else if before.contains(':') {
+                names(Emotion::list(), 20)
+            } else if before.contains('(') {
+                Vec::new()
+            } else {
+                names(Part::list(), 12)
+            }
+        }
+    }
+
+    /// The method `hover` returns the glyphs and their owners of the pair
+    /// of a sprite, or the owners of the glyph of a texel, at the position.
+    fn hover(&self, uri: &str, line: usize, character: usize) -> Option<String> {
+        let text: &str = self.documents.get(uri).map_or("", |text| text.as_str());
+        let map: GlyphMap = GlyphMap::new(&self.graphic);
+
+        if is_extension(uri, SPEC_EXT_NCS) {
+            pair_at(text, line, character).map(|(sheet, tuple, cell)|
+                match self.graphic.get_texel(&sheet, &tuple) {
+                    None => format!("no texel `{:?}:{:?}` for the posture `{}`",
+                                    tuple.part, tuple.emotion, sheet),
+                    Some(texels) => format!("`{:?}:{:?}` of `{}`{}, glyph {} of {}\n\n{}",
+                                            tuple.part, tuple.emotion, sheet,
+                                            self.graphic.get_texel_origin(&sheet, &tuple)
+                                                .and_then(|origin|
+                                                     if origin.eq(&sheet) {
+                                                         None
+                                                     } else {
+                                                         Some(format!(" inherited from `{}`", origin))
+                                                     })
+                                                .unwrap_or_default(),
+                                            cell + 1, texels.len(),
+                                            texels.get(cell)
+                                                  .map(|texel| owners(&map, texel.get_glyph()))
+                                                  .unwrap_or_else(||
+                                                       "without glyph for this cell".to_string())),
+                })
+        } else {
+            text.lines()
+                .nth(line)
+                .and_then(|content| content.chars().nth(character))
+                .and_then(|glyph|
+                     if let SPEC_GLYPH_MIN...SPEC_GLYPH_MAX = glyph {
+                         Some(owners(&map, glyph))
+                     } else {
+                         None
+                     })
+        }
+    }
+
+    /// The method `definition` returns the location of the texel line
+    /// of the pair of a sprite at the position.
+    fn definition(&self, uri: &str, line: usize, character: usize) -> Option<Value> {
+        let text: &str = self.documents.get(uri).map_or("", |text| text.as_str());
+
+        pair_at(text, line, character).and_then(|(sheet, tuple, _)| {
+            let sheet: Sheet = self.graphic.get_texel_origin(&sheet, &tuple).unwrap_or(sheet);
+            let (part, emotion): (String, String) = (format!("{:?}", tuple.part),
+                                                     format!("{:?}", tuple.emotion));
+
+            fs::read_dir(self.root.join(SPEC_SUBD_NCT)).ok()
+               .into_iter()
+               .flat_map(|entries| entries.filter_map(|entry| entry.ok()))
+               .map(|entry| entry.path())
+               .filter(|path| path.extension().eq(&Some(OsStr::new(SPEC_EXT_NCT))))
+               .filter_map(|path| {
+                   let mut buffer: String = String::new();
+
+                   fs::File::open(&path).and_then(|mut file| file.read_to_string(&mut buffer))
+                                        .ok()
+                                        .and_then(|_|
+                       buffer.lines()
+                             .map(grammar::strip)
+                             .position(|content|
+                                  content.find(':').map_or(false, |position| {
+                                      let (head, tail) = content.split_at(position);
+                                      let words: Vec<&str> = tail.split(|c| ":[,] ".contains(c))
+                                                                 .filter(|word| word.is_empty().not())
+                                                                 .collect::<Vec<&str>>();
+
+                                      head.split('(').next().map(str::trim).eq(&Some(part.as_str())) &&
+                                      words.first().eq(&Some(&emotion.as_str())) &&
+                                      words.iter().skip(1).any(|word| word.eq(&sheet.get_name()))
+                                  }))
+                             .map(|line| (path.clone(), line)))
+               })
+               .next()
+               .map(|(path, line)| json!({
+                   "uri": format!("{}{}", SPEC_SCHEME, path.display()),
+                   "range": {
+                       "start": { "line": line, "character": 0 },
+                       "end": { "line": line, "character": 0 },
+                   },
+               }))
+        })
+    }
+
+    /// The method `handle` returns the messages to send for a message
+    /// of the client.
+    fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let id: Option<&Value> = message.get("id");
+        let params: &Value = &message["params"];
+        let uri: String = params["textDocument"]["uri"].as_str().unwrap_or_default().to_string();
+        let (line, character): (usize, usize) = (
+            params["position"]["line"].as_u64().unwrap_or_default() as usize,
+            params["position"]["character"].as_u64().unwrap_or_default() as usize,
+        );
+        let result: Option<Value> = match message["method"].as_str().unwrap_or_default() {
+            "initialize" => Some(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "completionProvider": { "triggerCharacters": [":", "["] },
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                },
+            })),
+            "shutdown" => Some(Value::Null),
+            "exit" => {
+                self.exit = true;
+                return Vec::new();
+            },
+            "textDocument/didOpen" => {
+                self.documents.insert(uri.clone(),
+                                      params["textDocument"]["text"].as_str()
+                                                                    .unwrap_or_default()
+                                                                    .to_string());
+                return vec![self.diagnostics(&uri)];
+            },
+            "textDocument/didChange" => {
+                if let Some(text) = params["contentChanges"].as_array()
+                                                            .and_then(|changes| changes.last())
+                                                            .and_then(|change| change["text"].as_str()) {
+                    self.documents.insert(uri.clone(), text.to_string());
+                }
+                return vec![self.diagnostics(&uri)];
+            },
+            "textDocument/didSave" => {
+                self.graphic = Server::graphic(&self.root);
+                return self.documents.keys()
+                                     .map(|uri| self.diagnostics(uri))
+                                     .collect::<Vec<Value>>();
+            },
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![self.diagnostics(&uri)];
+            },
+            "textDocument/completion" => {
+                Some(Value::Array(self.completion(&uri, line, character)))
+            },
+            "textDocument/hover" => {
+                Some(self.hover(&uri, line, character)
+                         .map_or(Value::Null, |hover| json!({
+                             "contents": { "kind": "markdown", "value": hover },
+                         })))
+            },
+            "textDocument/definition" => {
+                Some(self.definition(&uri, line, character).unwrap_or(Value::Null))
+            },
+            _ => None,
+        };
+
+        match (id, result) {
+            (None, _) => Vec::new(),
+            (Some(id), Some(result)) => vec![json!({ "jsonrpc": "2.0", "id": id, "result": result })],
+            (Some(id), None) => vec![json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": SPEC_METHOD_NOT_FOUND, "message": "unknown method" },
+            })],
+        }
+    }
+
+    /// The method `run` answers the messages of the input until
+    /// the exit notification or the end of the input.
+    pub fn run<R: BufRead, W: Write>(&mut self, mut input: R, output: &mut W) -> io::Result<()> {
+        while self.exit.not() {
+            match try!(read_message(&mut input)) {
+                None => break,
+                Some(body) => {
+                    let answers: Vec<Value> = match serde_json::from_str::<Value>(&body) {
+                        Err(why) => vec![json!({
+                            "jsonrpc": "2.0",
+                            "id": Value::Null,
+                            "error": { "code": SPEC_PARSE_ERROR, "message": why.to_string() },
+                        })],
+                        Ok(message) => self.handle(&message),
+                    };
+
+                    try!(answers.iter()
+                                .map(|answer| write_message(output, answer))
+                                .find(|answer| answer.is_err())
+                                .unwrap_or_else(|| Ok(())));
+                },
+            }
+        }
+        Ok(())
+    }
+}
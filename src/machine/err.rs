@@ -0,0 +1,66 @@
+use std::error::Error;
+use std::fmt;
+
+use super::super::sheet::SheetError;
+
+pub type Result<T> = ::std::result::Result<T, MachineError>;
+
+
+/// The enum `MachineError` defines the possible errors
+/// from constructor Machine.
+#[derive(Clone, Debug)]
+pub enum MachineError {
+    /// Unvalid state or transition syntax.
+    SyntaxMachine(String),
+    /// The transition names an undeclared state.
+    UnknownState(String),
+    /// The machine hasn't state.
+    Empty,
+    /// The sprite of the state can't be found.
+    FoundSprite(String),
+    /// The Sheet interface has meet an error.
+    Sheet(SheetError),
+}
+
+impl fmt::Display for MachineError {
+    /// The function `fmt` formats the value using
+    /// the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MachineError::SyntaxMachine(ref line) => {
+                write!(f, "the machine line `{}` is malformed", line)
+            },
+            MachineError::UnknownState(ref name) => {
+                write!(f, "the state `{}` isn't declared", name)
+            },
+            MachineError::Empty => write!(f, "the machine hasn't state"),
+            MachineError::FoundSprite(ref name) => {
+                write!(f, "can't find the sprite `{}` of the state", name)
+            },
+            MachineError::Sheet(_) => write!(f, "the posture of a state is invalid"),
+        }
+    }
+}
+
+impl Error for MachineError {
+    /// The function `description` returns a short description of
+    /// the error.
+    fn description(&self) -> &str {
+        match *self {
+            MachineError::SyntaxMachine(ref line) => line,
+            MachineError::UnknownState(ref name) => name,
+            MachineError::Empty => "The machine hasn't state.",
+            MachineError::FoundSprite(ref name) => name,
+            MachineError::Sheet(_) => "The Sheet interface has meet an error.",
+        }
+    }
+
+    /// The function `source` returns the lower-level source of
+    /// this error if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            MachineError::Sheet(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
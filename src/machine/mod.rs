@@ -0,0 +1,441 @@
+mod err;
+
+use std::fmt;
+use std::io::Read;
+use std::ops::Not;
+use std::str::FromStr;
+
+pub use self::err::{MachineError, Result};
+
+use super::{Graphic, GraphicError};
+use super::grammar;
+use super::sheet::Sheet;
+use super::sprite::draw::Draw;
+use super::player::Player;
+use ::time;
+
+/// The default weight of a transition.
+pub const SPEC_WEIGHT_DEFAULT: u32 = 1;
+
+/// The struct `State` is a posture with an expression if any,
+/// like `Smug: SeizaNormal Smug`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct State {
+    name: String,
+    sheet: Sheet,
+    expression: Option<String>,
+}
+
+impl State {
+    /// The constructor `new` returns a named state.
+    pub fn new(name: &str, sheet: Sheet, expression: Option<&str>) -> Self {
+        State {
+            name: name.to_string(),
+            sheet: sheet,
+            expression: expression.map(|expression| expression.to_string()),
+        }
+    }
+
+    /// The accessor method `get_name` returns the name.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The accessor method `get_sheet` returns the posture.
+    pub fn get_sheet(&self) -> &Sheet {
+        &self.sheet
+    }
+
+    /// The accessor method `get_expression` returns the name of expression.
+    pub fn get_expression(&self) -> Option<&str> {
+        self.expression.as_ref().map(|expression| expression.as_str())
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.expression {
+            None => write!(f, "{}: {}", self.name, self.sheet),
+            Some(ref expression) => write!(f, "{}: {} {}", self.name, self.sheet, expression),
+        }
+    }
+}
+
+/// The enum `Trigger` defines when a transition is taken.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Trigger {
+    /// On the named event sent by the host.
+    Event(String),
+    /// After the milliseconds spent in the state.
+    Timeout(i64),
+    /// After the milliseconds without event.
+    Idle(i64),
+}
+
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Trigger::Event(ref event) => write!(f, "on {}", event),
+            Trigger::Timeout(ms) => write!(f, "after {}", ms),
+            Trigger::Idle(ms) => write!(f, "idle {}", ms),
+        }
+    }
+}
+
+/// The struct `Edge` is a weighted transition between two states,
+/// like `Idle -> Stretch: idle 10000 2`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Edge {
+    from: String,
+    to: String,
+    trigger: Trigger,
+    weight: u32,
+}
+
+impl Edge {
+    /// The constructor `new` returns a transition between two states.
+    pub fn new(from: &str, to: &str, trigger: Trigger, weight: u32) -> Self {
+        Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            trigger: trigger,
+            weight: weight,
+        }
+    }
+
+    /// The accessor method `get_from` returns the name of the state left.
+    pub fn get_from(&self) -> &str {
+        &self.from
+    }
+
+    /// The accessor method `get_to` returns the name of the state reached.
+    pub fn get_to(&self) -> &str {
+        &self.to
+    }
+
+    /// The accessor method `get_trigger` returns the trigger.
+    pub fn get_trigger(&self) -> &Trigger {
+        &self.trigger
+    }
+
+    /// The accessor method `get_weight` returns the weight.
+    pub fn get_weight(&self) -> u32 {
+        self.weight
+    }
+}
+
+impl fmt::Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} -> {}: {} {}", self.from, self.to, self.trigger, self.weight)
+    }
+}
+
+/// The enum `Line` is a line of machine file.
+#[derive(Clone, Debug)]
+enum Line {
+    State(State),
+    Edge(Edge),
+}
+
+impl FromStr for Line {
+    type Err = MachineError;
+
+    /// The function `from_str` parses a line of machine file.
+    fn from_str(line: &str) -> Result<Self> {
+        let spaced: String = line.replace("->", " -> ");
+        let words: Vec<&str> = spaced.split(|c| " :".contains(c))
+                                     .filter(|word| word.is_empty().not())
+                                     .collect::<Vec<&str>>();
+        let syntax = || MachineError::SyntaxMachine(line.trim().to_string());
+        let edge = |from: &str, to: &str, trigger: &str, value: &str, weight: Option<&str>| {
+            let weight: Option<u32> = weight.map_or(Some(SPEC_WEIGHT_DEFAULT),
+                                                    |weight| weight.parse::<u32>().ok());
+
+            match (trigger, value.parse::<i64>(), weight) {
+                (_, _, None) => None,
+                ("on", _, Some(weight)) => {
+                    Some(Edge::new(from, to, Trigger::Event(value.to_string()), weight))
+                },
+                ("after", Ok(ms), Some(weight)) => {
+                    Some(Edge::new(from, to, Trigger::Timeout(ms), weight))
+                },
+                ("idle", Ok(ms), Some(weight)) => {
+                    Some(Edge::new(from, to, Trigger::Idle(ms), weight))
+                },
+                _ => None,
+            }.map(Line::Edge)
+             .ok_or_else(syntax)
+        };
+
+        match words.as_slice() {
+            &[from, "->", to, trigger, value] => edge(from, to, trigger, value, None),
+            &[from, "->", to, trigger, value, weight] => {
+                edge(from, to, trigger, value, Some(weight))
+            },
+            &[name, sheet] => {
+                Sheet::new(sheet).map(|sheet| Line::State(State::new(name, sheet, None)))
+                                 .map_err(MachineError::Sheet)
+            },
+            &[name, sheet, expression] => {
+                Sheet::new(sheet).map(|sheet|
+                                          Line::State(State::new(name, sheet, Some(expression))))
+                                 .map_err(MachineError::Sheet)
+            },
+            _ => Err(syntax()),
+        }
+    }
+}
+
+/// The struct `Machine` is a state machine of the character, the first
+/// state is the initial state.
+#[derive(Clone, Debug)]
+pub struct Machine {
+    states: Vec<State>,
+    edges: Vec<Edge>,
+    /// The index of the current state.
+    current: usize,
+    /// The time spent in the current state.
+    state_time: time::Duration,
+    /// The time spent without event.
+    idle_time: time::Duration,
+    /// The seed of the weighted choice.
+    seed: u64,
+}
+
+impl Machine {
+    /// The constructor `new` returns the machine of the states and
+    /// the transitions between declared states.
+    pub fn new(states: Vec<State>, edges: Vec<Edge>) -> Result<Self> {
+        if states.is_empty() {
+            Err(MachineError::Empty)
+        } else if let Some(name) = edges.iter()
+                                        .flat_map(|edge| vec![edge.get_from(), edge.get_to()])
+                                        .find(|name|
+                                             states.iter()
+                                                   .any(|state| state.get_name().eq(*name))
+                                                   .not()) {
+            Err(MachineError::UnknownState(name.to_string()))
+        } else {
+            Ok(Machine {
+                states: states,
+                edges: edges,
+                current: 0,
+                state_time: time::Duration::zero(),
+                idle_time: time::Duration::zero(),
+                seed: time::precise_time_ns() | 1,
+            })
+        }
+    }
+
+    /// The accessor method `get_state` returns the current state.
+    pub fn get_state(&self) -> &State {
+        &self.states[self.current]
+    }
+
+    /// The accessor method `get_states` returns the states.
+    pub fn get_states(&self) -> &Vec<State> {
+        &self.states
+    }
+
+    /// The accessor method `get_edges` returns the transitions.
+    pub fn get_edges(&self) -> &Vec<Edge> {
+        &self.edges
+    }
+
+    /// The mutator method `set_seed` changes the seed of the weighted choice.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed | 1;
+    }
+
+    /// The function `random` returns the next number of the xorshift
+    /// generator.
+    fn random(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+
+    /// The method `take` chooses by weight a transition from the current
+    /// state among the triggered ones and enters its state, the time
+    /// without event goes on but restarts after an idle transition.
+    fn take<F: Fn(&Trigger) -> bool>(&mut self, triggered: F) -> Option<&State> {
+        let edges: Vec<(u32, String, bool)> =
+            self.edges.iter()
+                      .filter(|edge|
+                           edge.get_from().eq(self.get_state().get_name()) &&
+                           triggered(edge.get_trigger()))
+                      .map(|edge| {
+                           let idle: bool = match *edge.get_trigger() {
+                               Trigger::Idle(_) => true,
+                               _ => false,
+                           };
+
+                           (edge.get_weight(), edge.get_to().to_string(), idle)
+                      })
+                      .collect::<Vec<(u32, String, bool)>>();
+        let total: u64 = edges.iter().map(|&(weight, ..)| weight as u64).sum::<u64>();
+
+        if total == 0 {
+            None
+        } else {
+            let mut choice: u64 = self.random() % total;
+
+            edges.into_iter()
+                 .find(|&(weight, ..)|
+                       if choice < weight as u64 {
+                           true
+                       } else {
+                           choice -= weight as u64;
+                           false
+                       })
+                 .and_then(|(_, to, idle)|
+                      self.states.iter()
+                                 .position(|state| state.get_name().eq(&to))
+                                 .map(|index| (index, idle)))
+                 .map(move |(index, idle)| {
+                     self.current = index;
+                     self.state_time = time::Duration::zero();
+                     if idle {
+                         self.idle_time = time::Duration::zero();
+                     }
+                     &self.states[index]
+                 })
+        }
+    }
+
+    /// The method `send` receives the named event and returns the new
+    /// state if a transition is taken.
+    pub fn send(&mut self, event: &str) -> Option<&State> {
+        self.idle_time = time::Duration::zero();
+        self.take(|trigger| trigger.eq(&Trigger::Event(event.to_string())))
+    }
+
+    /// The method `tick` spends the time and returns the new state if
+    /// a timeout or an idle transition is taken.
+    pub fn tick(&mut self, elapsed: time::Duration) -> Option<&State> {
+        let (state_time, idle_time): (i64, i64) = (
+            (self.state_time + elapsed).num_milliseconds(),
+            (self.idle_time + elapsed).num_milliseconds(),
+        );
+
+        self.state_time = self.state_time + elapsed;
+        self.idle_time = self.idle_time + elapsed;
+        self.take(|trigger|
+                  match *trigger {
+                      Trigger::Timeout(ms) => ms <= state_time,
+                      Trigger::Idle(ms) => ms <= idle_time,
+                      Trigger::Event(_) => false,
+                  })
+    }
+}
+
+impl fmt::Display for Machine {
+    /// The function `fmt` formats the machine as a machine file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.states.iter()
+                   .map(|state| state.to_string())
+                   .chain(self.edges.iter().map(|edge| edge.to_string()))
+                   .map(|line| writeln!(f, "{}", line))
+                   .find(|line| line.is_err())
+                   .unwrap_or_else(|| Ok(()))
+    }
+}
+
+/// The function `read_machine` returns the machine of a machine file
+/// from a readable source.
+pub fn read_machine<R: Read>(source: R) -> ::Result<Machine> {
+    grammar::definitions(source, GraphicError::Machine)
+        .and_then(|lines: Vec<Line>| {
+            let (mut states, mut edges): (Vec<State>, Vec<Edge>) = (Vec::new(), Vec::new());
+
+            lines.into_iter()
+                 .all(|line| {
+                     match line {
+                         Line::State(state) => states.push(state),
+                         Line::Edge(edge) => edges.push(edge),
+                     }
+                     true
+                 });
+            Machine::new(states, edges).map_err(GraphicError::Machine)
+        })
+}
+
+/// The struct `Driver` drives the player with the state machine, the
+/// host only sends the events and the time spent.
+#[derive(Clone, Debug)]
+pub struct Driver {
+    machine: Machine,
+    player: Player,
+}
+
+impl Driver {
+    /// The constructor `new` returns the driver which plays
+    /// the initial state, or the error of its missing sprite.
+    pub fn new(graphic: &Graphic, machine: Machine) -> Result<Self> {
+        let mut driver: Driver = Driver {
+            machine: machine,
+            player: Player::new(),
+        };
+
+        driver.play(graphic).and(Ok(driver))
+    }
+
+    /// The function `play` plays the sprite of the current state.
+    fn play(&mut self, graphic: &Graphic) -> Result<()> {
+        let state: &State = self.machine.get_state();
+
+        self.player.play(graphic, state.get_sheet(), state.get_expression())
+                   .ok_or_else(|| MachineError::FoundSprite(state.get_sheet().get_name().to_string()))
+    }
+
+    /// The accessor method `get_machine` returns the state machine.
+    pub fn get_machine(&self) -> &Machine {
+        &self.machine
+    }
+
+    /// The accessor method `get_player` returns the player.
+    pub fn get_player(&self) -> &Player {
+        &self.player
+    }
+
+    /// The accessor method `get_draw` returns the draw played,
+    /// without the effects.
+    pub fn get_draw(&self) -> Option<&Draw> {
+        self.player.get_draw()
+    }
+
+    /// The method `get_frame` returns the draw to show with the
+    /// frames of the effects played.
+    pub fn get_frame(&self, graphic: &Graphic) -> Option<Draw> {
+        self.player.get_frame(graphic)
+    }
+
+    /// The method `add_effect` plays the effect of the name over the
+    /// draws of the states. Returns None if the effect is missing.
+    pub fn add_effect(&mut self, graphic: &Graphic, name: &str) -> Option<()> {
+        self.player.add_effect(graphic, name)
+    }
+
+    /// The method `send` receives the named event and returns true
+    /// if the state has changed, or the error of the missing sprite
+    /// of the new state.
+    pub fn send(&mut self, graphic: &Graphic, event: &str) -> Result<bool> {
+        if self.machine.send(event).is_some() {
+            self.play(graphic).and(Ok(true))
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// The method `tick` spends the time on the machine and the player,
+    /// returns true if the draw to show has changed, or the error of the
+    /// missing sprite of the new state.
+    pub fn tick(&mut self, graphic: &Graphic, elapsed: time::Duration) -> Result<bool> {
+        if self.machine.tick(elapsed).is_some() {
+            self.play(graphic).and(Ok(true))
+        } else {
+            Ok(self.player.tick(elapsed))
+        }
+    }
+}
@@ -31,14 +31,54 @@
 #[cfg(feature = "clipboard")]
 extern crate clipboard;
 extern crate termion;
+extern crate time;
+#[macro_use]
+extern crate serde_json;
 extern crate editeur;
 
 mod interface;
+mod command;
+mod server;
+mod lsp;
 
-use std::io::Write;
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
+
+use editeur::{Fallback, report};
 
 fn main() {
-    let mut editeur: interface::Editeur = interface::Editeur::new().unwrap();
+    match command::Command::new(env::args().skip(1)) {
+        Ok((root, fallback, None)) => editor(root, fallback),
+        Ok((_, _, Some(command))) => {
+            let color: bool = termion::is_tty(&io::stdout());
+
+            if let Err(why) = command.run(&mut io::stdout(), color) {
+                let _ = writeln!(io::stderr(), "editeur: {}", report(&why));
+                process::exit(1);
+            }
+        },
+        Err(why) => {
+            let _ = writeln!(io::stderr(), "editeur: {}\n\n{}",
+                             report(&why),
+                             command::SPEC_USAGE);
+            process::exit(2);
+        },
+    }
+}
+
+/// The function `editor` starts the interactive editor with
+/// the rendering of the glyphs or else the probed one, or reports
+/// why it can't once the terminal has left the raw mode.
+fn editor(root: PathBuf, fallback: Option<Fallback>) {
+    let mut editeur: interface::Editeur = match interface::Editeur::new(root, fallback) {
+        Ok(editeur) => editeur,
+        Err(why) => {
+            let _ = writeln!(io::stderr(), "editeur: {}", report(&why));
+            process::exit(1);
+        },
+    };
 
     loop {
         print!("{}", editeur);
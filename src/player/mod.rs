@@ -0,0 +1,160 @@
+use std::collections::VecDeque;
+
+use super::Graphic;
+use super::effect::{self, Effect};
+use super::sheet::Sheet;
+use super::sprite::{Sprite, SPEC_MAX_DRAW};
+use super::sprite::draw::Draw;
+use ::time;
+
+/// The struct `Player` plays the draws of a sprite in a loop, after the
+/// draws of the transition from the previous sprite, with the effects
+/// over the draws on their own rhythm.
+#[derive(Clone, Debug)]
+pub struct Player {
+    /// The posture of the sprite played.
+    sheet: Option<Sheet>,
+    /// The draws of transition played once.
+    queue: VecDeque<Draw>,
+    /// The draws of the sprite played in a loop.
+    draws: Vec<Draw>,
+    /// The index of the current draw of the sprite.
+    index: usize,
+    /// The time spent on the current draw.
+    elapsed: time::Duration,
+    /// The effects played with their time spent.
+    effects: Vec<(Effect, time::Duration)>,
+}
+
+impl Player {
+    /// The constructor `new` returns a player without sprite.
+    pub fn new() -> Self {
+        Player::default()
+    }
+
+    /// The method `play` plays the sprite of the Sheet key, with the
+    /// expression of the name if any, after the draws of the transition
+    /// from the sprite played. Returns None if the sprite is missing.
+    pub fn play(&mut self, graphic: &Graphic, sheet: &Sheet, expression: Option<&str>
+    ) -> Option<()> {
+        graphic.get_sprite(sheet).cloned().map(|mut sprite: Sprite| {
+            if let Some(expression) = expression.and_then(|name| graphic.get_expression(name)) {
+                sprite.explicite_emotion(&expression.get_change(0..SPEC_MAX_DRAW));
+            }
+            if let Some(current) = self.sheet {
+                if current.ne(sheet) {
+                    self.queue.extend(graphic.get_transition_draws(&current, sheet)
+                                             .unwrap_or_default());
+                }
+            }
+            self.sheet = Some(*sheet);
+            self.draws = sprite.into_iter().cloned().collect::<Vec<Draw>>();
+            self.index = 0;
+            self.elapsed = time::Duration::zero();
+        })
+    }
+
+    /// The method `add_effect` plays the effect of the name over the
+    /// draws from its first frame, again if it's already played.
+    /// Returns None if the effect is missing.
+    pub fn add_effect(&mut self, graphic: &Graphic, name: &str) -> Option<()> {
+        graphic.get_effect(name).cloned().map(|effect: Effect| {
+            self.effects.retain(|&(ref played, _)| played.get_name().ne(name));
+            self.effects.push((effect, time::Duration::zero()));
+        })
+    }
+
+    /// The method `remove_effect` stops the effect of the name.
+    /// Returns None if the effect isn't played.
+    pub fn remove_effect(&mut self, name: &str) -> Option<()> {
+        self.effects.iter()
+                    .position(|&(ref played, _)| played.get_name().eq(name))
+                    .map(|index| { self.effects.remove(index); })
+    }
+
+    /// The accessor method `get_effect_list` returns the names
+    /// of the effects played.
+    pub fn get_effect_list(&self) -> Vec<&str> {
+        self.effects.iter()
+                    .map(|&(ref effect, _)| effect.get_name())
+                    .collect::<Vec<&str>>()
+    }
+
+    /// The method `get_frame` returns the current draw with the current
+    /// frame of each effect, with the texels of its posture.
+    pub fn get_frame(&self, graphic: &Graphic) -> Option<Draw> {
+        self.get_draw().map(|draw|
+            if self.effects.is_empty() {
+                *draw
+            } else {
+                effect::compose_all(draw,
+                                    &self.effects.iter()
+                                                 .map(|&(ref effect, elapsed)|
+                                                      (effect, elapsed.num_milliseconds()))
+                                                 .collect::<Vec<(&Effect, i64)>>(),
+                                    &graphic.get_texel_resolved(draw.get_posture())
+                                            .unwrap_or_default())
+            })
+    }
+
+    /// The accessor method `get_sheet` returns the posture of the sprite played.
+    pub fn get_sheet(&self) -> Option<&Sheet> {
+        self.sheet.as_ref()
+    }
+
+    /// The accessor method `get_draw` returns the current draw.
+    pub fn get_draw(&self) -> Option<&Draw> {
+        self.queue.front().or_else(|| self.draws.get(self.index))
+    }
+
+    /// The method `tick` spends the time on the draws and the effects,
+    /// returns true if the current draw or the frame of an effect has
+    /// changed, a draw is at least played once.
+    pub fn tick(&mut self, elapsed: time::Duration) -> bool {
+        let mut changed: bool =
+            self.effects.iter_mut()
+                        .fold(false, |changed, &mut (ref effect, ref mut spent)| {
+                            let index: usize = effect.get_index(spent.num_milliseconds());
+
+                            *spent = *spent + elapsed;
+                            changed || index.ne(&effect.get_index(spent.num_milliseconds()))
+                        });
+        let mut count: usize = self.queue.len() + self.draws.len();
+
+        self.elapsed = self.elapsed + elapsed;
+        while let Some(duration) = self.get_draw()
+                                       .map(|draw| *draw.get_duration())
+                                       .and_then(|duration|
+                                           if duration.le(&self.elapsed) {
+                                               Some(duration)
+                                           } else {
+                                               None
+                                           }) {
+            if count == 0 {
+                self.elapsed = time::Duration::zero();
+                break;
+            }
+            count -= 1;
+            self.elapsed = self.elapsed - duration;
+            if self.queue.pop_front().is_none() {
+                self.index = (self.index + 1) % self.draws.len();
+            }
+            changed = true;
+        }
+        changed
+    }
+}
+
+impl Default for Player {
+    /// The constructor `default` returns a player without sprite.
+    fn default() -> Player {
+        Player {
+            sheet: None,
+            queue: VecDeque::new(),
+            draws: Vec::new(),
+            index: 0,
+            elapsed: time::Duration::zero(),
+            effects: Vec::new(),
+        }
+    }
+}
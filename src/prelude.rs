@@ -1,9 +1,19 @@
 pub use super::{SPEC_ROOT, SPEC_ROOT_DEFAULT, SPEC_MAX_DRAW, SPEC_SUBD_NCT, SPEC_SUBD_NCS, SPEC_SUBD_NCF};
-pub use super::{Graphic, GraphicError};
+pub use super::{SPEC_EXT_NCT, SPEC_EXT_NCS};
+pub use super::{Graphic, GraphicBuilder, GraphicError, Watcher, report};
 pub use super::tuple::Tuple;
 pub use super::sheet::{Sheet, SheetError};
-pub use super::emotion::{Emotion, EmotionError};
+pub use super::emotion::{Emotion, EmotionError, Intensity};
+pub use super::expression::{Expression, ExpressionError};
+pub use super::transition::{Kind, Transition, TransitionError};
+pub use super::template::{Step, Template, TemplateError};
+pub use super::effect::{Effect, EffectError, Frame};
+pub use super::player::Player;
+pub use super::machine::{Driver, Machine, MachineError};
 pub use super::sprite::{Sprite, SpriteError};
 pub use super::sprite::draw::{Draw, DrawError, SPEC_MAX_XY, SPEC_MAX_X, SPEC_MAX_Y};
-pub use super::sprite::texel::{Texel, TexelError};
+pub use super::sprite::draw::{Layer, LayerError};
+pub use super::sprite::texel::{Attributes, Texel, TexelError};
+pub use super::sprite::texel::color::{Color, ColorError};
+pub use super::sprite::texel::fallback::{Fallback, FallbackError};
 pub use super::sprite::texel::part::{Part, PartError};
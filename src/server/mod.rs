@@ -0,0 +1,239 @@
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use editeur::Graphic;
+use editeur::player::Player;
+use editeur::sheet::Sheet;
+use editeur::sprite::draw::Draw;
+use time;
+
+use serde_json::{self, Value};
+
+/// The delay in milliseconds between two frames pushed to a subscriber.
+pub const SPEC_FRAME_DELAY: u64 = 20;
+
+/// The function `frame` returns the draw of the sprite as glyph lines
+/// with the foreground and background colors of each cell.
+fn frame(sheet: &Sheet, draw: &Draw) -> Value {
+    json!({
+        "sheet": sheet.get_name(),
+        "posture": draw.get_posture().get_name(),
+        "duration": draw.get_duration().num_milliseconds(),
+        "lines": draw.get_lines(),
+        "colors": draw.get_colors()
+                      .iter()
+                      .map(|line|
+                           line.iter()
+                               .map(|&(fg, bg)|
+                                    json!([fg.map(|fg| fg.to_string()), bg.map(|bg| bg.to_string())]))
+                               .collect::<Vec<Value>>())
+                      .collect::<Vec<Vec<Value>>>(),
+    })
+}
+
+/// The function `error` returns the answer of a failed request.
+fn error(message: &str) -> Value {
+    json!({ "error": message })
+}
+
+/// The struct `Client` answers the requests of a connection and pushes
+/// the frames of the subscribed animation.
+#[derive(Debug)]
+struct Client {
+    graphic: Arc<Mutex<Graphic>>,
+    stream: UnixStream,
+    /// The subscribed animation with its sprite.
+    player: Option<(Sheet, Player)>,
+    /// The time of the last tick of the animation.
+    tick: Instant,
+}
+
+impl Client {
+    /// The constructor `new` returns the client of a connection.
+    fn new(graphic: Arc<Mutex<Graphic>>, stream: UnixStream) -> Self {
+        Client {
+            graphic: graphic,
+            stream: stream,
+            player: None,
+            tick: Instant::now(),
+        }
+    }
+
+    /// The method `send` writes a message on its line.
+    fn send(&mut self, message: &Value) -> io::Result<()> {
+        writeln!(self.stream, "{}", message)
+    }
+
+    /// The method `answer` returns the answer of a request line.
+    fn answer(&mut self, line: &str) -> Value {
+        let request: Value = match serde_json::from_str::<Value>(line) {
+            Err(why) => return error(&why.to_string()),
+            Ok(request) => request,
+        };
+        let sheet: Option<Sheet> = request["sheet"].as_str()
+                                                   .and_then(|name| Sheet::new(name).ok());
+        let draw: Option<usize> = request["draw"].as_u64().map(|draw| draw as usize);
+        let mut graphic = match self.graphic.lock() {
+            Err(_) => return error("the graphic is poisoned"),
+            Ok(graphic) => graphic,
+        };
+
+        match (request["request"].as_str().unwrap_or_default(), sheet) {
+            ("sheets", _) => json!({
+                "sheets": graphic.get_sprite_list()
+                                 .iter()
+                                 .map(|&(ref sheet, _)| sheet.get_name())
+                                 .collect::<Vec<&str>>(),
+            }),
+            ("expressions", _) => json!({
+                "expressions": graphic.get_expression_list()
+                                      .iter()
+                                      .map(|expression| expression.get_name())
+                                      .collect::<Vec<&str>>(),
+            }),
+            ("draw", Some(sheet)) => {
+                let index: usize = draw.unwrap_or_default();
+
+                graphic.get_sprite(&sheet)
+                       .and_then(|sprite| sprite.into_iter().nth(index))
+                       .map(|draw| json!({ "draw": index, "frame": frame(&sheet, draw) }))
+                       .unwrap_or_else(|| error("can't found the draw"))
+            },
+            ("expression", Some(sheet)) => {
+                match request["name"].as_str()
+                                     .and_then(|name|
+                                          graphic.apply_expression(&sheet, name,
+                                                                   draw.map(|draw| draw..draw + 1))) {
+                    None => error("can't found the sprite or the expression"),
+                    Some(sprite) => json!({
+                        "sheet": sheet.get_name(),
+                        "draws": sprite.into_iter()
+                                       .map(|draw| frame(&sheet, draw))
+                                       .collect::<Vec<Value>>(),
+                    }),
+                }
+            },
+            ("subscribe", Some(sheet)) => {
+                let mut player: Player = self.player.take()
+                                                    .map(|(_, player)| player)
+                                                    .unwrap_or_default();
+
+                let effects: Vec<&str> = request["effects"].as_array()
+                                                           .map(|effects|
+                                                                effects.iter()
+                                                                       .filter_map(Value::as_str)
+                                                                       .collect::<Vec<&str>>())
+                                                           .unwrap_or_default();
+
+                match player.play(&graphic, &sheet, request["expression"].as_str())
+                            .and_then(|()|
+                                 effects.iter()
+                                        .map(|name| player.add_effect(&graphic, name))
+                                        .find(|effect| effect.is_none())
+                                        .unwrap_or(Some(()))) {
+                    None => error("can't found the sprite or the effect"),
+                    Some(()) => {
+                        let answer: Value = player.get_frame(&graphic)
+                                                  .map(|draw| json!({ "frame": frame(&sheet, &draw) }))
+                                                  .unwrap_or_else(|| json!({ "subscribed": sheet.get_name() }));
+
+                        self.player = Some((sheet, player));
+                        self.tick = Instant::now();
+                        answer
+                    },
+                }
+            },
+            ("unsubscribe", _) => {
+                self.player = None;
+                json!({ "unsubscribed": true })
+            },
+            ("draw", None) | ("expression", None) | ("subscribe", None) => {
+                error("can't found the sheet")
+            },
+            (_, _) => error("unknown request"),
+        }
+    }
+
+    /// The method `push` ticks the subscribed animation and returns
+    /// the frame if its draw or the frame of an effect has changed.
+    fn push(&mut self) -> Option<Value> {
+        let elapsed: Duration = self.tick.elapsed();
+        let graphic: &Arc<Mutex<Graphic>> = &self.graphic;
+
+        self.tick = Instant::now();
+        self.player.as_mut().and_then(|&mut (sheet, ref mut player)|
+            if player.tick(time::Duration::milliseconds(elapsed.as_secs() as i64 * 1000 +
+                                                        elapsed.subsec_nanos() as i64 / 1_000_000)) {
+                graphic.lock()
+                       .ok()
+                       .and_then(|graphic| player.get_frame(&graphic))
+                       .map(|draw| json!({ "frame": frame(&sheet, &draw) }))
+            } else {
+                None
+            })
+    }
+
+    /// The method `run` reads the request lines until the connection
+    /// is closed.
+    fn run(mut self) -> io::Result<()> {
+        let mut reader: io::BufReader<UnixStream> = io::BufReader::new(try!(self.stream.try_clone()));
+        let mut line: String = String::new();
+
+        try!(self.stream.set_read_timeout(Some(Duration::from_millis(SPEC_FRAME_DELAY))));
+        loop {
+            match reader.read_line(&mut line) {
+                Ok(0) => return Ok(()),
+                Ok(_) if line.ends_with('\n') => {
+                    let answer: Value = self.answer(line.trim());
+
+                    line.clear();
+                    try!(self.send(&answer));
+                },
+                Ok(_) => {},
+                Err(ref why) if why.kind() == io::ErrorKind::WouldBlock ||
+                                why.kind() == io::ErrorKind::TimedOut => {},
+                Err(why) => return Err(why),
+            }
+            if let Some(frame) = self.push() {
+                try!(self.send(&frame));
+            }
+        }
+    }
+}
+
+/// The function `unlink` removes the socket left by a previous server,
+/// any other file of the path is kept and refused.
+fn unlink(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Err(ref why) if why.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(why) => Err(why),
+        Ok(ref metadata) if metadata.file_type().is_socket() => fs::remove_file(path),
+        Ok(_) => Err(io::Error::new(io::ErrorKind::AlreadyExists,
+                                    format!("`{}` exists and isn't a socket", path.display()))),
+    }
+}
+
+/// The function `serve` listens on the Unix socket and answers each
+/// connection in its thread.
+pub fn serve(graphic: Graphic, path: &Path) -> io::Result<()> {
+    let graphic: Arc<Mutex<Graphic>> = Arc::new(Mutex::new(graphic));
+
+    unlink(path).and_then(|()| UnixListener::bind(path))
+                .and_then(|listener| {
+                    listener.incoming()
+                            .filter_map(|stream| stream.ok())
+                            .all(|stream| {
+                                let graphic: Arc<Mutex<Graphic>> = graphic.clone();
+
+                                thread::spawn(move || Client::new(graphic, stream).run());
+                                true
+                            });
+                    Ok(())
+                })
+}
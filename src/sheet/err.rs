@@ -8,14 +8,17 @@ pub type Result<T> = ::std::result::Result<T, SheetError>;
 /// from constructor Sheet.
 #[derive(Clone, Debug)]
 pub enum SheetError {
-    UnknownSheet,
+    /// The sheet name is unknown.
+    UnknownSheet(String),
 }
 
 impl fmt::Display for SheetError {
     /// The function `fmt` formats the value using
     /// the given formatter.
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SheetError::UnknownSheet(ref name) => write!(f, "the sheet name `{}` is unknown", name),
+        }
     }
 }
 
@@ -24,13 +27,13 @@ impl Error for SheetError {
   /// the error.
   fn description(&self) -> &str {
       match *self {
-         SheetError::UnknownSheet => "The name is unknown.",
+         SheetError::UnknownSheet(_) => "The name is unknown.",
       }
   }
 
-    /// The function `cause` returns the lower-level cause of
+    /// The function `source` returns the lower-level source of
     /// this error if any.
-    fn cause(&self) -> Option<&Error> {
+    fn source(&self) -> Option<&(Error + 'static)> {
         None
     }
 }
@@ -77,6 +77,44 @@ pub enum Sheet {
     SeizaSurprised = 0x64,
 }
 
+/// The list of all Sheets.
+static SPEC_SHEET_LIST: [Sheet; 34] = [
+    Sheet::None,
+    Sheet::BustAngry,
+    Sheet::BustHappy,
+    Sheet::BustLove,
+    Sheet::BustMalicious,
+    Sheet::BustMisunderstanding,
+    Sheet::BustNormal,
+    Sheet::BustPlaying,
+    Sheet::BustShocked,
+    Sheet::BustSleepy,
+    Sheet::BustSpeechless,
+    Sheet::BustSurprised,
+    Sheet::LyingAngry,
+    Sheet::LyingHappy,
+    Sheet::LyingLove,
+    Sheet::LyingMalicious,
+    Sheet::LyingMisunderstanding,
+    Sheet::LyingNormal,
+    Sheet::LyingPlaying,
+    Sheet::LyingShocked,
+    Sheet::LyingSleepy,
+    Sheet::LyingSpeechless,
+    Sheet::LyingSurprised,
+    Sheet::SeizaAngry,
+    Sheet::SeizaHappy,
+    Sheet::SeizaLove,
+    Sheet::SeizaMalicious,
+    Sheet::SeizaMisunderstanding,
+    Sheet::SeizaNormal,
+    Sheet::SeizaPlaying,
+    Sheet::SeizaShocked,
+    Sheet::SeizaSleepy,
+    Sheet::SeizaSpeechless,
+    Sheet::SeizaSurprised,
+];
+
 impl Sheet {
     pub fn new(content: &str) -> Result<Self> {
         match content {
@@ -114,10 +152,15 @@ impl Sheet {
             "SeizaSleepy" => Ok(Sheet::SeizaSleepy),
             "SeizaSpeechless" => Ok(Sheet::SeizaSpeechless),
             "SeizaSurprised" => Ok(Sheet::SeizaSurprised),
-            _ => Err(SheetError::UnknownSheet),
+            name => Err(SheetError::UnknownSheet(name.to_string())),
         }
     }
 
+    /// The function `list` returns all the Sheets.
+    pub fn list() -> &'static [Sheet] {
+        &SPEC_SHEET_LIST
+    }
+
     pub fn get_name(&self) -> &'static str {
         match *self {
             Sheet::None => "None",
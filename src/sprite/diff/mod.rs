@@ -0,0 +1,160 @@
+use std::fmt;
+
+use super::draw::{Draw, Layer, SPEC_MAX_X};
+use super::{Emotion, Part, Sheet, Sprite};
+
+/// The struct `Cell` is a cell of a layer which changed of part or emotion.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cell {
+    /// The layer of the cell.
+    pub layer: Layer,
+    /// The index of the cell in the draw.
+    pub index: usize,
+    /// The part and emotion of the old draw.
+    pub before: (Part, Emotion),
+    /// The part and emotion of the new draw.
+    pub after: (Part, Emotion),
+}
+
+/// The enum `Change` defines the differences of a draw.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Change {
+    /// The draw is only in the new sprite.
+    Added(usize),
+    /// The draw is only in the old sprite.
+    Removed(usize),
+    /// The draw is in both sprites.
+    Modified {
+        draw: usize,
+        posture: Option<(Sheet, Sheet)>,
+        duration: Option<(i64, i64)>,
+        cells: Vec<Cell>,
+    },
+}
+
+/// The struct `Diff` is the list of changes between two sprites,
+/// the draws are compared by index.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Diff {
+    changes: Vec<Change>,
+}
+
+impl Diff {
+    /// The constructor `new` returns the changes from the old sprite
+    /// to the new sprite.
+    pub fn new(before: &Sprite, after: &Sprite) -> Self {
+        let (before, after): (Vec<&Draw>, Vec<&Draw>) = (
+            before.into_iter().collect::<Vec<&Draw>>(),
+            after.into_iter().collect::<Vec<&Draw>>(),
+        );
+
+        Diff {
+            changes: (0..before.len().max(after.len()))
+                .filter_map(|index|
+                    match (before.get(index), after.get(index)) {
+                        (Some(before), Some(after)) => Diff::draw(index, before, after),
+                        (Some(_), None) => Some(Change::Removed(index)),
+                        (None, Some(_)) => Some(Change::Added(index)),
+                        (None, None) => None,
+                    })
+                .collect::<Vec<Change>>(),
+        }
+    }
+
+    /// The function `draw` returns the changes of a draw if any.
+    fn draw(index: usize, before: &Draw, after: &Draw) -> Option<Change> {
+        let (posture, duration): (Option<(Sheet, Sheet)>, Option<(i64, i64)>) = (
+            Some((*before.get_posture(), *after.get_posture()))
+                .and_then(|(before, after)| if before.eq(&after) { None } else { Some((before, after)) }),
+            Some((before.get_duration().num_milliseconds(),
+                  after.get_duration().num_milliseconds()))
+                .and_then(|(before, after)| if before.eq(&after) { None } else { Some((before, after)) }),
+        );
+        let cells: Vec<Cell> =
+            Layer::list().iter()
+                         .flat_map(|layer|
+                              before.get_layer_cells(layer)
+                                    .iter()
+                                    .zip(after.get_layer_cells(layer).iter())
+                                    .enumerate()
+                                    .map(move |(index, (&(emotion_before, ref texel_before),
+                                                        &(emotion_after, ref texel_after)))|
+                                         Cell {
+                                             layer: *layer,
+                                             index: index,
+                                             before: (*texel_before.get_part(), emotion_before),
+                                             after: (*texel_after.get_part(), emotion_after),
+                                         }))
+                         .filter(|cell| cell.before.ne(&cell.after))
+                         .collect::<Vec<Cell>>();
+
+        if posture.is_none() && duration.is_none() && cells.is_empty() {
+            None
+        } else {
+            Some(Change::Modified {
+                draw: index,
+                posture: posture,
+                duration: duration,
+                cells: cells,
+            })
+        }
+    }
+
+    /// The accessor method `get_changes` returns the list of changes.
+    pub fn get_changes(&self) -> &Vec<Change> {
+        &self.changes
+    }
+
+    /// The accessor method `is_empty` returns true if the sprites
+    /// are the same.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The accessor method `get_cells` returns the changed cells
+    /// of the draw.
+    pub fn get_cells(&self, index: usize) -> Option<&Vec<Cell>> {
+        self.changes.iter()
+                    .filter_map(|change| match *change {
+                        Change::Modified { draw, ref cells, .. } if draw.eq(&index) => Some(cells),
+                        _ => None,
+                    })
+                    .next()
+    }
+}
+
+impl fmt::Display for Diff {
+    /// The function `fmt` formats a line by change.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.changes.iter()
+                    .map(|change| match *change {
+                        Change::Added(draw) => writeln!(f, "draw {}: added", draw),
+                        Change::Removed(draw) => writeln!(f, "draw {}: removed", draw),
+                        Change::Modified { draw, posture, duration, ref cells } => {
+                            posture.map_or(Ok(()), |(before, after)|
+                                           writeln!(f, "draw {}: posture {} -> {}",
+                                                    draw, before, after))
+                                   .and(duration.map_or(Ok(()), |(before, after)|
+                                                        writeln!(f, "draw {}: duration {} -> {}",
+                                                                 draw, before, after)))
+                                   .and(cells.iter()
+                                             .map(|&Cell { layer, index, before, after }|
+                                                  writeln!(f, "draw {}: {}cell {} ({}, {}) {:?}:{:?} -> {:?}:{:?}",
+                                                           draw,
+                                                           if layer.eq(&Layer::Body) {
+                                                               String::new()
+                                                           } else {
+                                                               format!("{} ", layer)
+                                                           },
+                                                           index,
+                                                           index % SPEC_MAX_X, index / SPEC_MAX_X,
+                                                           before.0, before.1,
+                                                           after.0, after.1))
+                                             .find(|cell| cell.is_err())
+                                             .unwrap_or_else(|| Ok(())))
+                        },
+                    })
+                    .find(|change| change.is_err())
+                    .unwrap_or_else(|| Ok(()))
+    }
+}
@@ -9,13 +9,19 @@ pub type Result<T> = ::std::result::Result<T, DrawError>;
 #[derive(Clone, Debug)]
 pub enum DrawError {
     OutOfSize(String),
+    UnvalidDuration(String),
 }
 
 impl fmt::Display for DrawError {
     /// The function `fmt` formats the value using
     /// the given formatter.
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DrawError::OutOfSize(ref size) => write!(f, "the draw has {} cells", size),
+            DrawError::UnvalidDuration(ref duration) => {
+                write!(f, "the duration `{}` isn't a number of milliseconds", duration)
+            },
+        }
     }
 }
 
@@ -25,12 +31,13 @@ impl Error for DrawError {
     fn description(&self) -> &str {
         match *self {
             DrawError::OutOfSize(ref size) => size,
+            DrawError::UnvalidDuration(ref duration) => duration,
         }
     }
 
-    /// The function `cause` returns the lower-level cause of
+    /// The function `source` returns the lower-level source of
     /// this error if any.
-    fn cause(&self) -> Option<&Error> {
+    fn source(&self) -> Option<&(Error + 'static)> {
         None
     }
 }
@@ -0,0 +1,37 @@
+use std::error::Error;
+use std::fmt;
+
+pub type Result<T> = ::std::result::Result<T, LayerError>;
+
+/// The enum `LayerError` defines the possible errors
+/// from constructor Layer.
+#[derive(Clone, Debug)]
+pub enum LayerError {
+    UnknownLayer(String),
+}
+
+impl fmt::Display for LayerError {
+    /// The function `fmt` formats the value using
+    /// the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LayerError::UnknownLayer(ref name) => write!(f, "the layer `{}` is unknown", name),
+        }
+    }
+}
+
+impl Error for LayerError {
+    /// The function `description` returns a short description of
+    /// the error.
+    fn description(&self) -> &str {
+        match *self {
+            LayerError::UnknownLayer(ref name) => name,
+        }
+    }
+
+    /// The function `source` returns the lower-level source of
+    /// this error if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
+        None
+    }
+}
@@ -0,0 +1,70 @@
+pub mod err;
+
+pub use self::err::{LayerError, Result};
+use std::fmt;
+
+/// The number of layers by draw.
+pub const SPEC_MAX_LAYER: usize = 3;
+
+/// The enum `Layer` is a plane of draw, from the lowest to the highest;
+/// the cells without part of the upper layers are transparent.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum Layer {
+    /// The body with the background, always opaque.
+    Body = 0,
+    /// The face over the body.
+    Face = 1,
+    /// The props over the face, like `Heart` or `QuestionMark`.
+    Effect = 2,
+}
+
+/// The list of all Layers from the lowest.
+static SPEC_LAYER_LIST: [Layer; SPEC_MAX_LAYER] = [
+    Layer::Body,
+    Layer::Face,
+    Layer::Effect,
+];
+
+impl Layer {
+    pub fn new(layer: &str) -> Result<Self> {
+        match layer {
+            "Body" => Ok(Layer::Body),
+            "Face" => Ok(Layer::Face),
+            "Effect" => Ok(Layer::Effect),
+            name => Err(LayerError::UnknownLayer(name.to_string())),
+        }
+    }
+
+    /// The function `list` returns all the Layers from the lowest.
+    pub fn list() -> &'static [Layer] {
+        &SPEC_LAYER_LIST
+    }
+
+    /// The accessor method `get_index` returns the order of the layer
+    /// from the lowest.
+    pub fn get_index(&self) -> usize {
+        *self as usize
+    }
+
+    /// The accessor method `get_next` returns the layer above,
+    /// or the lowest after the highest.
+    pub fn get_next(&self) -> Layer {
+        SPEC_LAYER_LIST[(self.get_index() + 1) % SPEC_MAX_LAYER]
+    }
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match *self {
+            Layer::Body => "Body",
+            Layer::Face => "Face",
+            Layer::Effect => "Effect",
+        })
+    }
+}
+
+impl Default for Layer {
+    fn default() -> Layer {
+        Layer::Body
+    }
+}
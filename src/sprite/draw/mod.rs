@@ -1,8 +1,12 @@
+pub mod layer;
 mod err;
 
 pub use self::err::{DrawError, Result};
+pub use self::layer::{Layer, LayerError, SPEC_MAX_LAYER};
+use std::collections::HashMap;
 use std::fmt;
 use std::mem;
+use std::ops::Not;
 
 pub const SPEC_MAX_X: usize = 10;
 pub const SPEC_MAX_Y: usize = 5;
@@ -14,9 +18,13 @@ use ::Cursor;
 pub use super::{Emotion, EmotionError};
 pub use super::{Sheet, SheetError};
 pub use super::Texel;
+pub use super::texel::color::Color;
+pub use super::texel::fallback::Fallback;
 pub use super::texel::part::Part;
 
+use ::Tuple;
 use ::time;
+use ::grammar::SPEC_LAYER_SEPARATOR;
 
 /// Sheet is like the Sheet of the drawned persona.
 
@@ -25,6 +33,29 @@ pub struct Draw {
     posture: Sheet,
     duration: time::Duration,
     board: Cursor<[(Emotion, Texel); SPEC_MAX_XY]>,
+    /// The active layer, whose cells are on the board.
+    layer: Layer,
+    /// The cells of each layer, the active one is on the board.
+    layers: [[(Emotion, Texel); SPEC_MAX_XY]; SPEC_MAX_LAYER],
+}
+
+/// The function `transparent` returns the cells of an empty layer.
+fn transparent() -> [(Emotion, Texel); SPEC_MAX_XY] {
+    [(Emotion::default(), Texel::default()); SPEC_MAX_XY]
+}
+
+/// The function `write_cells` writes a line of pairs by row.
+fn write_cells(f: &mut fmt::Formatter, cells: &[(Emotion, Texel)]) -> fmt::Result {
+    cells.chunks(SPEC_MAX_X)
+         .map(|line: &[(Emotion, Texel)]|
+              write!(f, "{}\n",
+                     line.iter()
+                         .map(|&(ref emotion, ref texel)|
+                              format!("{:?}:{:?}", texel.get_part(), emotion))
+                         .collect::<Vec<String>>()
+                         .join(" ")))
+         .find(|d| d.is_err())
+         .unwrap_or_else(|| Ok(()))
 }
 
 impl Draw {
@@ -43,6 +74,8 @@ impl Draw {
                     posture: *position,
                     duration: time::Duration::milliseconds(duration),
                     board: Cursor::new(line),
+                    layer: Layer::Body,
+                    layers: [transparent(); SPEC_MAX_LAYER],
                 })
             }
         } else {
@@ -79,7 +112,7 @@ impl Draw {
                        texel):
                       (&mut (Emotion, Texel), &Texel)| {
                     cell_emotion.clone_from(emotion);
-                    cell_texel.set_glyph(texel.get_glyph());
+                    cell_texel.set_look(texel);
                     true
                 });
         }
@@ -120,10 +153,358 @@ impl Draw {
             .and_then(|pos| Some(self.set_position(pos)))
     }
 
+    /// The accessor method `get_layer` returns the active layer.
+    pub fn get_layer(&self) -> &Layer {
+        &self.layer
+    }
+
+    /// The mutator method `set_layer` changes the active layer,
+    /// whose cells are edited by the other methods.
+    pub fn set_layer(&mut self, layer: Layer) {
+        self.layers[self.layer.get_index()] = *self.board.get_ref();
+        self.board.get_mut().copy_from_slice(&self.layers[layer.get_index()]);
+        self.layer = layer;
+    }
+
+    /// The accessor method `get_layer_cells` returns the cells of a layer.
+    pub fn get_layer_cells(&self, layer: &Layer) -> &[(Emotion, Texel); SPEC_MAX_XY] {
+        if layer.eq(&self.layer) {
+            self.board.get_ref()
+        } else {
+            &self.layers[layer.get_index()]
+        }
+    }
+
+    /// The mutator method `set_layer_cells` replaces the cells of a layer.
+    pub fn set_layer_cells(&mut self, layer: &Layer, buf: &[(Emotion, Texel)]) -> Result<()> {
+        let len: usize = buf.len();
+
+        if len.eq(&SPEC_MAX_XY) {
+            if layer.eq(&self.layer) {
+                self.board.get_mut().copy_from_slice(buf);
+            } else {
+                self.layers[layer.get_index()].copy_from_slice(buf);
+            }
+            Ok(())
+        } else {
+            Err(DrawError::OutOfSize(format!("{}/{}", len, SPEC_MAX_XY)))
+        }
+    }
+
+    /// The mutator method `set_layer_cell` replaces the cell at the index
+    /// of a layer.
+    pub fn set_layer_cell(&mut self, layer: &Layer, index: usize,
+                          cell: (Emotion, Texel)) -> Option<()> {
+        if layer.eq(&self.layer) {
+            self.set_cell(index, cell)
+        } else {
+            self.layers[layer.get_index()].get_mut(index)
+                                          .map(|current| *current = cell)
+        }
+    }
+
+    /// The accessor method `is_layer_empty` returns true if the layer
+    /// hasn't a cell with a part.
+    pub fn is_layer_empty(&self, layer: &Layer) -> bool {
+        self.get_layer_cells(layer)
+            .iter()
+            .all(|&(_, ref texel)| texel.get_part().not_empty().is_none())
+    }
+
+    /// The method `composite` returns the cells of the visible layers
+    /// flattened from the lowest, the cells without part of the upper
+    /// layers are transparent and a hidden body is blank.
+    pub fn composite(&self, visible: &[Layer]) -> [(Emotion, Texel); SPEC_MAX_XY] {
+        let mut blank: Texel = Texel::default();
+
+        blank.set_glyph(' ');
+        let mut cells: [(Emotion, Texel); SPEC_MAX_XY] = [(Emotion::default(), blank); SPEC_MAX_XY];
+
+        Layer::list().iter()
+                     .filter(|layer| visible.contains(layer))
+                     .all(|layer| {
+                         cells.iter_mut()
+                              .zip(self.get_layer_cells(layer).iter())
+                              .filter(|&(_, &(_, ref texel))|
+                                      layer.eq(&Layer::Body) ||
+                                      texel.get_part().not_empty().is_some())
+                              .all(|(cell, above)| {
+                                  *cell = *above;
+                                  true
+                              });
+                         true
+                     });
+        cells
+    }
+
+    /// The accessor method `get_lines` returns the glyphs by line
+    /// of the layers flattened.
+    pub fn get_lines(&self) -> Vec<String> {
+        self.composite(Layer::list())
+            .chunks(SPEC_MAX_X)
+            .map(|line|
+                 line.iter()
+                     .map(|&(_, ref texel)| texel.get_glyph())
+                     .collect::<String>())
+            .collect::<Vec<String>>()
+    }
+
+    /// The accessor method `get_rendered_lines` returns the glyphs
+    /// or their fallbacks by line of the layers flattened.
+    pub fn get_rendered_lines(&self, fallback: &Fallback) -> Vec<String> {
+        self.composite(Layer::list())
+            .chunks(SPEC_MAX_X)
+            .map(|line|
+                 line.iter()
+                     .map(|&(_, ref texel)| texel.get_rendered(fallback))
+                     .collect::<String>())
+            .collect::<Vec<String>>()
+    }
+
+    /// The accessor method `get_painted_lines` returns the glyphs or their
+    /// fallbacks with their colors for a terminal by line of the layers flattened.
+    pub fn get_painted_lines(&self, fallback: &Fallback) -> Vec<String> {
+        self.composite(Layer::list())
+            .chunks(SPEC_MAX_X)
+            .map(|line|
+                 line.iter()
+                     .map(|&(_, ref texel)| texel.get_painted(fallback))
+                     .collect::<String>())
+            .collect::<Vec<String>>()
+    }
+
+    /// The accessor method `get_colors` returns the foreground and
+    /// the background colors by line of the layers flattened.
+    pub fn get_colors(&self) -> Vec<Vec<(Option<Color>, Option<Color>)>> {
+        self.composite(Layer::list())
+            .chunks(SPEC_MAX_X)
+            .map(|line|
+                 line.iter()
+                     .map(|&(_, ref texel)| (texel.get_fg().cloned(), texel.get_bg().cloned()))
+                     .collect::<Vec<(Option<Color>, Option<Color>)>>())
+            .collect::<Vec<Vec<(Option<Color>, Option<Color>)>>>()
+    }
+
     pub fn get_duration(&self) -> &time::Duration {
         &self.duration
     }
 
+    /// The mutator method `set_duration` changes the duration
+    /// in milliseconds.
+    pub fn set_duration(&mut self, duration: i64) {
+        self.duration = time::Duration::milliseconds(duration);
+    }
+
+    /// The mutator method `set_posture` changes the posture.
+    pub fn set_posture(&mut self, posture: Sheet) {
+        self.posture = posture;
+    }
+
+    /// The accessor method `get_cell` returns the cell at the index.
+    pub fn get_cell(&self, index: usize) -> Option<&(Emotion, Texel)> {
+        self.board.get_ref().get(index)
+    }
+
+    /// The mutator method `set_cell` replaces the cell at the index.
+    pub fn set_cell(&mut self, index: usize, cell: (Emotion, Texel)) -> Option<()> {
+        self.board.get_mut()
+                  .get_mut(index)
+                  .and_then(|current| Some(*current = cell))
+    }
+
+    /// The accessor method `is_same` returns true if the draws have
+    /// the same posture, duration and cells with the same glyphs
+    /// and colors on each layer.
+    pub fn is_same(&self, other: &Draw) -> bool {
+        self.posture.eq(&other.posture) &&
+        self.duration.eq(&other.duration) &&
+        Layer::list().iter().all(|layer|
+            self.get_layer_cells(layer)
+                .iter()
+                .zip(other.get_layer_cells(layer).iter())
+                .all(|(&(emotion, ref texel), &(other_emotion, ref other_texel))|
+                     emotion.eq(&other_emotion) &&
+                     texel.eq(other_texel) &&
+                     texel.get_glyph().eq(&other_texel.get_glyph()) &&
+                     texel.get_fg().eq(&other_texel.get_fg()) &&
+                     texel.get_bg().eq(&other_texel.get_bg())))
+    }
+
+    /// The function `layer_cells_mut` returns the mutable cells of a layer.
+    fn layer_cells_mut(&mut self, layer: &Layer) -> &mut [(Emotion, Texel); SPEC_MAX_XY] {
+        if layer.eq(&self.layer) {
+            self.board.get_mut()
+        } else {
+            &mut self.layers[layer.get_index()]
+        }
+    }
+
+    /// The mutator method `mirror_horizontal` reverses each line of every
+    /// layer, swaps the left and right parts and takes the glyphs of the
+    /// texels of the swapped parts, returns the cells of the active layer
+    /// without texel which keep their glyph.
+    pub fn mirror_horizontal(&mut self, texels: &HashMap<Tuple, Vec<Texel>>) -> Vec<usize> {
+        let position: usize = self.get_position();
+        let active: Layer = self.layer;
+        let mut missing: Vec<usize> = Vec::new();
+
+        Layer::list().iter().all(|layer| {
+            let cells: &mut [(Emotion, Texel); SPEC_MAX_XY] = self.layer_cells_mut(layer);
+            let before: [(Emotion, Texel); SPEC_MAX_XY] = *cells;
+            let mut count: HashMap<Part, usize> = HashMap::new();
+
+            cells.iter_mut()
+                 .enumerate()
+                 .all(|(index, &mut (ref mut emotion, ref mut texel))| {
+                     let (from_emotion, from_texel): (Emotion, Texel) =
+                         before[index - index % SPEC_MAX_X + SPEC_MAX_X - 1 - index % SPEC_MAX_X];
+                     let part: Part = from_texel.get_part().mirror();
+
+                     *emotion = from_emotion;
+                     *texel = from_texel;
+                     if layer.eq(&Layer::Body) || part.not_empty().is_some() {
+                         let nth: &mut usize = count.entry(part).or_insert(0);
+
+                         texel.set_part(part);
+                         match texels.get(&Tuple::from((part, from_emotion)))
+                                     .and_then(|texels| texels.get(*nth)) {
+                             Some(mirror) => texel.set_look(mirror),
+                             None if layer.eq(&active) => missing.push(index),
+                             None => {},
+                         }
+                         *nth += 1;
+                     }
+                     true
+                 })
+        });
+        self.set_position(position - position % SPEC_MAX_X + SPEC_MAX_X - 1 - position % SPEC_MAX_X);
+        missing
+    }
+
+    /// The mutator method `remap` takes again the glyph and the colors
+    /// of every cell of every layer from the texels of its part and
+    /// emotion like the loader, the cells without part of the upper
+    /// layers are transparent; returns the cells of the active layer
+    /// without texel which keep their glyph.
+    pub fn remap(&mut self, texels: &HashMap<Tuple, Vec<Texel>>) -> Vec<usize> {
+        let active: Layer = self.layer;
+        let mut missing: Vec<usize> = Vec::new();
+
+        Layer::list().iter().all(|layer| {
+            let mut count: HashMap<Part, usize> = HashMap::new();
+
+            self.layer_cells_mut(layer)
+                .iter_mut()
+                .enumerate()
+                .filter(|&(_, &mut (_, ref texel))|
+                        layer.eq(&Layer::Body) || texel.get_part().not_empty().is_some())
+                .all(|(index, &mut (emotion, ref mut texel))| {
+                    let nth: &mut usize = count.entry(*texel.get_part()).or_insert(0);
+                    let look: Option<Texel> =
+                        texels.get(&Tuple::from((*texel.get_part(), emotion)))
+                              .and_then(|texels| texels.get(*nth))
+                              .cloned();
+
+                    *nth += 1;
+                    match look {
+                        Some(look) => texel.set_look(&look),
+                        None if layer.eq(&active) => missing.push(index),
+                        None => {},
+                    }
+                    true
+                })
+        });
+        missing
+    }
+
+    /// The mutator method `retouch` sets the part and the emotion of the
+    /// cells and remaps the glyphs, the cells whose new pair hasn't texel
+    /// are left untouched and returned.
+    fn retouch(&mut self, cells: &[(usize, (Part, Emotion))],
+               texels: &HashMap<Tuple, Vec<Texel>>) -> Vec<usize> {
+        let board: [(Emotion, Texel); SPEC_MAX_XY] = *self.board.get_ref();
+        let mut trial: Draw = *self;
+
+        cells.iter()
+             .all(|&(index, (part, emotion))| {
+                 trial.board.get_mut()
+                            .get_mut(index)
+                            .map(|&mut (ref mut cell, ref mut texel)| {
+                                *cell = emotion;
+                                texel.set_part(part);
+                            });
+                 true
+             });
+        let missing: Vec<usize> = trial.remap(texels)
+                                       .into_iter()
+                                       .filter(|index|
+                                               cells.iter().any(|&(cell, _)| cell.eq(index)))
+                                       .collect::<Vec<usize>>();
+
+        self.board.get_mut()
+                  .iter_mut()
+                  .zip(trial.board.get_ref().iter().zip(board.iter()))
+                  .enumerate()
+                  .all(|(index, (cell, (&after, &before)))| {
+                      *cell = if missing.contains(&index) { before } else { after };
+                      true
+                  });
+        self.remap(texels);
+        missing
+    }
+
+    /// The mutator method `fill` sets the emotion of the cells with
+    /// a part and remaps the glyphs, returns the cells without texel
+    /// which are left untouched.
+    pub fn fill(&mut self, cells: &[usize], emotion: &Emotion,
+                texels: &HashMap<Tuple, Vec<Texel>>) -> Vec<usize> {
+        let cells: Vec<(usize, (Part, Emotion))> =
+            self.board.get_ref()
+                      .iter()
+                      .enumerate()
+                      .filter(|&(index, &(_, ref texel))|
+                              cells.contains(&index) && texel.get_part().not_empty().is_some())
+                      .map(|(index, &(_, ref texel))| (index, (*texel.get_part(), *emotion)))
+                      .collect::<Vec<(usize, (Part, Emotion))>>();
+
+        self.retouch(&cells, texels)
+    }
+
+    /// The mutator method `paste` sets the part and the emotion of
+    /// the cells and remaps the glyphs, returns the cells without texel
+    /// which are left untouched.
+    pub fn paste(&mut self, cells: &[(usize, (Part, Emotion))],
+                 texels: &HashMap<Tuple, Vec<Texel>>) -> Vec<usize> {
+        self.retouch(cells, texels)
+    }
+
+    /// The mutator method `set_part_emotion` sets the emotion, the glyph
+    /// and the colors of every cell of the part from its texels, returns
+    /// the cells without glyph which are kept.
+    pub fn set_part_emotion(&mut self, part: &Part, emotion: &Emotion,
+                            texels: Option<&Vec<Texel>>) -> Vec<usize> {
+        let mut nth: usize = 0;
+
+        self.board.get_mut()
+                  .iter_mut()
+                  .enumerate()
+                  .filter(|&(_, &mut (_, ref texel))| texel.get_part().eq(part))
+                  .filter_map(|(index, &mut (ref mut cell, ref mut texel))| {
+                      let look: Option<&Texel> = texels.and_then(|texels| texels.get(nth));
+
+                      nth += 1;
+                      match look {
+                          Some(look) => {
+                              cell.clone_from(emotion);
+                              texel.set_look(look);
+                              None
+                          },
+                          None => Some(index),
+                      }
+                  })
+                  .collect::<Vec<usize>>()
+    }
+
     pub fn set_cell_at(&mut self,
         index: usize, texel: &Texel, emotion: &Emotion
     ) {
@@ -158,6 +539,24 @@ impl<'a> IntoIterator for &'a Draw {
     }
 }
 
+impl fmt::Display for Draw {
+    /// The function `fmt` formats the draw like in a sprite file,
+    /// the posture with the duration and a line of pairs by row,
+    /// followed by each upper layer which isn't empty.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}\n", self.posture, self.duration.num_milliseconds())
+            .and(write_cells(f, self.get_layer_cells(&Layer::Body)))
+            .and(Layer::list().iter()
+                              .skip(1)
+                              .filter(|layer| self.is_layer_empty(layer).not())
+                              .map(|layer|
+                                   write!(f, "{} {}\n", SPEC_LAYER_SEPARATOR, layer)
+                                       .and(write_cells(f, self.get_layer_cells(layer))))
+                              .find(|d| d.is_err())
+                              .unwrap_or_else(|| Ok(())))
+    }
+}
+
 impl fmt::Debug for Draw {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "(Sheet: {:?}, sprite: {:?})",
@@ -176,6 +575,8 @@ impl Clone for Draw {
             posture: self.posture,
             duration: self.duration,
             board: Cursor::new(*self.board.get_ref()),
+            layer: self.layer,
+            layers: self.layers,
         }
     }
 
@@ -183,6 +584,8 @@ impl Clone for Draw {
         self.posture.clone_from(&source.posture);
         self.duration.clone_from(&source.duration);
         self.board.get_mut().copy_from_slice(source.board.get_ref());
+        self.layer.clone_from(&source.layer);
+        self.layers = source.layers;
     }
 }
 
@@ -200,6 +603,8 @@ impl Default for Draw {
                 posture: Sheet::default(),
                 duration: time::Duration::milliseconds(0),
                 board: Cursor::new(board),
+                layer: Layer::Body,
+                layers: [transparent(); SPEC_MAX_LAYER],
             }
         }
     }
@@ -6,15 +6,26 @@ pub type Result<T> = ::std::result::Result<T, SpriteError>;
 
 /// The enum `SpriteError` defines the possible errors
 /// from constructor Sprite.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum SpriteError {
+    /// Unvalid conflict syntax.
+    SyntaxConflict(String),
+    /// The sprite has already all its draws.
+    OutOfDraw(usize),
 }
 
 impl fmt::Display for SpriteError {
     /// The function `fmt` formats the value using
     /// the given formatter.
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SpriteError::SyntaxConflict(ref line) => {
+                write!(f, "the conflict line `{}` is malformed", line)
+            },
+            SpriteError::OutOfDraw(count) => {
+                write!(f, "the sprite has already {} draws", count)
+            },
+        }
     }
 }
 
@@ -23,12 +34,14 @@ impl Error for SpriteError {
     /// the error.
     fn description(&self) -> &str {
         match *self {
+            SpriteError::SyntaxConflict(ref line) => line,
+            SpriteError::OutOfDraw(_) => "The sprite has already all its draws.",
         }
     }
 
-    /// The function `cause` returns the lower-level cause of
+    /// The function `source` returns the lower-level source of
     /// this error if any.
-    fn cause(&self) -> Option<&Error> {
+    fn source(&self) -> Option<&(Error + 'static)> {
         None
     }
 }
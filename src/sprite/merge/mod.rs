@@ -0,0 +1,596 @@
+use std::char;
+use std::ffi::OsString;
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::draw::{Color, Draw, Layer};
+use super::{Emotion, Sheet, Sprite, SpriteError, Texel};
+use super::super::{GraphicError, Result};
+
+/// The extension of the conflict file written next to a merged sprite file.
+pub const SPEC_EXT_CONFLICT: &'static str = "conflict";
+
+/// The enum `Side` defines a side of a merge.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Side {
+    Ours,
+    Theirs,
+}
+
+impl Side {
+    /// The constructor `new` parses the name of a side.
+    pub fn new(side: &str) -> Option<Self> {
+        match side {
+            "ours" => Some(Side::Ours),
+            "theirs" => Some(Side::Theirs),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match *self {
+            Side::Ours => "ours",
+            Side::Theirs => "theirs",
+        })
+    }
+}
+
+/// The enum `Conflict` defines a change made by both sides of a merge,
+/// the merged sprite keeps our side.
+#[derive(Clone, Copy, Debug)]
+pub enum Conflict {
+    /// Both sides changed the cell of a layer.
+    Cell {
+        draw: usize,
+        layer: Layer,
+        index: usize,
+        ours: (Emotion, Texel),
+        theirs: (Emotion, Texel),
+    },
+    /// Both sides changed the duration.
+    Duration { draw: usize, ours: i64, theirs: i64 },
+    /// Both sides changed the posture.
+    Posture { draw: usize, ours: Sheet, theirs: Sheet },
+    /// A side changed the draw and the other removed it, the draw is kept.
+    Draw { draw: usize, removed: Side },
+    /// Both sides added a different draw, ours is at the draw and
+    /// theirs follows it.
+    Added { draw: usize },
+}
+
+impl Conflict {
+    /// The accessor method `get_draw` returns the index of the draw
+    /// in the merged sprite.
+    pub fn get_draw(&self) -> usize {
+        match *self {
+            Conflict::Cell { draw, .. } |
+            Conflict::Duration { draw, .. } |
+            Conflict::Posture { draw, .. } |
+            Conflict::Draw { draw, .. } |
+            Conflict::Added { draw } => draw,
+        }
+    }
+
+    /// The accessor method `is_removal` returns true if taking the side
+    /// removes a draw of the conflict.
+    pub fn is_removal(&self, side: Side) -> bool {
+        match *self {
+            Conflict::Draw { removed, .. } => removed.eq(&side),
+            Conflict::Added { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// The accessor method `get_cell` returns the index of the cell
+    /// if the conflict is on a cell.
+    pub fn get_cell(&self) -> Option<usize> {
+        match *self {
+            Conflict::Cell { index, .. } => Some(index),
+            _ => None,
+        }
+    }
+}
+
+/// The function `cell_to_string` formats a cell as `Part:Emotion:glyph`,
+/// followed by `:fg:bg` with `-` for a default color if the texel is colored.
+fn cell_to_string(&(emotion, ref texel): &(Emotion, Texel)) -> String {
+    let color = |color: Option<&Color>| color.map_or("-".to_string(), |color| color.to_string());
+
+    if texel.get_fg().is_none() && texel.get_bg().is_none() {
+        format!("{:?}:{:?}:{:x}", texel.get_part(), emotion, texel.get_glyph() as u32)
+    } else {
+        format!("{:?}:{:?}:{:x}:{}:{}",
+                texel.get_part(), emotion, texel.get_glyph() as u32,
+                color(texel.get_fg()), color(texel.get_bg()))
+    }
+}
+
+/// The function `cell_from_str` parses a cell formatted as `Part:Emotion:glyph`
+/// or `Part:Emotion:glyph:fg:bg`.
+fn cell_from_str(cell: &str) -> Option<(Emotion, Texel)> {
+    let color = |color: &str| -> Option<Option<Color>> {
+        if color.eq("-") {
+            Some(None)
+        } else {
+            Color::new(color).ok().map(Some)
+        }
+    };
+    let words: Vec<&str> = cell.split(':').collect::<Vec<&str>>();
+    let colors: Option<(Option<Color>, Option<Color>)> = match words.as_slice() {
+        &[_, _, _] => Some((None, None)),
+        &[_, _, _, fg, bg] => color(fg).and_then(|fg| color(bg).map(|bg| (fg, bg))),
+        _ => None,
+    };
+
+    match (words.as_slice(), colors) {
+        (&[part, emotion, glyph, ..], Some((fg, bg))) => {
+            u32::from_str_radix(glyph, 16).ok()
+                .and_then(char::from_u32)
+                .and_then(|glyph| Texel::new(part, glyph).ok())
+                .and_then(|mut texel| Emotion::new(emotion).ok()
+                                                           .map(|emotion| {
+                                                               texel.set_colors(fg, bg);
+                                                               (emotion, texel)
+                                                           }))
+        },
+        _ => None,
+    }
+}
+
+impl fmt::Display for Conflict {
+    /// The function `fmt` formats the conflict as a line of conflict file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Conflict::Cell { draw, layer: Layer::Body, index, ref ours, ref theirs } => {
+                write!(f, "cell {} {} {} {}",
+                       draw, index, cell_to_string(ours), cell_to_string(theirs))
+            },
+            Conflict::Cell { draw, layer, index, ref ours, ref theirs } => {
+                write!(f, "cell {} {} {} {} {}",
+                       draw, layer, index, cell_to_string(ours), cell_to_string(theirs))
+            },
+            Conflict::Duration { draw, ours, theirs } => {
+                write!(f, "duration {} {} {}", draw, ours, theirs)
+            },
+            Conflict::Posture { draw, ours, theirs } => {
+                write!(f, "posture {} {} {}", draw, ours, theirs)
+            },
+            Conflict::Draw { draw, removed } => write!(f, "draw {} {}", draw, removed),
+            Conflict::Added { draw } => write!(f, "added {}", draw),
+        }
+    }
+}
+
+impl FromStr for Conflict {
+    type Err = SpriteError;
+
+    /// The function `from_str` parses a line of conflict file.
+    fn from_str(line: &str) -> ::std::result::Result<Self, Self::Err> {
+        let words: Vec<&str> = line.split_whitespace().collect::<Vec<&str>>();
+        let number = |word: &str| word.parse::<usize>().ok();
+
+        match words.as_slice() {
+            &["cell", draw, index, ours, theirs] => {
+                match (number(draw), number(index), cell_from_str(ours), cell_from_str(theirs)) {
+                    (Some(draw), Some(index), Some(ours), Some(theirs)) => Some(
+                        Conflict::Cell { draw: draw, layer: Layer::Body, index: index,
+                                         ours: ours, theirs: theirs }
+                    ),
+                    _ => None,
+                }
+            },
+            &["cell", draw, layer, index, ours, theirs] => {
+                match (number(draw), Layer::new(layer).ok(), number(index),
+                       cell_from_str(ours), cell_from_str(theirs)) {
+                    (Some(draw), Some(layer), Some(index), Some(ours), Some(theirs)) => Some(
+                        Conflict::Cell { draw: draw, layer: layer, index: index,
+                                         ours: ours, theirs: theirs }
+                    ),
+                    _ => None,
+                }
+            },
+            &["duration", draw, ours, theirs] => {
+                match (number(draw), ours.parse::<i64>(), theirs.parse::<i64>()) {
+                    (Some(draw), Ok(ours), Ok(theirs)) => Some(
+                        Conflict::Duration { draw: draw, ours: ours, theirs: theirs }
+                    ),
+                    _ => None,
+                }
+            },
+            &["posture", draw, ours, theirs] => {
+                match (number(draw), Sheet::new(ours), Sheet::new(theirs)) {
+                    (Some(draw), Ok(ours), Ok(theirs)) => Some(
+                        Conflict::Posture { draw: draw, ours: ours, theirs: theirs }
+                    ),
+                    _ => None,
+                }
+            },
+            &["draw", draw] => {
+                number(draw).map(|draw| Conflict::Draw { draw: draw, removed: Side::Theirs })
+            },
+            &["added", draw] => number(draw).map(|draw| Conflict::Added { draw: draw }),
+            &["draw", draw, removed] => {
+                match (number(draw), Side::new(removed)) {
+                    (Some(draw), Some(removed)) => Some(
+                        Conflict::Draw { draw: draw, removed: removed }
+                    ),
+                    _ => None,
+                }
+            },
+            _ => None,
+        }.ok_or_else(|| SpriteError::SyntaxConflict(line.to_string()))
+    }
+}
+
+/// The function `same_cell` returns true if the cells have the same
+/// emotion, part and glyph.
+fn same_cell(&(emotion, ref texel): &(Emotion, Texel),
+             &(other_emotion, ref other_texel): &(Emotion, Texel)) -> bool {
+    emotion.eq(&other_emotion) &&
+    texel.get_part().eq(other_texel.get_part()) &&
+    texel.get_glyph().eq(&other_texel.get_glyph())
+}
+
+/// The function `pick` returns the side to keep of a three-way value
+/// or None if both sides changed it differently.
+fn pick<T, F: Fn(&T, &T) -> bool>(base: T, ours: T, theirs: T, same: F) -> Option<T> {
+    if same(&ours, &theirs) || same(&base, &theirs) {
+        Some(ours)
+    } else if same(&base, &ours) {
+        Some(theirs)
+    } else {
+        None
+    }
+}
+
+/// The function `matches` returns for each base draw the index of the
+/// same draw in the other draws, from their longest common subsequence.
+fn matches(base: &[&Draw], other: &[&Draw]) -> Vec<Option<usize>> {
+    let mut length: Vec<Vec<usize>> = vec![vec![0; other.len() + 1]; base.len() + 1];
+    let mut matched: Vec<Option<usize>> = vec![None; base.len()];
+    let (mut index, mut other_index): (usize, usize) = (base.len(), other.len());
+
+    (0..base.len()).all(|b| (0..other.len()).all(|o| {
+        length[b + 1][o + 1] = if base[b].is_same(other[o]) {
+            length[b][o] + 1
+        } else {
+            length[b][o + 1].max(length[b + 1][o])
+        };
+        true
+    }));
+    while index > 0 && other_index > 0 {
+        if base[index - 1].is_same(other[other_index - 1]) {
+            matched[index - 1] = Some(other_index - 1);
+            index -= 1;
+            other_index -= 1;
+        } else if length[index - 1][other_index] >= length[index][other_index - 1] {
+            index -= 1;
+        } else {
+            other_index -= 1;
+        }
+    }
+    matched
+}
+
+/// The function `is_same_chunk` returns true if the chunks have
+/// the same draws.
+fn is_same_chunk(chunk: &[&Draw], other: &[&Draw]) -> bool {
+    chunk.len().eq(&other.len()) &&
+    chunk.iter().zip(other.iter()).all(|(draw, other)| draw.is_same(other))
+}
+
+/// The struct `Merge` is the three-way merge of two sprites from their
+/// common ancestor, the draws are aligned on the draws which both sides
+/// kept from the base.
+#[derive(Clone, Debug)]
+pub struct Merge {
+    sprite: Sprite,
+    conflicts: Vec<Conflict>,
+}
+
+impl Merge {
+    /// The constructor `new` returns the merge of our sprite and their
+    /// sprite from the base sprite, or an error if the merged sprite
+    /// hasn't room for all the draws.
+    pub fn new(base: &Sprite, ours: &Sprite, theirs: &Sprite)
+               -> ::std::result::Result<Self, SpriteError> {
+        let mut merge: Merge = Merge {
+            sprite: Sprite::default(),
+            conflicts: Vec::new(),
+        };
+        let (base_draws, ours_draws, theirs_draws): (Vec<&Draw>, Vec<&Draw>, Vec<&Draw>) = (
+            base.into_iter().collect::<Vec<&Draw>>(),
+            ours.into_iter().collect::<Vec<&Draw>>(),
+            theirs.into_iter().collect::<Vec<&Draw>>(),
+        );
+        let anchors: Vec<(usize, usize, usize)> =
+            matches(&base_draws, &ours_draws).into_iter()
+                .zip(matches(&base_draws, &theirs_draws).into_iter())
+                .enumerate()
+                .filter_map(|(index, matched)| match matched {
+                    (Some(ours), Some(theirs)) => Some((index, ours, theirs)),
+                    _ => None,
+                })
+                .chain(Some((base_draws.len(), ours_draws.len(), theirs_draws.len())))
+                .collect::<Vec<(usize, usize, usize)>>();
+        let mut from: (usize, usize, usize) = (0, 0, 0);
+
+        merge.sprite.extend(&theirs.texel);
+        merge.sprite.extend(&ours.texel);
+        let kept: bool = anchors.iter().all(|&(base, ours, theirs)| {
+            let kept: bool = merge.chunk(&base_draws[from.0..base],
+                                         &ours_draws[from.1..ours],
+                                         &theirs_draws[from.2..theirs]);
+
+            from = (base + 1, ours + 1, theirs + 1);
+            kept && ours_draws.get(ours)
+                              .map_or(true, |draw| merge.sprite.insert_draw(**draw).is_some())
+        });
+
+        if kept {
+            Ok(merge)
+        } else {
+            Err(SpriteError::OutOfDraw(merge.sprite.count))
+        }
+    }
+
+    /// The function `chunk` merges the draws between two draws which
+    /// both sides kept, a side which left the base takes the chunk,
+    /// else the draws are merged by index; returns false if a draw
+    /// is out of the sprite.
+    fn chunk(&mut self, base: &[&Draw], ours: &[&Draw], theirs: &[&Draw]) -> bool {
+        if is_same_chunk(base, ours) {
+            theirs.iter().all(|draw| self.sprite.insert_draw(**draw).is_some())
+        } else if is_same_chunk(base, theirs) || is_same_chunk(ours, theirs) {
+            ours.iter().all(|draw| self.sprite.insert_draw(**draw).is_some())
+        } else {
+            (0..base.len().max(ours.len()).max(theirs.len())).all(|index| {
+                let draws: Vec<Draw> = match (base.get(index), ours.get(index), theirs.get(index)) {
+                    (_, Some(ours), Some(theirs)) if ours.is_same(theirs) => vec![**ours],
+                    (Some(base), Some(ours), Some(theirs)) => vec![self.draw(base, ours, theirs)],
+                    (None, Some(ours), Some(theirs)) => {
+                        self.conflicts.push(Conflict::Added { draw: self.sprite.count });
+                        vec![**ours, **theirs]
+                    },
+                    (Some(base), Some(kept), None) |
+                    (Some(base), None, Some(kept)) => {
+                        if base.is_same(kept) {
+                            Vec::new()
+                        } else {
+                            let removed: Side = if ours.get(index).is_some() {
+                                Side::Theirs
+                            } else {
+                                Side::Ours
+                            };
+
+                            self.conflicts.push(Conflict::Draw {
+                                draw: self.sprite.count,
+                                removed: removed,
+                            });
+                            vec![**kept]
+                        }
+                    },
+                    (None, Some(kept), None) |
+                    (None, None, Some(kept)) => vec![**kept],
+                    (_, None, None) => Vec::new(),
+                };
+
+                draws.into_iter().all(|draw| self.sprite.insert_draw(draw).is_some())
+            })
+        }
+    }
+
+    /// The function `draw` merges a draw changed by both sides
+    /// cell by cell of each layer.
+    fn draw(&mut self, base: &Draw, ours: &Draw, theirs: &Draw) -> Draw {
+        let index: usize = self.sprite.count;
+        let mut draw: Draw = ours.clone();
+
+        match pick(*base.get_posture(), *ours.get_posture(), *theirs.get_posture(),
+                   |a, b| a.eq(b)) {
+            Some(posture) => draw.set_posture(posture),
+            None => self.conflicts.push(Conflict::Posture {
+                draw: index,
+                ours: *ours.get_posture(),
+                theirs: *theirs.get_posture(),
+            }),
+        }
+        match pick(base.get_duration().num_milliseconds(),
+                   ours.get_duration().num_milliseconds(),
+                   theirs.get_duration().num_milliseconds(),
+                   |a, b| a.eq(b)) {
+            Some(duration) => draw.set_duration(duration),
+            None => self.conflicts.push(Conflict::Duration {
+                draw: index,
+                ours: ours.get_duration().num_milliseconds(),
+                theirs: theirs.get_duration().num_milliseconds(),
+            }),
+        }
+        Layer::list().iter().all(|layer|
+            base.get_layer_cells(layer)
+                .iter()
+                .zip(ours.get_layer_cells(layer).iter())
+                .zip(theirs.get_layer_cells(layer).iter())
+                .enumerate()
+                .all(|(cell, ((&base, &ours), &theirs))| {
+                    match pick(base, ours, theirs, same_cell) {
+                        Some(kept) => draw.set_layer_cell(layer, cell, kept),
+                        None => Some(self.conflicts.push(Conflict::Cell {
+                            draw: index,
+                            layer: *layer,
+                            index: cell,
+                            ours: ours,
+                            theirs: theirs,
+                        })),
+                    }.is_some()
+                }));
+        draw
+    }
+
+    /// The accessor method `get_sprite` returns the merged sprite.
+    pub fn get_sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+
+    /// The accessor method `get_conflicts` returns the conflicts.
+    pub fn get_conflicts(&self) -> &Vec<Conflict> {
+        &self.conflicts
+    }
+
+    /// The accessor method `is_clean` returns true if the merge
+    /// hasn't conflict.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+impl Into<(Sprite, Vec<Conflict>)> for Merge {
+    /// The function `into` returns the merged sprite with its conflicts.
+    fn into(self) -> (Sprite, Vec<Conflict>) {
+        (self.sprite, self.conflicts)
+    }
+}
+
+/// The function `conflict_path` returns the path of the conflict file
+/// of a sprite file.
+pub fn conflict_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let mut name: OsString = path.as_ref().as_os_str().to_os_string();
+
+    name.push(".");
+    name.push(SPEC_EXT_CONFLICT);
+    PathBuf::from(name)
+}
+
+/// The function `read_conflicts` returns the conflicts of a conflict file
+/// from a readable source.
+pub fn read_conflicts<R: Read>(source: R) -> Result<Vec<Conflict>> {
+    io::BufReader::new(source)
+        .lines()
+        .filter_map(|line: io::Result<String>|
+            match line {
+                Err(why) => Some(Err(GraphicError::ReadFile(None, why))),
+                Ok(ref line) if line.trim().is_empty() => None,
+                Ok(line) => Some(line.parse::<Conflict>().map_err(GraphicError::Sprite)),
+            })
+        .collect::<Result<Vec<Conflict>>>()
+}
+
+/// The function `write_conflicts` writes a line by conflict.
+pub fn write_conflicts<W: Write>(mut output: W, conflicts: &[Conflict]) -> io::Result<()> {
+    conflicts.iter()
+             .map(|conflict| writeln!(output, "{}", conflict))
+             .find(|line| line.is_err())
+             .unwrap_or_else(|| Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::SPEC_MAX_DRAW;
+    use super::super::draw::SPEC_MAX_XY;
+
+    /// The function `draw` returns a draw of empty pairs with
+    /// the duration.
+    fn draw(duration: i64) -> Draw {
+        let cell: (Emotion, Texel) = (Emotion::new("None").unwrap(),
+                                      Texel::new("None", '\u{e000}').unwrap());
+
+        Draw::new(&Sheet::new("BustNormal").unwrap(), duration, &[cell; SPEC_MAX_XY]).unwrap()
+    }
+
+    /// The function `sprite` returns a sprite with a draw by duration.
+    fn sprite(durations: &[i64]) -> Sprite {
+        let mut sprite: Sprite = Sprite::default();
+
+        durations.iter().all(|&duration| sprite.insert_draw(draw(duration)).is_some());
+        sprite
+    }
+
+    /// The function `durations` returns the duration of every draw.
+    fn durations(sprite: &Sprite) -> Vec<i64> {
+        sprite.into_iter()
+              .map(|draw| draw.get_duration().num_milliseconds())
+              .collect::<Vec<i64>>()
+    }
+
+    #[test]
+    fn matches_common_draws() {
+        let (a, b, c, d): (Draw, Draw, Draw, Draw) = (draw(1), draw(2), draw(3), draw(4));
+
+        assert_eq!(matches(&[&a, &b, &c], &[&a, &c, &d]), vec![Some(0), None, Some(1)]);
+        assert_eq!(matches(&[&a, &b], &[&b, &a]).iter()
+                                                 .filter(|index| index.is_some())
+                                                 .count(), 1);
+        assert_eq!(matches(&[&a], &[]), vec![None]);
+    }
+
+    #[test]
+    fn merge_takes_both_sides() {
+        let merge: Merge = Merge::new(&sprite(&[100, 200]),
+                                      &sprite(&[100, 200, 300]),
+                                      &sprite(&[100])).unwrap();
+
+        assert!(merge.is_clean());
+        assert_eq!(durations(merge.get_sprite()), vec![100, 300]);
+    }
+
+    #[test]
+    fn merge_keeps_our_conflicting_side() {
+        let merge: Merge = Merge::new(&sprite(&[100]),
+                                      &sprite(&[200]),
+                                      &sprite(&[300])).unwrap();
+
+        assert_eq!(durations(merge.get_sprite()), vec![200]);
+        assert_eq!(merge.get_conflicts().iter()
+                                        .map(|conflict| conflict.to_string())
+                                        .collect::<Vec<String>>(),
+                   vec!["duration 0 200 300".to_string()]);
+    }
+
+    #[test]
+    fn merge_out_of_draw() {
+        let ours: Vec<i64> = (1..11).collect::<Vec<i64>>();
+        let theirs: Vec<i64> = (11..21).collect::<Vec<i64>>();
+
+        match Merge::new(&sprite(&[]), &sprite(&ours), &sprite(&theirs)) {
+            Err(SpriteError::OutOfDraw(count)) => assert_eq!(count, SPEC_MAX_DRAW),
+            merge => panic!("{:?}", merge.map(|merge| durations(merge.get_sprite()))),
+        }
+    }
+
+    #[test]
+    fn conflict_round_trip() {
+        let cell = |glyph: char, fg: Option<Color>| {
+            let mut texel: Texel = Texel::new("Mouth", glyph).unwrap();
+
+            texel.set_colors(fg, None);
+            (Emotion::new("Happy").unwrap(), texel)
+        };
+        let conflicts: Vec<Conflict> = vec![
+            Conflict::Cell { draw: 0, layer: Layer::Body, index: 12,
+                             ours: cell('\u{e000}', None),
+                             theirs: cell('\u{e001}', Color::new("Red").ok()) },
+            Conflict::Cell { draw: 1, layer: Layer::Effect, index: 3,
+                             ours: cell('\u{e002}', Color::new("20,30,40").ok()),
+                             theirs: cell('\u{e003}', None) },
+            Conflict::Duration { draw: 2, ours: 100, theirs: 200 },
+            Conflict::Posture { draw: 3,
+                                ours: Sheet::new("BustNormal").unwrap(),
+                                theirs: Sheet::new("BustHappy").unwrap() },
+            Conflict::Draw { draw: 4, removed: Side::Ours },
+            Conflict::Added { draw: 5 },
+        ];
+
+        conflicts.iter().all(|conflict| {
+            let line: String = conflict.to_string();
+
+            assert_eq!(line.parse::<Conflict>().unwrap().to_string(), line);
+            true
+        });
+        assert!("cell 0 12 Mouth:Happy".parse::<Conflict>().is_err());
+    }
+}
@@ -1,16 +1,23 @@
 pub mod texel;
 pub mod draw;
+pub mod diff;
+pub mod merge;
 mod err;
 
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
 use std::usize;
 use std::mem;
 
 use Cursor;
+use super::grammar::{SPEC_KEYWORD_TEMPLATE, SPEC_KEYWORD_VERSION, SPEC_SEPARATOR, SPEC_VERSION};
 
 pub use self::draw::SPEC_MAX_XY;
 
-use self::draw::Draw;
+use self::draw::{Draw, Layer};
+use self::diff::Diff;
+use self::merge::{Conflict, Merge, Side};
 pub use self::texel::Texel;
 
 pub use self::err::{SpriteError, Result};
@@ -22,25 +29,48 @@ pub use super::sheet::{Sheet, SheetError};
 /// The limit of draws by sprite.
 pub const SPEC_MAX_DRAW: usize = 16;
 
+/// The struct `Missing` is a cell without texel for the posture
+/// of its draw.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Missing {
+    pub draw: usize,
+    pub posture: Sheet,
+    pub cell: usize,
+}
+
+impl fmt::Display for Missing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "draw {} {} cell {}", self.draw, self.posture, self.cell)
+    }
+}
+
 #[derive(Debug)]
 pub struct Sprite {
     texel: HashMap<Tuple, Vec<Texel>>,
     sheet: Cursor<[Draw; SPEC_MAX_DRAW]>,
     count: usize,
+    /// The names of the templates expanded at load time.
+    template: Vec<String>,
+    /// The draws of the sprite file before the expansion.
+    base: Vec<Draw>,
 }
 
 impl Sprite {
 
+    /// The method `explicite_emotion` sets the emotion by part of each draw
+    /// from a list of tuples by draw, the default tuple is an empty slot.
     pub fn explicite_emotion(&mut self,
         change: &[[Tuple; SPEC_MAX_XY]; SPEC_MAX_DRAW]
     ) {
         let board: Vec<Vec<(Emotion, Vec<Texel>)>> =
             change.iter().map(|tuples: &[Tuple; SPEC_MAX_XY]| {
-                 tuples.iter().filter_map(|tuple| {
-                      self.texel.get(&Tuple::from((tuple.part, tuple.emotion)))
-                          .and_then(|texels| Some((tuple.emotion, texels.clone())))
-                 })
-                 .collect::<Vec<(Emotion, Vec<Texel>)>>()
+                 tuples.iter()
+                       .filter(|tuple| tuple.ne(&&Tuple::default()))
+                       .filter_map(|tuple| {
+                            self.texel.get(&Tuple::from((tuple.part, tuple.emotion)))
+                                .and_then(|texels| Some((tuple.emotion, texels.clone())))
+                       })
+                       .collect::<Vec<(Emotion, Vec<Texel>)>>()
             })
             .collect::<Vec<Vec<(Emotion, Vec<Texel>)>>>();
 
@@ -60,21 +90,29 @@ impl Sprite {
     }
 
     /// The function `insert_list` push a new draw from a list of
-    /// tuple of emotion by part.
+    /// tuple of emotion by part; like the lints report it, a draw with
+    /// a pair without texel is dropped and a pair with fewer glyphs
+    /// than cells repeats its last glyph.
     pub fn insert_list(&mut self,
         duration: i64,
         posture: &Sheet,
         source: &[Tuple],
-    ) {
+    ) -> Result<()> {
         let mut draw: Vec<(Emotion, Texel)> = Vec::with_capacity(SPEC_MAX_XY);
-    
+
+        if self.count >= SPEC_MAX_DRAW {
+            return Err(SpriteError::OutOfDraw(self.count));
+        }
         source.iter().all(|&tuple: &Tuple| {
            self.texel.get(&tuple)
                      .and_then(|texels: &Vec<Texel>| {
                 let index: usize = draw.iter().filter(|&&(_, ref texel)| {
                     texel.get_part().eq(&tuple.part)
                 }).count();
-                Some(draw.push((tuple.emotion, *texels.get(index).unwrap())))
+
+                texels.get(index)
+                      .or_else(|| texels.last())
+                      .map(|texel: &Texel| draw.push((tuple.emotion, *texel)))
             }).is_some()
         });
         if let Ok(draw) = Draw::new(posture, duration, draw.as_slice()) {
@@ -84,6 +122,181 @@ impl Sprite {
                 self.count += 1;
             }
         }
+        Ok(())
+    }
+
+    /// The function `insert_layer` sets an upper layer of the last draw
+    /// from a list of tuple of emotion by part, the cells without
+    /// part are transparent.
+    pub fn insert_layer(&mut self, layer: &Layer, source: &[Tuple]) -> Option<()> {
+        let mut cells: Vec<(Emotion, Texel)> = Vec::with_capacity(SPEC_MAX_XY);
+
+        source.iter().all(|&tuple: &Tuple| {
+            if tuple.part.not_empty().is_none() {
+                cells.push((tuple.emotion, Texel::default()));
+                true
+            } else {
+                let index: usize = cells.iter().filter(|&&(_, ref texel)| {
+                    texel.get_part().eq(&tuple.part)
+                }).count();
+
+                self.texel.get(&tuple)
+                          .and_then(|texels: &Vec<Texel>| texels.get(index))
+                          .map(|texel: &Texel| cells.push((tuple.emotion, *texel)))
+                          .is_some()
+            }
+        });
+        self.count.checked_sub(1)
+                  .and_then(|last| self.sheet.get_mut().get_mut(last))
+                  .and_then(|draw| draw.set_layer_cells(layer, &cells).ok())
+    }
+
+    /// The method `diff` returns the changes by draw and by cell
+    /// from this sprite to another.
+    pub fn diff(&self, other: &Sprite) -> Diff {
+        Diff::new(self, other)
+    }
+
+    /// The method `merge` returns the three-way merge of this sprite
+    /// and their sprite from the common ancestor.
+    pub fn merge(&self, base: &Sprite, theirs: &Sprite) -> Result<Merge> {
+        Merge::new(base, self, theirs)
+    }
+
+    /// The method `resolve` applies a side of a conflict, the merged
+    /// sprite already has our side but for the draw removed by us and
+    /// has the draws added by both sides.
+    pub fn resolve(&mut self, conflict: &Conflict, side: Side) -> Option<()> {
+        match *conflict {
+            Conflict::Draw { draw, .. } if conflict.is_removal(side) => self.remove_draw(draw),
+            Conflict::Draw { .. } => Some(()),
+            Conflict::Added { draw } if side.eq(&Side::Ours) => self.remove_draw(draw + 1),
+            Conflict::Added { draw } => self.remove_draw(draw),
+            _ if side.eq(&Side::Ours) => Some(()),
+            Conflict::Cell { draw, layer, index, theirs, .. } => {
+                self.sheet.get_mut()
+                    .split_at_mut(self.count).0
+                    .get_mut(draw)
+                    .and_then(|draw| draw.set_layer_cell(&layer, index, theirs))
+            },
+            Conflict::Duration { draw, theirs, .. } => {
+                self.sheet.get_mut()
+                    .split_at_mut(self.count).0
+                    .get_mut(draw)
+                    .and_then(|draw| Some(draw.set_duration(theirs)))
+            },
+            Conflict::Posture { draw, theirs, .. } => {
+                self.sheet.get_mut()
+                    .split_at_mut(self.count).0
+                    .get_mut(draw)
+                    .and_then(|draw| Some(draw.set_posture(theirs)))
+            },
+        }
+    }
+
+    /// The method `mirror_horizontal` mirrors the current draw with the
+    /// texels of its posture, returns the cells without texel.
+    pub fn mirror_horizontal(&mut self,
+                             texels: &HashMap<Tuple, Vec<Texel>>
+    ) -> Option<Vec<usize>> {
+        self.with_current_draw(|draw| draw.mirror_horizontal(texels))
+    }
+
+    /// The method `with_current_draw` calls the function with the
+    /// current draw.
+    pub fn with_current_draw<T, F: FnOnce(&mut Draw) -> T>(&mut self, function: F) -> Option<T> {
+        let position: usize = self.sheet.position();
+
+        self.sheet
+            .get_mut()
+            .split_at_mut(self.count).0
+            .get_mut(position)
+            .map(function)
+    }
+
+    /// The method `set_part_emotion_all` sets the emotion of the part
+    /// on the draws of the range with the texels of their posture,
+    /// returns the cells without texel which are kept.
+    pub fn set_part_emotion_all(&mut self,
+                                part: &Part,
+                                emotion: &Emotion,
+                                range: Range<usize>,
+                                texels: &HashMap<Sheet, HashMap<Tuple, Vec<Texel>>>,
+    ) -> Vec<Missing> {
+        let count: usize = self.count;
+
+        self.sheet.get_mut()
+            .iter_mut()
+            .enumerate()
+            .take(count.min(range.end))
+            .skip(range.start)
+            .flat_map(|(index, draw)| {
+                let posture: Sheet = *draw.get_posture();
+
+                draw.set_part_emotion(part, emotion,
+                                      texels.get(&posture)
+                                            .and_then(|texels|
+                                                      texels.get(&Tuple::from((*part, *emotion)))))
+                    .into_iter()
+                    .map(move |cell| Missing { draw: index, posture: posture, cell: cell })
+            })
+            .collect::<Vec<Missing>>()
+    }
+
+    /// The function `insert_draw` pushes a draw if the sprite
+    /// isn't full.
+    pub fn insert_draw(&mut self, draw: Draw) -> Option<()> {
+        self.sheet.get_mut()
+            .get_mut(self.count)
+            .and_then(|current| Some(*current = draw))
+            .and_then(|()| Some(self.count += 1))
+    }
+
+    /// The accessor method `get_template` returns the names of the
+    /// templates expanded from the base draws.
+    pub fn get_template(&self) -> &Vec<String> {
+        &self.template
+    }
+
+    /// The mutator method `set_template` keeps the names of the templates
+    /// and the base draws of an expanded sprite, they're written back
+    /// instead of the frames.
+    pub fn set_template(&mut self, names: &[&str], base: &[Draw]) {
+        self.template = names.iter().map(|name| name.to_string()).collect::<Vec<String>>();
+        self.base = base.to_vec();
+    }
+
+    /// The method `get_base` returns the sprite of the base draws
+    /// without its templates, or nothing for a sprite without template.
+    pub fn get_base(&self) -> Option<Sprite> {
+        if self.template.is_empty() {
+            None
+        } else {
+            let mut base: Sprite = self.clone();
+
+            base.set_template(&[], &[]);
+            while base.remove_draw(0).is_some() {}
+            self.base.iter().all(|draw| base.insert_draw(*draw).is_some());
+            Some(base)
+        }
+    }
+
+    /// The function `remove_draw` removes the draw at the index.
+    pub fn remove_draw(&mut self, index: usize) -> Option<()> {
+        if index < self.count {
+            self.sheet.get_mut()
+                .split_at_mut(self.count).0
+                .split_at_mut(index).1
+                .rotate_left(1);
+            self.count -= 1;
+            *self.sheet.get_mut().get_mut(self.count).unwrap() = Draw::default();
+            if self.sheet.position() >= self.count {
+                self.sheet.set_position(self.count.saturating_sub(1));
+            }
+            Some(())
+        } else {
+            None
+        }
     }
 
     /// The function `extend` extends the local dictionary of texel.
@@ -98,6 +311,30 @@ impl Sprite {
                               .is_none());
     }
 
+    /// The mutator method `remap` takes again the glyphs of the draws
+    /// and of the base draws from the texels by posture, the parts and
+    /// the emotions of the cells are kept.
+    pub fn remap(&mut self, texels: &HashMap<Sheet, HashMap<Tuple, Vec<Texel>>>) {
+        let count: usize = self.count;
+        let mut local: HashMap<Tuple, Vec<Texel>> = HashMap::new();
+
+        self.sheet.get_mut()
+                  .iter_mut()
+                  .take(count)
+                  .chain(self.base.iter_mut())
+                  .all(|draw: &mut Draw| {
+                      let posture: Sheet = *draw.get_posture();
+
+                      texels.get(&posture).map(|texels| {
+                          local.extend(texels.iter().map(|(tuple, texels)|
+                                                          (*tuple, texels.clone())));
+                          draw.remap(texels)
+                      });
+                      true
+                  });
+        self.texel.extend(local);
+    }
+
     pub fn current(&self) -> Option<(&Emotion, &Texel)> {
         self.sheet
             .get_ref()
@@ -124,12 +361,44 @@ impl Sprite {
         self.sheet.get_ref().get(self.sheet.position())
     }
 
+    /// The accessor method `get_position` returns the position of
+    /// the draw sheet cursor.
+    pub fn get_position(&self) -> usize {
+        self.sheet.position()
+    }
+
     /// The mutator method `set_position` changes the position of
     /// the file sprite cursor.
     fn set_position(&mut self, position: usize) {
         self.sheet.set_position(position);
     }
 
+    /// The mutator method `set_position_cell` moves the draw sheet
+    /// cursor and the cell board cursor of this draw.
+    pub fn set_position_cell(&mut self, draw: usize, cell: usize) -> Option<()> {
+        if draw < self.count && cell < SPEC_MAX_XY {
+            self.set_position(draw);
+            self.sheet.get_mut()
+                .get_mut(draw)
+                .and_then(|draw| Some(draw.set_position(cell)))
+        } else {
+            None
+        }
+    }
+
+    /// The mutator method `clone_position_from` copies the draw sheet
+    /// cursor and the cell board cursors from another sprite.
+    pub fn clone_position_from(&mut self, source: &Sprite) {
+        self.set_position(source.get_position());
+        self.sheet.get_mut()
+            .iter_mut()
+            .zip(source.sheet.get_ref().iter())
+            .all(|(draw, source): (&mut Draw, &Draw)| {
+                draw.set_position(source.get_position());
+                true
+            });
+    }
+
     /// The mutator method `add_position_draw` increments the position of
     /// the draw sheet cursor.
     pub fn add_position(&mut self, position: usize) -> Option<()> {
@@ -183,11 +452,43 @@ impl Clone for Sprite {
                     texel: self.texel.clone(),
                     sheet: Cursor::new(sheet),
                     count: self.count,
+                    template: self.template.clone(),
+                    base: self.base.clone(),
                 }
             }
        }
 }
 
+impl fmt::Display for Sprite {
+    /// The function `fmt` formats the sprite like a sprite file,
+    /// the version header with a separator between the draws; an
+    /// expanded sprite is formatted as its template lines and its
+    /// base draws.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let draws: Vec<&Draw> = if self.template.is_empty() {
+            self.into_iter().collect::<Vec<&Draw>>()
+        } else {
+            self.base.iter().collect::<Vec<&Draw>>()
+        };
+
+        write!(f, "{} {}\n", SPEC_KEYWORD_VERSION, SPEC_VERSION)
+            .and(self.template.iter()
+                              .map(|name| write!(f, "{} {}\n", SPEC_KEYWORD_TEMPLATE, name))
+                              .find(|d| d.is_err())
+                              .unwrap_or_else(|| Ok(())))
+            .and(draws.into_iter()
+                      .enumerate()
+                      .map(|(index, draw): (usize, &Draw)|
+                           if index == 0 {
+                               draw.fmt(f)
+                           } else {
+                               write!(f, "{}\n", SPEC_SEPARATOR).and(draw.fmt(f))
+                           })
+                      .find(|d| d.is_err())
+                      .unwrap_or_else(|| Ok(())))
+    }
+}
+
 impl<'a> IntoIterator for &'a Sprite {
     type Item = &'a Draw;
     type IntoIter = ::std::slice::Iter<'a, Draw>;
@@ -210,6 +511,8 @@ impl Default for Sprite {
                 texel: HashMap::with_capacity(SPEC_MAX_XY),
                 sheet: Cursor::new(sheet),
                 count: 0,
+                template: Vec::new(),
+                base: Vec::new(),
             }
         }
     }
@@ -0,0 +1,37 @@
+use std::error::Error;
+use std::fmt;
+
+pub type Result<T> = ::std::result::Result<T, ColorError>;
+
+/// The enum `ColorError` defines the possible errors
+/// from constructor Color.
+#[derive(Clone, Debug)]
+pub enum ColorError {
+    UnknownColor(String),
+}
+
+impl fmt::Display for ColorError {
+    /// The function `fmt` formats the value using
+    /// the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ColorError::UnknownColor(ref name) => write!(f, "the color `{}` is unknown", name),
+        }
+    }
+}
+
+impl Error for ColorError {
+    /// The function `description` returns a short description of
+    /// the error.
+    fn description(&self) -> &str {
+        match *self {
+            ColorError::UnknownColor(ref name) => name,
+        }
+    }
+
+    /// The function `source` returns the lower-level source of
+    /// this error if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
+        None
+    }
+}
@@ -0,0 +1,83 @@
+pub mod err;
+
+pub use self::err::{ColorError, Result};
+use std::fmt;
+
+/// The escape sequence which resets the colors of a terminal.
+pub const SPEC_SGR_RESET: &'static str = "\x1b[0m";
+
+/// The list of the names of the 16 colors.
+static SPEC_COLOR_LIST: [&'static str; 16] = [
+    "Black", "Red", "Green", "Yellow", "Blue", "Magenta", "Cyan", "White",
+    "LightBlack", "LightRed", "LightGreen", "LightYellow",
+    "LightBlue", "LightMagenta", "LightCyan", "LightWhite",
+];
+
+/// The enum `Color` is a foreground or background color of texel,
+/// from the 16 named colors, the 256 indexed colors or the truecolors.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum Color {
+    /// The named color from 0 to 15, like `Red`.
+    Named(u8),
+    /// The indexed color from 0 to 255, like `196`.
+    Indexed(u8),
+    /// The truecolor, like `255,105,180`.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// The constructor `new` parses a color name, an index
+    /// or the red, green and blue components.
+    pub fn new(color: &str) -> Result<Self> {
+        match (SPEC_COLOR_LIST.iter().position(|name| name.eq(&color)),
+               color.split(',')
+                    .map(|component| component.trim().parse::<u8>())
+                    .collect::<::std::result::Result<Vec<u8>, _>>()) {
+            (Some(index), _) => Ok(Color::Named(index as u8)),
+            (None, Ok(ref components)) if components.len() == 1 => {
+                Ok(Color::Indexed(components[0]))
+            },
+            (None, Ok(ref components)) if components.len() == 3 => {
+                Ok(Color::Rgb(components[0], components[1], components[2]))
+            },
+            _ => Err(ColorError::UnknownColor(color.to_string())),
+        }
+    }
+
+    /// The function `list` returns the names of the 16 colors.
+    pub fn list() -> &'static [&'static str] {
+        &SPEC_COLOR_LIST
+    }
+
+    /// The accessor method `get_sgr` returns the escape sequence of
+    /// the color for the foreground or the background of a terminal.
+    pub fn get_sgr(&self, background: bool) -> String {
+        match (*self, background) {
+            (Color::Named(index @ 0...7), false) => format!("\x1b[{}m", 30 + index),
+            (Color::Named(index @ 0...7), true) => format!("\x1b[{}m", 40 + index),
+            (Color::Named(index), false) => format!("\x1b[{}m", 82 + index),
+            (Color::Named(index), true) => format!("\x1b[{}m", 92 + index),
+            (Color::Indexed(index), false) => format!("\x1b[38;5;{}m", index),
+            (Color::Indexed(index), true) => format!("\x1b[48;5;{}m", index),
+            (Color::Rgb(red, green, blue), false) => {
+                format!("\x1b[38;2;{};{};{}m", red, green, blue)
+            },
+            (Color::Rgb(red, green, blue), true) => {
+                format!("\x1b[48;2;{};{};{}m", red, green, blue)
+            },
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    /// The function `fmt` formats the color like in a texel file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Color::Named(index) => {
+                write!(f, "{}", SPEC_COLOR_LIST.get(index as usize).unwrap_or(&"White"))
+            },
+            Color::Indexed(index) => write!(f, "{}", index),
+            Color::Rgb(red, green, blue) => write!(f, "{},{},{}", red, green, blue),
+        }
+    }
+}
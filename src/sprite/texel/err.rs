@@ -2,6 +2,7 @@ use std::error::Error;
 use std::fmt;
 
 use super::part::PartError;
+use super::color::ColorError;
 
 pub type Result<T> = ::std::result::Result<T, TexelError>;
 
@@ -11,13 +12,24 @@ pub type Result<T> = ::std::result::Result<T, TexelError>;
 pub enum TexelError {
     PartFail(PartError),
     ForbiddenGlyph(char),
+    ColorFail(ColorError),
+    UnknownAttribute(String),
 }
 
 impl fmt::Display for TexelError {
     /// The function `fmt` formats the value using
     /// the given formatter.
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TexelError::PartFail(_) => write!(f, "the part of the texel is invalid"),
+            TexelError::ForbiddenGlyph(glyph) => {
+                write!(f, "the glyph U+{:04X} is out of the private unicode range", glyph as u32)
+            },
+            TexelError::ColorFail(_) => write!(f, "the color of the texel is invalid"),
+            TexelError::UnknownAttribute(ref attribute) => {
+                write!(f, "the attribute `{}` is unknown", attribute)
+            },
+        }
     }
 }
 
@@ -28,14 +40,17 @@ impl Error for TexelError {
         match *self {
             TexelError::PartFail(_) => "The part constructor as occured an error.",
             TexelError::ForbiddenGlyph(_) => "Is out of the private unicode range.",
+            TexelError::ColorFail(_) => "The color constructor as occured an error.",
+            TexelError::UnknownAttribute(ref attribute) => attribute,
         }
     }
 
-    /// The function `cause` returns the lower-level cause of
+    /// The function `source` returns the lower-level source of
     /// this error if any.
-    fn cause(&self) -> Option<&Error> {
+    fn source(&self) -> Option<&(Error + 'static)> {
         match *self {
             TexelError::PartFail(ref err) => Some(err),
+            TexelError::ColorFail(ref err) => Some(err),
             _ => None,
         }
     }
@@ -0,0 +1,39 @@
+use std::error::Error;
+use std::fmt;
+
+pub type Result<T> = ::std::result::Result<T, FallbackError>;
+
+/// The enum `FallbackError` defines the possible errors
+/// from constructor Fallback.
+#[derive(Clone, Debug)]
+pub enum FallbackError {
+    UnknownFallback(String),
+}
+
+impl fmt::Display for FallbackError {
+    /// The function `fmt` formats the value using
+    /// the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FallbackError::UnknownFallback(ref name) => {
+                write!(f, "the fallback `{}` is unknown", name)
+            },
+        }
+    }
+}
+
+impl Error for FallbackError {
+    /// The function `description` returns a short description of
+    /// the error.
+    fn description(&self) -> &str {
+        match *self {
+            FallbackError::UnknownFallback(ref name) => name,
+        }
+    }
+
+    /// The function `source` returns the lower-level source of
+    /// this error if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
+        None
+    }
+}
@@ -0,0 +1,106 @@
+pub mod err;
+
+pub use self::err::{FallbackError, Result};
+use std::env;
+use std::fmt;
+use std::ops::Not;
+
+/// The environment variable which forces the rendering of the glyphs.
+pub const SPEC_ENV_FALLBACK: &'static str = "NEKO_FALLBACK";
+/// The terminals whose font hasn't the private glyphs but the blocks.
+pub const SPEC_TERM_BLOCK: [&'static str; 2] = ["linux", "cons25"];
+/// The terminals without unicode.
+pub const SPEC_TERM_ASCII: [&'static str; 3] = ["dumb", "vt100", "vt220"];
+
+/// The list of the renderings of the glyphs.
+static SPEC_FALLBACK_LIST: [Fallback; 4] = [
+    Fallback::Glyph, Fallback::Code, Fallback::Ascii, Fallback::Block,
+];
+
+/// The enum `Fallback` is the rendering of the texels on a terminal
+/// whose font hasn't the private glyphs of Neko.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum Fallback {
+    /// The private glyph.
+    Glyph,
+    /// The two letters of the part, like `Mo`.
+    Code,
+    /// The ASCII approximation of the texel.
+    Ascii,
+    /// The unicode block art of the texel.
+    Block,
+}
+
+impl Fallback {
+    /// The constructor `new` parses the name of a rendering.
+    pub fn new(fallback: &str) -> Result<Self> {
+        SPEC_FALLBACK_LIST.iter()
+                          .find(|name| name.to_string().eq_ignore_ascii_case(fallback))
+                          .cloned()
+                          .ok_or_else(|| FallbackError::UnknownFallback(fallback.to_string()))
+    }
+
+    /// The function `list` returns the renderings.
+    pub fn list() -> &'static [Fallback] {
+        &SPEC_FALLBACK_LIST
+    }
+
+    /// The function `probe` returns the rendering of the variable
+    /// `NEKO_FALLBACK` or else the rendering supported by the locale
+    /// and the terminal of the environment: the ASCII without UTF-8
+    /// or on a terminal without unicode, the blocks on a console
+    /// and the glyphs else. The environment can't tell if the font
+    /// has the private glyphs, the binary measures a glyph on the
+    /// terminal and else the user chooses with `NEKO_FALLBACK`.
+    pub fn probe() -> Self {
+        let variable = |name: &str| env::var(name).ok()
+                                                  .and_then(|value|
+                                                       if value.is_empty() { None } else { Some(value) });
+
+        match variable(SPEC_ENV_FALLBACK).and_then(|fallback| Fallback::new(&fallback).ok()) {
+            Some(fallback) => fallback,
+            None => {
+                let locale: String = variable("LC_ALL").or_else(|| variable("LC_CTYPE"))
+                                                       .or_else(|| variable("LANG"))
+                                                       .unwrap_or_default()
+                                                       .to_lowercase();
+                let term: String = variable("TERM").unwrap_or_default();
+
+                if (locale.contains("utf-8") || locale.contains("utf8")).not() ||
+                   SPEC_TERM_ASCII.contains(&term.as_str()) {
+                    Fallback::Ascii
+                } else if SPEC_TERM_BLOCK.contains(&term.as_str()) {
+                    Fallback::Block
+                } else {
+                    Fallback::Glyph
+                }
+            },
+        }
+    }
+
+    /// The method `get_next` returns the following rendering.
+    pub fn get_next(&self) -> Self {
+        SPEC_FALLBACK_LIST.iter()
+                          .position(|fallback| fallback.eq(self))
+                          .and_then(|index| SPEC_FALLBACK_LIST.get(index + 1))
+                          .cloned()
+                          .unwrap_or(Fallback::Glyph)
+    }
+}
+
+impl Default for Fallback {
+    fn default() -> Self {
+        Fallback::Glyph
+    }
+}
+
+impl fmt::Display for Fallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match *self {
+            Fallback::Glyph => "Glyph",
+            Fallback::Code => "Code",
+            Fallback::Ascii => "Ascii",
+            Fallback::Block => "Block",
+        })
+    }
+}
@@ -1,26 +1,58 @@
 pub mod err;
 pub mod part;
+pub mod color;
+pub mod fallback;
 
 pub use self::err::{TexelError, Result};
 
 use self::part::Part;
+use self::color::{Color, SPEC_SGR_RESET};
+use self::fallback::Fallback;
 use std::fmt;
+use std::str::FromStr;
+
+/// The first glyph of the private unicode range.
+pub const SPEC_GLYPH_MIN: char = '\u{e000}';
+/// The last glyph of the private unicode range.
+pub const SPEC_GLYPH_MAX: char = '\u{efff}';
+/// The attribute of the foreground color after the postures.
+pub const SPEC_ATTRIBUTE_FG: &'static str = "fg=";
+/// The attribute of the background color after the postures.
+pub const SPEC_ATTRIBUTE_BG: &'static str = "bg=";
+/// The attribute of the ASCII approximations after the postures.
+pub const SPEC_ATTRIBUTE_ASCII: &'static str = "ascii=";
+/// The attribute of the block arts after the postures.
+pub const SPEC_ATTRIBUTE_BLOCK: &'static str = "block=";
+/// The block art of a texel without its own.
+pub const SPEC_BLOCK_DEFAULT: char = '\u{2588}';
 
 #[derive(Copy, Clone, Debug, Default)]
 pub struct Texel {
     part: Part,
     glyph: char,
+    /// The foreground color, the color of the terminal without.
+    fg: Option<Color>,
+    /// The background color, the color of the terminal without.
+    bg: Option<Color>,
+    /// The ASCII approximation of the glyph.
+    ascii: Option<char>,
+    /// The unicode block art of the glyph.
+    block: Option<char>,
 }
 
 impl Texel {
     pub fn new(part: &str, glyph: char) -> Result<Self> {
-        if let '\u{e000}'...'\u{efff}' = glyph {
+        if let SPEC_GLYPH_MIN...SPEC_GLYPH_MAX = glyph {
             match Part::new(part) {
                 Err(why) => Err(TexelError::PartFail(why)),
                 Ok(part) => Ok(
                     Texel {
                         part: part,
                         glyph: glyph,
+                        fg: None,
+                        bg: None,
+                        ascii: None,
+                        block: None,
                     }
                 ),
             }
@@ -42,6 +74,136 @@ impl Texel {
     pub fn set_glyph(&mut self, glyph: char) {
         self.glyph = glyph;
     }
+
+    /// The mutator method `set_part` changes the Texel Part.
+    pub fn set_part(&mut self, part: Part) {
+        self.part = part;
+    }
+
+    /// The accessor method `get_fg` returns the foreground color.
+    pub fn get_fg(&self) -> Option<&Color> {
+        self.fg.as_ref()
+    }
+
+    /// The accessor method `get_bg` returns the background color.
+    pub fn get_bg(&self) -> Option<&Color> {
+        self.bg.as_ref()
+    }
+
+    /// The mutator method `set_colors` changes the foreground
+    /// and the background colors.
+    pub fn set_colors(&mut self, fg: Option<Color>, bg: Option<Color>) {
+        self.fg = fg;
+        self.bg = bg;
+    }
+
+    /// The accessor method `get_ascii` returns the ASCII approximation.
+    pub fn get_ascii(&self) -> Option<char> {
+        self.ascii
+    }
+
+    /// The accessor method `get_block` returns the block art.
+    pub fn get_block(&self) -> Option<char> {
+        self.block
+    }
+
+    /// The mutator method `set_fallbacks` changes the ASCII
+    /// approximation and the block art.
+    pub fn set_fallbacks(&mut self, ascii: Option<char>, block: Option<char>) {
+        self.ascii = ascii;
+        self.block = block;
+    }
+
+    /// The accessor method `get_rendered` returns the glyph or its fallback,
+    /// the two letters of the part, the ASCII approximation or else the
+    /// first letter of the part, the block art or else a full block;
+    /// the texel without part is blank out of the glyphs and the codes.
+    pub fn get_rendered(&self, fallback: &Fallback) -> String {
+        match (*fallback, self.part) {
+            (Fallback::Glyph, _) => self.glyph.to_string(),
+            (Fallback::Code, part) => part.to_string(),
+            (_, Part::None) => " ".to_string(),
+            (Fallback::Ascii, part) => {
+                self.ascii.map_or_else(|| part.to_string().chars().take(1).collect::<String>(),
+                                       |ascii| ascii.to_string())
+            },
+            (Fallback::Block, _) => self.block.unwrap_or(SPEC_BLOCK_DEFAULT).to_string(),
+        }
+    }
+
+    /// The accessor method `get_painted` returns the glyph or its fallback
+    /// with the escape sequences of its colors for a terminal.
+    pub fn get_painted(&self, fallback: &Fallback) -> String {
+        if self.fg.is_none() && self.bg.is_none() {
+            self.get_rendered(fallback)
+        } else {
+            format!("{}{}{}{}",
+                    self.fg.map(|fg| fg.get_sgr(false)).unwrap_or_default(),
+                    self.bg.map(|bg| bg.get_sgr(true)).unwrap_or_default(),
+                    self.get_rendered(fallback),
+                    SPEC_SGR_RESET)
+        }
+    }
+
+    /// The mutator method `set_look` changes the glyph, the colors
+    /// and the fallbacks like another texel.
+    pub fn set_look(&mut self, texel: &Texel) {
+        self.glyph = texel.glyph;
+        self.fg = texel.fg;
+        self.bg = texel.bg;
+        self.ascii = texel.ascii;
+        self.block = texel.block;
+    }
+}
+
+/// The struct `Attributes` is the look of the glyphs of a texel line
+/// after its postures, like `fg=Red bg=236 ascii=o- block=\u{2580}\u{2584}`,
+/// the ASCII approximations and the block arts by glyph in order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Attributes {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub ascii: Vec<char>,
+    pub block: Vec<char>,
+}
+
+impl Attributes {
+    /// The method `apply` changes the look of the nth glyph of the line.
+    pub fn apply(&self, nth: usize, texel: &mut Texel) {
+        texel.set_colors(self.fg, self.bg);
+        texel.set_fallbacks(self.ascii.get(nth).cloned(), self.block.get(nth).cloned());
+    }
+}
+
+impl FromStr for Attributes {
+    type Err = TexelError;
+
+    /// The function `from_str` parses the attributes of a texel line.
+    fn from_str(attributes: &str) -> ::std::result::Result<Self, Self::Err> {
+        attributes.split_whitespace()
+                  .fold(Ok(Attributes::default()), |attributes, attribute|
+                      attributes.and_then(|mut attributes| {
+                          if attribute.starts_with(SPEC_ATTRIBUTE_FG) {
+                              attributes.fg = Some(try!(
+                                  Color::new(&attribute[SPEC_ATTRIBUTE_FG.len()..])
+                                        .map_err(TexelError::ColorFail)));
+                          } else if attribute.starts_with(SPEC_ATTRIBUTE_BG) {
+                              attributes.bg = Some(try!(
+                                  Color::new(&attribute[SPEC_ATTRIBUTE_BG.len()..])
+                                        .map_err(TexelError::ColorFail)));
+                          } else if attribute.starts_with(SPEC_ATTRIBUTE_ASCII) &&
+                                    attribute[SPEC_ATTRIBUTE_ASCII.len()..].is_ascii() {
+                              attributes.ascii = attribute[SPEC_ATTRIBUTE_ASCII.len()..].chars()
+                                                                                        .collect();
+                          } else if attribute.starts_with(SPEC_ATTRIBUTE_BLOCK) {
+                              attributes.block = attribute[SPEC_ATTRIBUTE_BLOCK.len()..].chars()
+                                                                                        .collect();
+                          } else {
+                              return Err(TexelError::UnknownAttribute(attribute.to_string()));
+                          }
+                          Ok(attributes)
+                      }))
+    }
 }
 
 impl PartialEq for Texel {
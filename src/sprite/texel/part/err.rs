@@ -13,8 +13,10 @@ pub enum PartError {
 impl fmt::Display for PartError {
     /// The function `fmt` formats the value using
     /// the given formatter.
-    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PartError::UnknownPart(ref name) => write!(f, "the part `{}` is unknown", name),
+        }
     }
 }
 
@@ -27,9 +29,9 @@ impl Error for PartError {
         }
     }
 
-    /// The function `cause` returns the lower-level cause of
+    /// The function `source` returns the lower-level source of
     /// this error if any.
-    fn cause(&self) -> Option<&Error> {
+    fn source(&self) -> Option<&(Error + 'static)> {
         None
     }
 }
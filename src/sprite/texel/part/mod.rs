@@ -59,6 +59,35 @@ pub enum Part {
     WoolBall = 0x77,
 }
 
+/// The list of all Parts.
+static SPEC_PART_LIST: [Part; 25] = [
+    Part::None,
+    Part::ArmLeft,
+    Part::ArmRight,
+    Part::Boobs,
+    Part::Clavicle,
+    Part::EarLeft,
+    Part::EarRight,
+    Part::EyeLeft,
+    Part::EyeRight,
+    Part::HairTop,
+    Part::HairLeft,
+    Part::HairRight,
+    Part::HandLeft,
+    Part::HandRight,
+    Part::Mouth,
+    Part::Tail,
+    Part::Bell,
+    Part::ExclamationMark,
+    Part::ExclamationMarks,
+    Part::Heart,
+    Part::Hearts,
+    Part::Lantern,
+    Part::QuestionMark,
+    Part::QuestionMarks,
+    Part::WoolBall,
+];
+
 impl Part {
     pub fn new(part: &str) -> Result<Self> {
         match part {
@@ -93,6 +122,29 @@ impl Part {
         }
     }
 
+    /// The function `list` returns all the Parts.
+    pub fn list() -> &'static [Part] {
+        &SPEC_PART_LIST
+    }
+
+    /// The function `mirror` returns the part of the other side,
+    /// or the same part if it hasn't side.
+    pub fn mirror(&self) -> Part {
+        match *self {
+            Part::ArmLeft => Part::ArmRight,
+            Part::ArmRight => Part::ArmLeft,
+            Part::EarLeft => Part::EarRight,
+            Part::EarRight => Part::EarLeft,
+            Part::EyeLeft => Part::EyeRight,
+            Part::EyeRight => Part::EyeLeft,
+            Part::HairLeft => Part::HairRight,
+            Part::HairRight => Part::HairLeft,
+            Part::HandLeft => Part::HandRight,
+            Part::HandRight => Part::HandLeft,
+            other => other,
+        }
+    }
+
     pub fn not_empty(&self) -> Option<&Part> {
         match *self {
             Part::None => None,
@@ -0,0 +1,80 @@
+use std::error::Error;
+use std::fmt;
+
+use super::super::emotion::EmotionError;
+use super::super::sprite::texel::part::PartError;
+
+pub type Result<T> = ::std::result::Result<T, TemplateError>;
+
+
+/// The enum `TemplateError` defines the possible errors
+/// from constructor Template.
+#[derive(Clone, Debug)]
+pub enum TemplateError {
+    /// The name isn't a word of letters and digits.
+    UnvalidName(String),
+    /// Unvalid template syntax.
+    SyntaxTemplate(String),
+    /// The template isn't defined.
+    UnknownTemplate(String),
+    /// The expanded draws overflow the sprite.
+    Overflow(String),
+    /// The frames of the expanded sprite aren't its templates anymore.
+    EditedSprite(String),
+    /// The Part interface has meet an error.
+    Part(PartError),
+    /// The Emotion interface has meet an error.
+    Emotion(EmotionError),
+}
+
+impl fmt::Display for TemplateError {
+    /// The function `fmt` formats the value using
+    /// the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TemplateError::UnvalidName(ref name) => {
+                write!(f, "the name `{}` isn't a word of letters and digits", name)
+            },
+            TemplateError::SyntaxTemplate(ref line) => {
+                write!(f, "the template line `{}` is malformed", line)
+            },
+            TemplateError::UnknownTemplate(ref name) => {
+                write!(f, "the template `{}` isn't defined", name)
+            },
+            TemplateError::Overflow(ref name) => {
+                write!(f, "the frames of the template `{}` overflow the sprite", name)
+            },
+            TemplateError::EditedSprite(ref name) => {
+                write!(f, "the frames of the sprite `{}` are edited, only the base draws are saved", name)
+            },
+            TemplateError::Part(_) => write!(f, "the part of the template is invalid"),
+            TemplateError::Emotion(_) => write!(f, "the emotion of the template is invalid"),
+        }
+    }
+}
+
+impl Error for TemplateError {
+    /// The function `description` returns a short description of
+    /// the error.
+    fn description(&self) -> &str {
+        match *self {
+            TemplateError::UnvalidName(ref name) => name,
+            TemplateError::SyntaxTemplate(ref line) => line,
+            TemplateError::UnknownTemplate(ref name) => name,
+            TemplateError::Overflow(ref name) => name,
+            TemplateError::EditedSprite(ref name) => name,
+            TemplateError::Part(_) => "The Part interface has meet an error.",
+            TemplateError::Emotion(_) => "The Emotion interface has meet an error.",
+        }
+    }
+
+    /// The function `source` returns the lower-level source of
+    /// this error if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            TemplateError::Part(ref why) => Some(why),
+            TemplateError::Emotion(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
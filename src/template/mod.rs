@@ -0,0 +1,266 @@
+mod err;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Read, Write};
+use std::io;
+use std::ops::Not;
+use std::str::FromStr;
+
+pub use self::err::{TemplateError, Result};
+
+use super::emotion::Emotion;
+use super::grammar;
+use super::sheet::Sheet;
+use super::sprite::Sprite;
+use super::sprite::draw::Draw;
+use super::sprite::texel::Texel;
+use super::sprite::texel::part::Part;
+use super::tuple::Tuple;
+use super::GraphicError;
+
+/// The keyword before the duration of a step.
+pub const SPEC_KEYWORD_FOR: &'static str = " for ";
+/// The keyword before the period of a template.
+pub const SPEC_KEYWORD_EVERY: &'static str = " every ";
+
+/// The struct `Step` is a draw of template, the emotion by part
+/// held for a duration in milliseconds.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Step {
+    tuples: Vec<Tuple>,
+    duration: i64,
+}
+
+impl Step {
+    /// The constructor `new` returns a step from its list of emotion
+    /// by part and its duration.
+    pub fn new(tuples: &[Tuple], duration: i64) -> Self {
+        Step {
+            tuples: tuples.to_vec(),
+            duration: duration,
+        }
+    }
+
+    /// The accessor method `get_tuples` returns the emotion by part.
+    pub fn get_tuples(&self) -> &Vec<Tuple> {
+        &self.tuples
+    }
+
+    /// The accessor method `get_duration` returns the duration
+    /// in milliseconds.
+    pub fn get_duration(&self) -> i64 {
+        self.duration
+    }
+}
+
+impl fmt::Display for Step {
+    /// The function `fmt` formats the step like in a template file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}{}",
+               self.tuples.iter()
+                          .map(|&Tuple { part, emotion }| format!("{:?}:{:?}", part, emotion))
+                          .collect::<Vec<String>>()
+                          .join(", "),
+               SPEC_KEYWORD_FOR,
+               self.duration)
+    }
+}
+
+impl FromStr for Step {
+    type Err = TemplateError;
+
+    /// The function `from_str` parses a step like
+    /// `EyeLeft:Sleepy, EyeRight:Sleepy for 120`.
+    fn from_str(step: &str) -> ::std::result::Result<Self, Self::Err> {
+        match step.rfind(SPEC_KEYWORD_FOR).map(|position| step.split_at(position)) {
+            None => Err(TemplateError::SyntaxTemplate(step.to_string())),
+            Some((tuples, duration)) => {
+                match duration[SPEC_KEYWORD_FOR.len()..].trim().parse::<i64>() {
+                    Ok(duration) if duration > 0 => {
+                        tuples.split(',')
+                              .map(str::trim)
+                              .filter(|pair| pair.is_empty().not())
+                              .map(|pair|
+                                   match pair.split(':').collect::<Vec<&str>>().as_slice() {
+                                       &[part, emotion] => {
+                                           match (Part::new(part), Emotion::new(emotion)) {
+                                               (Err(why), _) => Err(TemplateError::Part(why)),
+                                               (_, Err(why)) => Err(TemplateError::Emotion(why)),
+                                               (Ok(part), Ok(emotion)) => {
+                                                   Ok(Tuple::from((part, emotion)))
+                                               },
+                                           }
+                                       },
+                                       _ => Err(TemplateError::SyntaxTemplate(step.to_string())),
+                                   })
+                              .collect::<Result<Vec<Tuple>>>()
+                              .map(|tuples| Step::new(&tuples, duration))
+                    },
+                    _ => Err(TemplateError::SyntaxTemplate(step.to_string())),
+                }
+            },
+        }
+    }
+}
+
+/// The struct `Template` is a named sequence of steps which is
+/// applied on a base draw to generate the frames, like
+/// `Blink = EyeLeft:Sleepy, EyeRight:Sleepy for 120 every 3000`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Template {
+    name: String,
+    steps: Vec<Step>,
+    /// The period in milliseconds of the base draw with the steps.
+    every: Option<i64>,
+}
+
+impl Template {
+    /// The constructor `new` returns a template from a name of letters
+    /// and digits with its steps and its period.
+    pub fn new(name: &str, steps: Vec<Step>, every: Option<i64>) -> Result<Self> {
+        if name.is_empty() || name.chars().all(char::is_alphanumeric).not() {
+            Err(TemplateError::UnvalidName(name.to_string()))
+        } else if steps.is_empty() {
+            Err(TemplateError::SyntaxTemplate(name.to_string()))
+        } else {
+            Ok(Template {
+                name: name.to_string(),
+                steps: steps,
+                every: every,
+            })
+        }
+    }
+
+    /// The accessor method `get_name` returns the name.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The accessor method `get_steps` returns the steps.
+    pub fn get_steps(&self) -> &Vec<Step> {
+        &self.steps
+    }
+
+    /// The accessor method `get_every` returns the period
+    /// in milliseconds.
+    pub fn get_every(&self) -> Option<i64> {
+        self.every
+    }
+
+    /// The accessor method `get_duration` returns the duration
+    /// of the steps in milliseconds.
+    pub fn get_duration(&self) -> i64 {
+        self.steps.iter()
+                  .map(|step| step.duration)
+                  .sum()
+    }
+
+    /// The method `expand` returns the frames of the template on the base
+    /// draw with the texels of its posture, the base draw held until the end
+    /// of the period, or for its own duration, followed by the steps.
+    pub fn expand(&self, base: &Draw, texels: &HashMap<Tuple, Vec<Texel>>) -> Vec<Draw> {
+        let hold: i64 = self.every.map_or(base.get_duration().num_milliseconds(),
+                                          |every| every - self.get_duration());
+        let mut frames: Vec<Draw> = Vec::with_capacity(self.steps.len() + 1);
+
+        if hold > 0 {
+            let mut draw: Draw = *base;
+
+            draw.set_duration(hold);
+            frames.push(draw);
+        }
+        frames.extend(self.steps.iter().map(|step| {
+            let mut draw: Draw = *base;
+
+            step.tuples.iter().all(|tuple| {
+                draw.set_part_emotion(&tuple.part, &tuple.emotion, texels.get(tuple));
+                true
+            });
+            draw.set_duration(step.duration);
+            draw
+        }));
+        frames
+    }
+
+    /// The method `expand_sprite` returns the sprite with the frames
+    /// of the template expanded from each of its draws, with the
+    /// texels by posture.
+    pub fn expand_sprite(&self,
+                         sprite: &Sprite,
+                         texels: &HashMap<Sheet, HashMap<Tuple, Vec<Texel>>>,
+    ) -> Result<Sprite> {
+        let empty: HashMap<Tuple, Vec<Texel>> = HashMap::new();
+        let mut expanded: Sprite = sprite.clone();
+
+        expanded.set_template(&[], &[]);
+        while expanded.remove_draw(0).is_some() {}
+        if sprite.into_iter()
+                 .flat_map(|draw|
+                      self.expand(draw, texels.get(draw.get_posture()).unwrap_or(&empty)))
+                 .all(|frame| expanded.insert_draw(frame).is_some()) {
+            Ok(expanded)
+        } else {
+            Err(TemplateError::Overflow(self.name.clone()))
+        }
+    }
+}
+
+impl fmt::Display for Template {
+    /// The function `fmt` formats the template as a line of template file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}",
+               self.name,
+               self.steps.iter()
+                         .map(|step| step.to_string())
+                         .collect::<Vec<String>>()
+                         .join("; "))
+            .and(self.every.map_or(Ok(()), |every|
+                      write!(f, "{}{}", SPEC_KEYWORD_EVERY, every)))
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    /// The function `from_str` parses a line of template file.
+    fn from_str(line: &str) -> ::std::result::Result<Self, Self::Err> {
+        match line.find('=').map(|position| line.split_at(position)) {
+            None => Err(TemplateError::SyntaxTemplate(line.to_string())),
+            Some((name, steps)) => {
+                let steps: &str = &steps[1..];
+                let (steps, every): (&str, Result<Option<i64>>) =
+                    match steps.rfind(SPEC_KEYWORD_EVERY) {
+                        None => (steps, Ok(None)),
+                        Some(position) => (
+                            &steps[..position],
+                            match steps[position + SPEC_KEYWORD_EVERY.len()..].trim()
+                                                                          .parse::<i64>() {
+                                Ok(every) if every > 0 => Ok(Some(every)),
+                                _ => Err(TemplateError::SyntaxTemplate(line.to_string())),
+                            },
+                        ),
+                    };
+
+                every.and_then(|every|
+                    steps.split(';')
+                         .map(str::trim)
+                         .filter(|step| step.is_empty().not())
+                         .map(|step| step.parse::<Step>())
+                         .collect::<Result<Vec<Step>>>()
+                         .and_then(|steps| Template::new(name.trim(), steps, every)))
+            },
+        }
+    }
+}
+
+/// The function `read_templates` returns the templates of a
+/// template file from a readable source.
+pub fn read_templates<R: Read>(source: R) -> ::Result<Vec<Template>> {
+    grammar::definitions(source, GraphicError::Template)
+}
+
+/// The function `write_templates` writes a line by template.
+pub fn write_templates<W: Write>(output: W, templates: &[Template]) -> io::Result<()> {
+    grammar::write_definitions(output, templates)
+}
@@ -0,0 +1,50 @@
+use std::error::Error;
+use std::fmt;
+
+use super::super::sheet::SheetError;
+
+pub type Result<T> = ::std::result::Result<T, TransitionError>;
+
+
+/// The enum `TransitionError` defines the possible errors
+/// from constructor Transition.
+#[derive(Clone, Debug)]
+pub enum TransitionError {
+    /// Unvalid transition syntax.
+    SyntaxTransition(String),
+    /// The Sheet interface has meet an error.
+    Sheet(SheetError),
+}
+
+impl fmt::Display for TransitionError {
+    /// The function `fmt` formats the value using
+    /// the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TransitionError::SyntaxTransition(ref line) => {
+                write!(f, "the transition line `{}` is malformed", line)
+            },
+            TransitionError::Sheet(_) => write!(f, "the posture of the transition is invalid"),
+        }
+    }
+}
+
+impl Error for TransitionError {
+    /// The function `description` returns a short description of
+    /// the error.
+    fn description(&self) -> &str {
+        match *self {
+            TransitionError::SyntaxTransition(ref line) => line,
+            TransitionError::Sheet(_) => "The Sheet interface has meet an error.",
+        }
+    }
+
+    /// The function `source` returns the lower-level source of
+    /// this error if any.
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            TransitionError::Sheet(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+mod err;
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::ops::Not;
+use std::str::FromStr;
+
+pub use self::err::{TransitionError, Result};
+
+use super::emotion::Emotion;
+use super::sheet::Sheet;
+use super::sprite::draw::{Draw, SPEC_MAX_XY};
+use super::sprite::texel::Texel;
+use super::grammar;
+use super::GraphicError;
+
+/// The default number of draws of a crossfade.
+pub const SPEC_CROSSFADE_STEP: usize = 4;
+/// The default duration in milliseconds of a draw of crossfade.
+pub const SPEC_CROSSFADE_DURATION: i64 = 80;
+/// The step between two cells in the order of a crossfade,
+/// coprime with the number of cells to visit every cell once.
+pub const SPEC_CROSSFADE_SCATTER: usize = 17;
+
+/// The enum `Kind` defines how a transition goes from a posture to another.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// Plays the draws of the intermediate sprite.
+    Sprite(Sheet),
+    /// Replaces the cells of the last draw by the cells of the first draw
+    /// of the target, in the positive number of draws of the positive
+    /// duration.
+    Crossfade { step: usize, duration: i64 },
+}
+
+/// The struct `Transition` declares the kind of transition from
+/// a posture to another, like `SeizaNormal -> LyingSleepy: SeizaSleepy`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Transition {
+    from: Sheet,
+    to: Sheet,
+    kind: Kind,
+}
+
+impl Transition {
+    /// The constructor `new` returns the transition from the posture
+    /// to the target posture.
+    pub fn new(from: Sheet, to: Sheet, kind: Kind) -> Self {
+        Transition {
+            from: from,
+            to: to,
+            kind: kind,
+        }
+    }
+
+    /// The accessor method `get_from` returns the current posture.
+    pub fn get_from(&self) -> &Sheet {
+        &self.from
+    }
+
+    /// The accessor method `get_to` returns the target posture.
+    pub fn get_to(&self) -> &Sheet {
+        &self.to
+    }
+
+    /// The accessor method `get_kind` returns the kind of transition.
+    pub fn get_kind(&self) -> &Kind {
+        &self.kind
+    }
+
+    /// The method `is_between` returns true if the transition goes
+    /// from the posture to the target posture.
+    pub fn is_between(&self, from: &Sheet, to: &Sheet) -> bool {
+        self.from.eq(from) && self.to.eq(to)
+    }
+}
+
+impl fmt::Display for Transition {
+    /// The function `fmt` formats the transition as a line of transition file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            Kind::Sprite(sheet) => write!(f, "{} -> {}: {}", self.from, self.to, sheet),
+            Kind::Crossfade { step, duration } => {
+                write!(f, "{} -> {}: Crossfade {} {}", self.from, self.to, step, duration)
+            },
+        }
+    }
+}
+
+impl FromStr for Transition {
+    type Err = TransitionError;
+
+    /// The function `from_str` parses a line of transition file.
+    fn from_str(line: &str) -> ::std::result::Result<Self, Self::Err> {
+        let line: String = line.replace("->", " -> ");
+        let words: Vec<&str> = line.split(|c| " :".contains(c))
+                                   .filter(|word| word.is_empty().not())
+                                   .collect::<Vec<&str>>();
+        let kind: Option<Result<Kind>> = match words.as_slice() {
+            &[_, "->", _, "Crossfade"] => Some(Ok(Kind::Crossfade {
+                step: SPEC_CROSSFADE_STEP,
+                duration: SPEC_CROSSFADE_DURATION,
+            })),
+            &[_, "->", _, "Crossfade", step, duration] => {
+                match (step.parse::<usize>(), duration.parse::<i64>()) {
+                    (Ok(step), Ok(duration)) if step > 0 && duration > 0 => {
+                        Some(Ok(Kind::Crossfade {
+                            step: step,
+                            duration: duration,
+                        }))
+                    },
+                    _ => None,
+                }
+            },
+            &[_, "->", _, sheet] => {
+                Some(Sheet::new(sheet).map(Kind::Sprite).map_err(TransitionError::Sheet))
+            },
+            _ => None,
+        };
+
+        match (kind, words.first(), words.get(2)) {
+            (Some(kind), Some(from), Some(to)) => {
+                match (Sheet::new(from), Sheet::new(to), kind) {
+                    (Err(why), _, _) | (_, Err(why), _) => Err(TransitionError::Sheet(why)),
+                    (_, _, Err(why)) => Err(why),
+                    (Ok(from), Ok(to), Ok(kind)) => Ok(Transition::new(from, to, kind)),
+                }
+            },
+            _ => Err(TransitionError::SyntaxTransition(line.trim().to_string())),
+        }
+    }
+}
+
+/// The function `crossfade` returns the draws which replace step by step
+/// the cells of the draw by the cells of the target draw, the posture
+/// of a draw is the posture of most of its cells.
+pub fn crossfade(from: &Draw, to: &Draw, step: usize, duration: i64) -> Vec<Draw> {
+    (1..step + 1).filter_map(|count| {
+        let threshold: usize = count * SPEC_MAX_XY / (step + 1);
+        let posture: &Sheet = if threshold * 2 < SPEC_MAX_XY {
+            from.get_posture()
+        } else {
+            to.get_posture()
+        };
+
+        (0..SPEC_MAX_XY).map(|index|
+                             if (index * SPEC_CROSSFADE_SCATTER) % SPEC_MAX_XY < threshold {
+                                 to.get_cell(index)
+                             } else {
+                                 from.get_cell(index)
+                             }.cloned())
+                        .collect::<Option<Vec<(Emotion, Texel)>>>()
+                        .and_then(|cells| Draw::new(posture, duration, &cells).ok())
+    })
+    .collect::<Vec<Draw>>()
+}
+
+/// The function `read_transitions` returns the transitions of a
+/// transition file from a readable source.
+pub fn read_transitions<R: Read>(source: R) -> ::Result<Vec<Transition>> {
+    grammar::definitions(source, GraphicError::Transition)
+}
+
+/// The function `write_transitions` writes a line by transition.
+pub fn write_transitions<W: Write>(output: W, transitions: &[Transition]) -> io::Result<()> {
+    grammar::write_definitions(output, transitions)
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::{SPEC_EXT_NCT, SPEC_EXT_NCS};
+
+/// The default interval between two polls of the sub-directories.
+pub const SPEC_WATCH_INTERVAL: u64 = 500;
+
+/// The struct `Watcher` polls the modification time of the files
+/// from the texel and sprite sub-directories.
+#[derive(Clone, Debug)]
+pub struct Watcher {
+    /// The texel sub-directory.
+    nct: PathBuf,
+    /// The sprite sub-directory.
+    ncs: PathBuf,
+    /// The last modification time by loaded file.
+    modified: HashMap<PathBuf, SystemTime>,
+    /// The modification time by changed file until it's loaded,
+    /// nothing for a removed file.
+    pending: HashMap<PathBuf, Option<SystemTime>>,
+    /// The minimal interval between two polls.
+    interval: Duration,
+    /// The time of the last poll.
+    last: Instant,
+}
+
+impl Watcher {
+    /// The constructor `new` returns a Watcher with the modification
+    /// time of the current files.
+    pub fn new<P: AsRef<Path>>(nct: P, ncs: P) -> Self {
+        let mut watcher: Watcher = Watcher {
+            nct: nct.as_ref().to_path_buf(),
+            ncs: ncs.as_ref().to_path_buf(),
+            modified: HashMap::new(),
+            pending: HashMap::new(),
+            interval: Duration::from_millis(SPEC_WATCH_INTERVAL),
+            last: Instant::now(),
+        };
+        let (nct, ncs): (Vec<PathBuf>, Vec<PathBuf>) = watcher.changed();
+
+        nct.iter().chain(ncs.iter()).all(|path| {
+            watcher.loaded(path);
+            true
+        });
+        watcher
+    }
+
+    /// The method `with_interval` sets the minimal interval in
+    /// milliseconds between two polls.
+    pub fn with_interval(mut self, interval: u64) -> Self {
+        self.interval = Duration::from_millis(interval);
+        self
+    }
+
+    /// The accessor method `get_nct` returns the texel sub-directory.
+    pub fn get_nct(&self) -> &Path {
+        &self.nct
+    }
+
+    /// The accessor method `get_ncs` returns the sprite sub-directory.
+    pub fn get_ncs(&self) -> &Path {
+        &self.ncs
+    }
+
+    /// The function `modified_from` returns the files of the directory
+    /// with the extension which are new, modified or removed since
+    /// their last load.
+    fn modified_from(&mut self, directory: &Path, extension: &str) -> Vec<PathBuf> {
+        let present: HashMap<PathBuf, SystemTime> =
+            fs::read_dir(directory).ok()
+                .map(|entry|
+                     entry.filter_map(|entry| entry.ok())
+                          .filter(|entry|
+                              entry.path().extension().eq(&Some(OsStr::new(extension))))
+                          .filter_map(|entry|
+                              entry.metadata().ok()
+                                   .and_then(|metadata| metadata.modified().ok())
+                                   .and_then(|time| Some((entry.path(), time))))
+                          .collect::<HashMap<PathBuf, SystemTime>>())
+                .unwrap_or_default();
+        let removed: Vec<PathBuf> = self.modified.keys()
+                                                 .filter(|path|
+                                                      path.parent().eq(&Some(directory)) &&
+                                                      present.contains_key(*path).not())
+                                                 .cloned()
+                                                 .collect::<Vec<PathBuf>>();
+        let changed: Vec<(PathBuf, Option<SystemTime>)> =
+            present.into_iter()
+                   .filter(|&(ref path, time)|
+                        self.modified.get(path).map_or(true, |last| last.ne(&time)))
+                   .map(|(path, time)| (path, Some(time)))
+                   .chain(removed.into_iter().map(|path| (path, None)))
+                   .collect::<Vec<(PathBuf, Option<SystemTime>)>>();
+
+        self.pending.extend(changed.iter().cloned());
+        let mut changed: Vec<PathBuf> = changed.into_iter()
+                                               .map(|(path, _)| path)
+                                               .collect::<Vec<PathBuf>>();
+
+        changed.sort();
+        changed
+    }
+
+    /// The method `loaded` keeps the modification time of a changed
+    /// file once it's loaded, or forgets a removed file once its
+    /// data is dropped; a file not loaded is changed again at the next poll.
+    pub fn loaded(&mut self, path: &Path) {
+        match self.pending.remove(path) {
+            Some(Some(time)) => {
+                self.modified.insert(path.to_path_buf(), time);
+            },
+            Some(None) => {
+                self.modified.remove(path);
+            },
+            None => {},
+        }
+    }
+
+    /// The method `changed` returns a couple of texel and sprite
+    /// files which are new, modified or removed since their last load.
+    pub fn changed(&mut self) -> (Vec<PathBuf>, Vec<PathBuf>) {
+        let (nct, ncs): (PathBuf, PathBuf) = (self.nct.clone(), self.ncs.clone());
+
+        self.last = Instant::now();
+        (self.modified_from(&nct, SPEC_EXT_NCT), self.modified_from(&ncs, SPEC_EXT_NCS))
+    }
+
+    /// The method `poll` returns the changed files like `changed`
+    /// if the interval is elapsed since the last poll.
+    pub fn poll(&mut self) -> Option<(Vec<PathBuf>, Vec<PathBuf>)> {
+        if self.last.elapsed() < self.interval {
+            None
+        } else {
+            match self.changed() {
+                (ref nct, ref ncs) if nct.is_empty() && ncs.is_empty() => None,
+                changed => Some(changed),
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+extern crate editeur;
+
+use std::env;
+use std::io::Cursor;
+use std::iter;
+use std::path::PathBuf;
+use std::process;
+
+use editeur::{Graphic, GraphicBuilder, SPEC_MAX_XY};
+use editeur::sheet::Sheet;
+
+/// The function `texel` returns a texel source with a glyph by cell
+/// of the empty pair.
+fn texel() -> String {
+    format!("None(\"{}\"):None [BustNormal]\n",
+            (0..SPEC_MAX_XY as u32).filter_map(|index| ::std::char::from_u32(0xe000 + index))
+                                   .collect::<String>())
+}
+
+/// The function `sprite` returns a sprite source of one draw
+/// of empty pairs.
+fn sprite() -> String {
+    format!("BustNormal 200\n{}\n",
+            iter::repeat("None:None").take(SPEC_MAX_XY)
+                                     .collect::<Vec<&str>>()
+                                     .join(" "))
+}
+
+#[test]
+fn graphic_from_cursors() {
+    let root: PathBuf = env::temp_dir().join(format!("editeur-cursor-{}", process::id()));
+    let sheet: Sheet = Sheet::new("BustNormal").unwrap();
+
+    env::set_var("NEKO_PATH", &root);
+    let graphic: Graphic = GraphicBuilder::new().texel(Cursor::new(texel()))
+                                                .sprite(sheet, Cursor::new(sprite()))
+                                                .build()
+                                                .unwrap();
+
+    assert_eq!(graphic.get_sprite(&sheet).unwrap().into_iter().count(), 1);
+    assert!(graphic.read_conflictfile(&sheet).unwrap().is_empty());
+    assert_eq!(graphic.get_ncs().unwrap(), root.join("sprites"));
+    assert!(root.exists().eq(&false));
+}
@@ -0,0 +1,149 @@
+#[macro_use]
+extern crate serde_json;
+
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+
+use serde_json::Value;
+
+/// The texel file of the root.
+const TEXEL: &'static str = "Mouth(\"\u{e300}\u{e301}\"):None [BustNormal]\n";
+
+/// The opened sprite, its second pair hasn't texel.
+const SPRITE: &'static str = "BustNormal 200\nMouth:None Mouth:Angry\n";
+
+/// The function `root` returns a new root with a texel file and a
+/// sprite file which the loader refuses.
+fn root(name: &str) -> PathBuf {
+    let root: PathBuf = env::temp_dir().join(format!("editeur-{}-{}", name, process::id()));
+
+    fs::create_dir_all(root.join("texels")).unwrap();
+    fs::create_dir_all(root.join("sprites")).unwrap();
+    fs::write(root.join("texels/BustNormal.cnt"), TEXEL).unwrap();
+    fs::write(root.join("sprites/BustNormal.cns"), "BustNormal abc\nMouth:None\n").unwrap();
+    root
+}
+
+/// The function `binary` returns the path of the editeur binary
+/// built beside the test.
+fn binary() -> PathBuf {
+    let mut path: PathBuf = env::current_exe().unwrap();
+
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.join("editeur")
+}
+
+/// The function `frame` returns a message with its header.
+fn frame(message: &Value) -> String {
+    let body: String = message.to_string();
+
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+/// The function `session` writes the messages to the server of the root
+/// then the exit notification, and returns the messages of the server.
+fn session(root: &PathBuf, messages: &[Value]) -> Vec<Value> {
+    let mut child = Command::new(binary()).arg("--root").arg(root)
+                                          .arg("lsp")
+                                          .stdin(Stdio::piped())
+                                          .stdout(Stdio::piped())
+                                          .spawn()
+                                          .unwrap();
+    {
+        let stdin = child.stdin.as_mut().unwrap();
+
+        for message in messages.iter()
+                               .chain(Some(json!({ "jsonrpc": "2.0", "method": "exit" })).iter()) {
+            stdin.write_all(frame(message).as_bytes()).unwrap();
+        }
+    }
+    let mut output: BufReader<_> = BufReader::new(child.stdout.take().unwrap());
+    let mut answers: Vec<Value> = Vec::new();
+    let mut header: String = String::new();
+
+    while output.read_line(&mut header).unwrap() > 0 {
+        if let Ok(length) = header.trim_left_matches("Content-Length:").trim().parse::<usize>() {
+            let mut body: Vec<u8> = vec![0; length];
+
+            output.read_line(&mut header).unwrap();
+            output.read_exact(&mut body).unwrap();
+            answers.push(serde_json::from_slice(&body).unwrap());
+        }
+        header.clear();
+    }
+    assert!(child.wait().unwrap().success());
+    answers
+}
+
+/// The function `request` returns a request of the document at the position.
+fn request(id: u64, method: &str, uri: &str, line: u64, character: u64) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": method,
+        "params": {
+            "textDocument": { "uri": uri },
+            "position": { "line": line, "character": character },
+        },
+    })
+}
+
+/// The function `answer` returns the result of the request.
+fn answer(answers: &[Value], id: u64) -> Value {
+    answers.iter()
+           .find(|answer| answer["id"].eq(&json!(id)))
+           .map(|answer| answer["result"].clone())
+           .unwrap()
+}
+
+#[test]
+fn lsp_session() {
+    let root: PathBuf = root("lsp");
+    let uri: String = format!("file://{}", root.join("sprites/BustNormal.cns").display());
+    let answers: Vec<Value> = session(&root, &[
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": uri, "text": SPRITE } },
+        }),
+        request(2, "textDocument/completion", &uri, 1, 0),
+        request(3, "textDocument/hover", &uri, 1, 2),
+        request(4, "textDocument/definition", &uri, 1, 2),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didSave",
+            "params": { "textDocument": { "uri": uri } },
+        }),
+        json!({ "jsonrpc": "2.0", "id": 5, "method": "shutdown" }),
+    ]);
+    let diagnostics: Vec<&Value> = answers.iter()
+                                          .filter(|answer|
+                                               answer["method"].eq("textDocument/publishDiagnostics"))
+                                          .collect::<Vec<&Value>>();
+
+    assert!(answer(&answers, 1)["capabilities"]["hoverProvider"].eq(&json!(true)));
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|diagnostic|
+        diagnostic["params"]["diagnostics"].as_array().unwrap()
+                                           .iter()
+                                           .any(|diagnostic|
+                                                diagnostic["range"]["start"]["line"].eq(&json!(1)) &&
+                                                diagnostic["message"].as_str().unwrap().contains("Angry"))));
+    assert!(answer(&answers, 2).as_array().unwrap()
+                               .iter()
+                               .any(|item| item["label"].eq("Mouth")));
+    assert!(answer(&answers, 3)["contents"]["value"].as_str().unwrap()
+                                                    .contains("glyph 1 of 2"));
+    assert!(answer(&answers, 4)["uri"].as_str().unwrap()
+                                      .ends_with("texels/BustNormal.cnt"));
+    assert_eq!(answer(&answers, 4)["range"]["start"]["line"], json!(0));
+    assert_eq!(answer(&answers, 5), Value::Null);
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,88 @@
+extern crate editeur;
+extern crate time;
+
+use editeur::machine::{self, Machine, State, Edge, Trigger};
+use editeur::sheet::Sheet;
+
+/// The machine file of a character which yawns when it's left alone.
+const MACHINE: &'static str = "\
+version 2
+# The first state is the initial state.
+Idle: BustNormal
+Yawn: BustHappy Smile
+Idle -> Yawn: idle 30000
+Yawn -> Idle: after 1000
+Idle -> Yawn: on poke
+";
+
+/// The function `tick` returns the name of the state reached by a tick.
+fn tick(machine: &mut Machine, ms: i64) -> Option<String> {
+    machine.tick(time::Duration::milliseconds(ms))
+           .map(|state| state.get_name().to_string())
+}
+
+#[test]
+fn machine_from_file() {
+    let machine: Machine = machine::read_machine(MACHINE.as_bytes()).unwrap();
+
+    assert_eq!(machine.get_states(), &vec![
+        State::new("Idle", Sheet::new("BustNormal").unwrap(), None),
+        State::new("Yawn", Sheet::new("BustHappy").unwrap(), Some("Smile")),
+    ]);
+    assert_eq!(machine.get_edges(), &vec![
+        Edge::new("Idle", "Yawn", Trigger::Idle(30000), 1),
+        Edge::new("Yawn", "Idle", Trigger::Timeout(1000), 1),
+        Edge::new("Idle", "Yawn", Trigger::Event("poke".to_string()), 1),
+    ]);
+    assert_eq!(machine.get_state().get_name(), "Idle");
+    assert!(machine::read_machine("Idle: BustNormal\nIdle -> Sleep: after 10\n".as_bytes())
+                    .is_err());
+    assert!(machine::read_machine("Idle -> Idle: sometime 10\n".as_bytes()).is_err());
+    assert!(machine::read_machine("# nothing\n".as_bytes()).is_err());
+}
+
+#[test]
+fn machine_idle_restarts() {
+    let mut machine: Machine = machine::read_machine(MACHINE.as_bytes()).unwrap();
+
+    assert_eq!(tick(&mut machine, 29999), None);
+    assert_eq!(tick(&mut machine, 1), Some("Yawn".to_string()));
+    assert_eq!(tick(&mut machine, 1000), Some("Idle".to_string()));
+    assert_eq!(tick(&mut machine, 1000), None);
+    assert_eq!(tick(&mut machine, 27999), None);
+    assert_eq!(tick(&mut machine, 1), Some("Yawn".to_string()));
+}
+
+#[test]
+fn machine_event() {
+    let mut machine: Machine = machine::read_machine(MACHINE.as_bytes()).unwrap();
+
+    assert_eq!(tick(&mut machine, 20000), None);
+    assert!(machine.send("nothing").is_none());
+    assert_eq!(machine.send("poke").map(|state| state.get_name().to_string()),
+               Some("Yawn".to_string()));
+    assert_eq!(tick(&mut machine, 1000), Some("Idle".to_string()));
+    assert_eq!(tick(&mut machine, 28000), None);
+    assert_eq!(tick(&mut machine, 1000), Some("Yawn".to_string()));
+}
+
+#[test]
+fn machine_weight() {
+    let normal: Sheet = Sheet::new("BustNormal").unwrap();
+    let mut machine: Machine = Machine::new(
+        vec![State::new("Idle", normal, None),
+             State::new("Left", normal, None),
+             State::new("Right", normal, None)],
+        vec![Edge::new("Idle", "Left", Trigger::Timeout(10), 1),
+             Edge::new("Idle", "Right", Trigger::Timeout(10), 0),
+             Edge::new("Left", "Idle", Trigger::Timeout(10), 1)],
+    ).unwrap();
+
+    machine.set_seed(42);
+    (0..10).all(|_| {
+        assert_eq!(tick(&mut machine, 10), Some("Left".to_string()));
+        assert_eq!(tick(&mut machine, 10), Some("Idle".to_string()));
+        true
+    });
+    assert!(Machine::new(Vec::new(), Vec::new()).is_err());
+}
@@ -0,0 +1,93 @@
+extern crate editeur;
+extern crate time;
+
+use std::env;
+use std::io::Cursor;
+use std::iter;
+use std::process;
+
+use editeur::{Graphic, GraphicBuilder, SPEC_MAX_XY};
+use editeur::machine::{self, Driver};
+use editeur::player::Player;
+use editeur::sheet::Sheet;
+
+/// The function `texel` returns a texel source with a glyph by cell
+/// of the empty pair of the posture.
+fn texel(posture: &str) -> String {
+    format!("None(\"{}\"):None [{}]\n",
+            (0..SPEC_MAX_XY as u32).filter_map(|index| ::std::char::from_u32(0xe000 + index))
+                                   .collect::<String>(),
+            posture)
+}
+
+/// The function `sprite` returns a sprite source of a draw of empty
+/// pairs by duration.
+fn sprite(posture: &str, durations: &[i64]) -> String {
+    format!("version 2\n{}",
+            durations.iter()
+                     .map(|duration|
+                          format!("{} {}\n{}\n",
+                                  posture, duration,
+                                  iter::repeat("None:None").take(SPEC_MAX_XY)
+                                                           .collect::<Vec<&str>>()
+                                                           .join(" ")))
+                     .collect::<Vec<String>>()
+                     .join("---\n"))
+}
+
+/// The function `graphic` returns a Graphic with a sprite of two
+/// draws for the normal posture and of one draw for the happy one.
+fn graphic() -> Graphic {
+    let (normal, happy): (Sheet, Sheet) = (Sheet::new("BustNormal").unwrap(),
+                                           Sheet::new("BustHappy").unwrap());
+
+    env::set_var("NEKO_PATH", env::temp_dir().join(format!("editeur-player-{}", process::id())));
+    GraphicBuilder::new().texel(Cursor::new(texel("BustNormal")))
+                         .texel(Cursor::new(texel("BustHappy")))
+                         .sprite(normal, Cursor::new(sprite("BustNormal", &[100, 200])))
+                         .sprite(happy, Cursor::new(sprite("BustHappy", &[300])))
+                         .build()
+                         .unwrap()
+}
+
+/// The function `duration` returns the duration of the draw played.
+fn duration(player: &Player) -> Option<i64> {
+    player.get_draw().map(|draw| draw.get_duration().num_milliseconds())
+}
+
+#[test]
+fn player_loop() {
+    let graphic: Graphic = graphic();
+    let mut player: Player = Player::new();
+
+    assert!(player.play(&graphic, &Sheet::new("BustAngry").unwrap(), None).is_none());
+    assert_eq!(duration(&player), None);
+    assert!(player.play(&graphic, &Sheet::new("BustNormal").unwrap(), None).is_some());
+    assert_eq!(duration(&player), Some(100));
+    assert!(player.tick(time::Duration::milliseconds(99)).eq(&false));
+    assert!(player.tick(time::Duration::milliseconds(1)));
+    assert_eq!(duration(&player), Some(200));
+    assert!(player.tick(time::Duration::milliseconds(150)).eq(&false));
+    assert!(player.tick(time::Duration::milliseconds(50)));
+    assert_eq!(duration(&player), Some(100));
+    assert!(player.tick(time::Duration::milliseconds(1000)));
+    assert!(duration(&player).is_some());
+}
+
+#[test]
+fn driver_states() {
+    let graphic: Graphic = graphic();
+    let mut driver: Driver = Driver::new(&graphic, machine::read_machine(
+        "Idle: BustNormal\nHappy: BustHappy\nAngry: BustAngry\n\
+         Idle -> Happy: on poke\nHappy -> Idle: after 300\nIdle -> Angry: on hit\n".as_bytes()
+    ).unwrap()).unwrap();
+
+    assert_eq!(driver.get_player().get_sheet(), Some(&Sheet::new("BustNormal").unwrap()));
+    assert!(driver.send(&graphic, "wave").unwrap().eq(&false));
+    assert!(driver.send(&graphic, "poke").unwrap());
+    assert_eq!(driver.get_player().get_sheet(), Some(&Sheet::new("BustHappy").unwrap()));
+    assert_eq!(driver.get_draw().map(|draw| draw.get_duration().num_milliseconds()), Some(300));
+    assert!(driver.tick(&graphic, time::Duration::milliseconds(300)).unwrap());
+    assert_eq!(driver.get_machine().get_state().get_name(), "Idle");
+    assert!(driver.send(&graphic, "hit").is_err());
+}